@@ -0,0 +1,364 @@
+/*
+ * SentinelBERT Collection Analytics
+ *
+ * This module summarizes collected posts for analysts who need an overview
+ * of a run rather than the individual posts.
+ *
+ * Author Summaries:
+ * `aggregate_by_author` groups posts by the anonymized author id and sums
+ * their engagement. Authors with a known follower count also get an
+ * influence score (UserProfile::calculate_influence_score), computed from
+ * the followers and the number of posts collected for them.
+ *
+ * Volume Over Time:
+ * `volume_over_time` bins posts into fixed-width buckets (a minute to
+ * several days). Buckets are aligned to multiples of the width since the
+ * Unix epoch in UTC, so daily buckets start at UTC midnight and a DST
+ * change never produces a 23- or 25-hour bucket. Empty buckets between the
+ * first and last post are included, keeping the series continuous.
+ *
+ * Hashtag Co-occurrence:
+ * `hashtag_cooccurrence` builds an undirected graph whose nodes are
+ * hashtags (lowercased, without `#`) and whose edge weights count the
+ * posts using both. A hashtag repeated within a post counts once, and
+ * posts with fewer than two distinct hashtags add nothing.
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use chrono::{DateTime, TimeZone, Utc}; // Author activity range, bucket bounds
+use serde::Serialize;                   // Analytics output as JSON
+use std::collections::{BTreeMap, BTreeSet, HashMap}; // Sorted sets and adjacency, grouping
+use std::time::Duration;                // Bucket width
+
+use crate::api_connectors::SocialPost;
+use crate::models::UserProfile;
+
+/// Activity of one (anonymized) author across the collected posts
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuthorSummary {
+    pub author_id_hash: String,
+
+    /// Username on the most recent post
+    pub username: String,
+
+    pub post_count: usize,
+    pub likes: u64,
+    pub shares: u64,
+    pub comments: u64,
+
+    /// likes + shares + comments
+    pub total_engagement: u64,
+
+    pub first_post_at: DateTime<Utc>,
+    pub last_post_at: DateTime<Utc>,
+
+    /// Platforms the author posted on, sorted
+    pub platforms: BTreeSet<String>,
+
+    /// Highest follower count seen, if any platform reports it
+    pub follower_count: Option<u64>,
+
+    /// Influence score, when the follower count is known and non-zero
+    /// and the latest post is from a known platform
+    pub influence_score: Option<f64>,
+}
+
+/// Posts created within one time bucket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TimeBucket {
+    /// Inclusive start
+    pub start: DateTime<Utc>,
+
+    /// Exclusive end
+    pub end: DateTime<Utc>,
+
+    pub post_count: usize,
+
+    /// Summed likes + shares + comments
+    pub engagement: u64,
+}
+
+/// Undirected hashtag co-occurrence graph
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CoOccurrenceGraph {
+    /// Neighbours of each hashtag with the edge weight; every edge appears
+    /// under both of its hashtags
+    pub adjacency: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+/// One edge of a CoOccurrenceGraph, with `a` < `b`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CoOccurrence {
+    pub a: String,
+    pub b: String,
+
+    /// Posts using both hashtags
+    pub weight: usize,
+}
+
+impl CoOccurrenceGraph {
+    /// Posts using both hashtags (0 if they never co-occur)
+    pub fn weight(&self, a: &str, b: &str) -> usize {
+        let (a, b) = (normalize_hashtag(a), normalize_hashtag(b));
+        self.adjacency.get(&a).and_then(|neighbours| neighbours.get(&b)).copied().unwrap_or(0)
+    }
+
+    /// The `n` heaviest edges, heaviest first (ties in hashtag order)
+    pub fn top_edges(&self, n: usize) -> Vec<CoOccurrence> {
+        let mut edges: Vec<CoOccurrence> = self.adjacency
+            .iter()
+            .flat_map(|(a, neighbours)| {
+                neighbours
+                    .iter()
+                    .filter(move |(b, _)| a < *b)
+                    .map(move |(b, weight)| CoOccurrence { a: a.clone(), b: b.clone(), weight: *weight })
+            })
+            .collect();
+
+        edges.sort_by(|x, y| y.weight.cmp(&x.weight).then_with(|| (&x.a, &x.b).cmp(&(&y.a, &y.b))));
+        edges.truncate(n);
+        edges
+    }
+}
+
+/// Summarize posts per author, most engaged authors first
+///
+/// Ties in engagement are broken by post count, then author id, so the
+/// order is deterministic.
+pub fn aggregate_by_author(posts: &[SocialPost]) -> Vec<AuthorSummary> {
+    let mut authors: HashMap<&str, AuthorSummary> = HashMap::new();
+
+    for post in posts {
+        let metrics = &post.metrics;
+        let summary = authors.entry(post.author.id_hash.as_str()).or_insert_with(|| AuthorSummary {
+            author_id_hash: post.author.id_hash.clone(),
+            username: post.author.username.clone(),
+            post_count: 0,
+            likes: 0,
+            shares: 0,
+            comments: 0,
+            total_engagement: 0,
+            first_post_at: post.created_at,
+            last_post_at: post.created_at,
+            platforms: BTreeSet::new(),
+            follower_count: None,
+            influence_score: None,
+        });
+
+        summary.post_count += 1;
+        summary.likes += metrics.likes;
+        summary.shares += metrics.shares;
+        summary.comments += metrics.comments;
+        summary.total_engagement += metrics.likes + metrics.shares + metrics.comments;
+        summary.first_post_at = summary.first_post_at.min(post.created_at);
+        if post.created_at >= summary.last_post_at {
+            summary.last_post_at = post.created_at;
+            summary.username = post.author.username.clone();
+        }
+        summary.platforms.insert(post.platform.clone());
+        summary.follower_count = summary.follower_count.max(post.author.follower_count);
+    }
+
+    let mut summaries: Vec<AuthorSummary> = authors.into_values().collect();
+    for summary in &mut summaries {
+        summary.influence_score = influence_score(summary, posts);
+    }
+
+    summaries.sort_by(|a, b| {
+        b.total_engagement
+            .cmp(&a.total_engagement)
+            .then(b.post_count.cmp(&a.post_count))
+            .then_with(|| a.author_id_hash.cmp(&b.author_id_hash))
+    });
+    summaries
+}
+
+/// Post counts and engagement per `bucket`-wide interval, oldest first
+///
+/// The width is truncated to whole seconds; a width under one second, or
+/// no posts, gives an empty series.
+pub fn volume_over_time(posts: &[SocialPost], bucket: Duration) -> Vec<TimeBucket> {
+    let width = bucket.as_secs() as i64;
+    if width == 0 || posts.is_empty() {
+        return Vec::new();
+    }
+
+    let index = |post: &SocialPost| post.created_at.timestamp().div_euclid(width);
+    let first = posts.iter().map(index).min().unwrap_or_default();
+    let last = posts.iter().map(index).max().unwrap_or_default();
+
+    let at = |index: i64| Utc.timestamp_opt(index * width, 0).single().unwrap_or_default();
+    let mut buckets: Vec<TimeBucket> = (first..=last)
+        .map(|i| TimeBucket { start: at(i), end: at(i + 1), post_count: 0, engagement: 0 })
+        .collect();
+
+    for post in posts {
+        let bucket = &mut buckets[(index(post) - first) as usize];
+        bucket.post_count += 1;
+        bucket.engagement += post.metrics.likes + post.metrics.shares + post.metrics.comments;
+    }
+
+    buckets
+}
+
+/// Graph of hashtags appearing together in the same post
+pub fn hashtag_cooccurrence(posts: &[SocialPost]) -> CoOccurrenceGraph {
+    let mut graph = CoOccurrenceGraph::default();
+
+    for post in posts {
+        let tags: BTreeSet<String> = post.hashtags
+            .iter()
+            .map(|tag| normalize_hashtag(tag))
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        if tags.len() < 2 {
+            continue;
+        }
+
+        for a in &tags {
+            let neighbours = graph.adjacency.entry(a.clone()).or_default();
+            for b in tags.iter().filter(|b| *b != a) {
+                *neighbours.entry(b.clone()).or_default() += 1;
+            }
+        }
+    }
+
+    graph
+}
+
+/// Hashtag as a graph node: lowercased, without `#`
+fn normalize_hashtag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// Influence score from the author's follower count and collected posts
+fn influence_score(summary: &AuthorSummary, posts: &[SocialPost]) -> Option<f64> {
+    let follower_count = summary.follower_count.filter(|count| *count > 0)?;
+    let latest = posts
+        .iter()
+        .filter(|post| post.author.id_hash == summary.author_id_hash)
+        .max_by_key(|post| post.created_at)?;
+
+    let profile = UserProfile {
+        id: summary.author_id_hash.clone(),
+        platform: latest.platform.parse().ok()?,
+        username: summary.username.clone(),
+        display_name: None,
+        bio: None,
+        follower_count,
+        following_count: 0,
+        post_count: summary.post_count as u64,
+        verified: latest.author.verified,
+        account_created: latest.author.account_created,
+        profile_image_url: None,
+        location: None,
+        website: None,
+        last_updated: Utc::now(),
+    };
+    Some(profile.calculate_influence_score())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils::post;
+
+    fn authored(id: &str, author: &str, platform: &str, hour: u32, likes: u64, shares: u64, comments: u64) -> SocialPost {
+        let mut post = post(id, "update");
+        post.author.id_hash = author.to_string();
+        post.author.username = format!("{}-{}", author, platform);
+        post.platform = platform.to_string();
+        post.created_at = Utc.with_ymd_and_hms(2024, 7, 8, hour, 0, 0).unwrap();
+        post.metrics.likes = likes;
+        post.metrics.shares = shares;
+        post.metrics.comments = comments;
+        post
+    }
+
+    #[test]
+    fn test_aggregate_by_author_across_platforms() {
+        let mut followed = authored("4", "bob", "twitter", 9, 3, 0, 0);
+        followed.author.follower_count = Some(5000);
+
+        let posts = vec![
+            authored("1", "alice", "twitter", 8, 10, 2, 1),
+            authored("2", "alice", "reddit", 11, 5, 0, 4),
+            authored("3", "bob", "reddit", 7, 1, 1, 1),
+            followed,
+            authored("5", "carol", "twitter", 10, 0, 0, 0),
+        ];
+
+        let summaries = aggregate_by_author(&posts);
+        let order: Vec<&str> = summaries.iter().map(|s| s.author_id_hash.as_str()).collect();
+        assert_eq!(order, vec!["alice", "bob", "carol"]);
+
+        let alice = &summaries[0];
+        assert_eq!((alice.post_count, alice.likes, alice.shares, alice.comments), (2, 15, 2, 5));
+        assert_eq!(alice.total_engagement, 22);
+        assert_eq!(alice.platforms, BTreeSet::from(["reddit".to_string(), "twitter".to_string()]));
+        assert_eq!((alice.first_post_at, alice.last_post_at), (posts[0].created_at, posts[1].created_at));
+        assert_eq!(alice.username, "alice-reddit");
+        assert_eq!(alice.influence_score, None);
+
+        let bob = &summaries[1];
+        assert_eq!((bob.post_count, bob.total_engagement, bob.follower_count), (2, 6, Some(5000)));
+        assert!(bob.influence_score.unwrap() > 0.0);
+
+        assert!(aggregate_by_author(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_volume_over_time_includes_empty_buckets() {
+        let at = |hour: u32, minute: u32| Utc.with_ymd_and_hms(2024, 3, 31, hour, minute, 0).unwrap();
+        let mut posts = vec![
+            authored("1", "alice", "twitter", 0, 4, 1, 0),
+            authored("2", "bob", "reddit", 0, 2, 0, 0),
+            authored("3", "carol", "twitter", 0, 0, 0, 3),
+        ];
+        // 00:59 and 01:00 straddle the EU DST switch, which must not matter in UTC
+        for (post, time) in posts.iter_mut().zip([at(0, 59), at(0, 5), at(2, 30)]) {
+            post.created_at = time;
+        }
+
+        let hourly = volume_over_time(&posts, Duration::from_secs(3600));
+        let series: Vec<(DateTime<Utc>, usize, u64)> = hourly.iter().map(|b| (b.start, b.post_count, b.engagement)).collect();
+        assert_eq!(series, vec![(at(0, 0), 2, 7), (at(1, 0), 0, 0), (at(2, 0), 1, 3)]);
+        assert_eq!(hourly[2].end, at(3, 0));
+
+        let daily = volume_over_time(&posts, Duration::from_secs(86_400));
+        assert_eq!((daily.len(), daily[0].start, daily[0].post_count), (1, at(0, 0), 3));
+
+        assert!(volume_over_time(&posts, Duration::ZERO).is_empty());
+    }
+
+    #[test]
+    fn test_hashtag_cooccurrence_weights() {
+        let tagged = |id: &str, tags: &[&str]| {
+            let mut post = post(id, "update");
+            post.hashtags = tags.iter().map(|tag| tag.to_string()).collect();
+            post
+        };
+        let posts = vec![
+            tagged("1", &["MumbaiRains", "flood", "Andheri"]),
+            tagged("2", &["mumbairains", "#Flood", "flood"]),
+            tagged("3", &["Flood", "Relief"]),
+            tagged("4", &["solo"]),
+        ];
+
+        let graph = hashtag_cooccurrence(&posts);
+        assert_eq!(graph.weight("mumbairains", "flood"), 2);
+        assert_eq!(graph.weight("#FLOOD", "MumbaiRains"), 2);
+        assert_eq!(graph.weight("flood", "andheri"), 1);
+        assert_eq!(graph.weight("flood", "relief"), 1);
+        assert_eq!(graph.weight("relief", "andheri"), 0);
+        assert_eq!(graph.weight("flood", "flood"), 0);
+        assert!(!graph.adjacency.contains_key("solo"));
+        assert_eq!(graph.adjacency["flood"].len(), 3);
+
+        let top = graph.top_edges(2);
+        assert_eq!(top[0], CoOccurrence { a: "flood".to_string(), b: "mumbairains".to_string(), weight: 2 });
+        assert_eq!((top[1].a.as_str(), top[1].b.as_str(), top[1].weight), ("andheri", "flood", 1));
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * =============================================================================
+ * Post Lookup Cache for SentinelBERT Connectors
+ * =============================================================================
+ *
+ * Investigations often look up the same post repeatedly (e.g. a viral tweet
+ * opened by several analysts). This cache keeps recently fetched posts in
+ * memory so repeat get_post_by_id calls don't spend rate-limit budget.
+ *
+ * Behaviour:
+ * - Keyed on (platform, post_id), so one cache can be shared by connectors
+ * - Entries expire after a TTL; least recently used entries are evicted
+ *   once the capacity is reached
+ * - Only found posts are cached; misses and errors always go to the API
+ *
+ * =============================================================================
+ */
+
+use super::SocialPost;
+use moka::sync::Cache;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Post cache settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PostCacheConfig {
+    /// Seconds a cached post stays valid
+    pub ttl_secs: u64,
+
+    /// Maximum number of cached posts
+    pub max_capacity: u64,
+}
+
+impl Default for PostCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: 300,
+            max_capacity: 10_000,
+        }
+    }
+}
+
+/// TTL-based LRU cache of posts fetched by id
+#[derive(Clone)]
+pub struct PostCache {
+    posts: Cache<(String, String), SocialPost>,
+}
+
+impl PostCache {
+    pub fn new(config: &PostCacheConfig) -> Self {
+        Self {
+            posts: Cache::builder()
+                .max_capacity(config.max_capacity)
+                .time_to_live(Duration::from_secs(config.ttl_secs))
+                .build(),
+        }
+    }
+
+    /// Cached post, if present and not expired
+    pub fn get(&self, platform: &str, post_id: &str) -> Option<SocialPost> {
+        self.posts.get(&(platform.to_string(), post_id.to_string()))
+    }
+
+    pub fn insert(&self, platform: &str, post_id: &str, post: SocialPost) {
+        self.posts.insert((platform.to_string(), post_id.to_string()), post);
+    }
+}
+
+impl Default for PostCache {
+    fn default() -> Self {
+        Self::new(&PostCacheConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils::post;
+
+    #[test]
+    fn test_entries_keyed_by_platform_and_expire() {
+        let cache = PostCache::new(&PostCacheConfig { ttl_secs: 1, max_capacity: 10 });
+        cache.insert("twitter", "42", post("42", "viral"));
+
+        assert_eq!(cache.get("twitter", "42").unwrap().content, "viral");
+        assert!(cache.get("reddit", "42").is_none());
+
+        std::thread::sleep(Duration::from_millis(1100));
+        assert!(cache.get("twitter", "42").is_none());
+    }
+}
@@ -0,0 +1,527 @@
+/*
+ * =============================================================================
+ * Imageboard (4chan-style) Connector for SentinelBERT
+ * =============================================================================
+ *
+ * This module implements a connector for public imageboards exposing the
+ * read-only 4chan JSON API (a.4cdn.org, and the many boards running
+ * compatible software). No credentials are needed.
+ *
+ * Read-only JSON API:
+ * - Board catalog: GET /{board}/catalog.json (pages of thread openers)
+ * - Thread: GET /{board}/thread/{no}.json (opener and every reply)
+ * - Images: {media_url}/{board}/{tim}{ext}
+ * - API rules: at most one request per second
+ *
+ * Searching:
+ * There is no server-side search. `search_posts` reads the catalog of each
+ * board (`set_boards`, or a comma-separated `boards` extra param) and
+ * keeps the threads whose subject or comment contains the query,
+ * case-insensitively. The thread openers are returned, with the thread's
+ * reply count as comments.
+ *
+ * Posts:
+ * - Ids are "{board}-{no}"; post numbers are only unique within a board
+ * - Content is the comment with its HTML stripped (line breaks kept);
+ *   the subject is kept under metadata["subject"]
+ * - Quote links (>>123) are recorded under metadata["quoted_posts"]
+ * - `get_post_by_id` reads the thread, so only thread openers resolve
+ *
+ * Privacy Compliance:
+ * - Posters with a tripcode are identified by the hashed tripcode
+ * - Everyone else is "anonymous", with no usable identity
+ * - Sensitive content detection on comments
+ *
+ * =============================================================================
+ */
+
+use super::*;
+use async_trait::async_trait;
+use chrono::TimeZone;
+use regex::Regex;
+use reqwest::Client;
+use scraper::Html;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+/// Shortest gap between two requests, per the API rules
+const MIN_REQUEST_SPACING: Duration = Duration::from_secs(1);
+
+/// Imageboard connector implementation
+pub struct ChanConnector {
+    /// HTTP client for API requests
+    client: Client,
+
+    /// Base URL of the JSON API
+    base_url: String,
+
+    /// Base URL images are served from
+    media_url: String,
+
+    /// Boards searched when a search names none
+    boards: Vec<String>,
+
+    /// Privacy configuration
+    privacy_config: PrivacyConfig,
+
+    /// When the last request was sent, for request spacing
+    last_request: tokio::sync::Mutex<Option<Instant>>,
+}
+
+/// One catalog page
+#[derive(Debug, Deserialize)]
+struct ChanCatalogPage {
+    #[serde(default)]
+    threads: Vec<ChanPost>,
+}
+
+/// Thread response
+#[derive(Debug, Deserialize)]
+struct ChanThread {
+    posts: Vec<ChanPost>,
+}
+
+/// A post, as it appears in both catalogs and threads
+#[derive(Debug, Deserialize)]
+struct ChanPost {
+    no: u64,
+
+    /// Thread the post replies to, 0 for a thread opener
+    #[serde(default)]
+    resto: u64,
+
+    time: Option<i64>,
+    trip: Option<String>,
+    sub: Option<String>,
+    com: Option<String>,
+
+    /// Replies in the thread (openers only)
+    replies: Option<u64>,
+
+    /// Images in the thread (openers only)
+    images: Option<u64>,
+
+    /// Upload time stamp naming the image, with its extension
+    tim: Option<u64>,
+    ext: Option<String>,
+    w: Option<u32>,
+    h: Option<u32>,
+    fsize: Option<u64>,
+    filename: Option<String>,
+
+    country: Option<String>,
+    sticky: Option<u8>,
+    closed: Option<u8>,
+}
+
+impl ChanConnector {
+    pub fn new(privacy_config: PrivacyConfig) -> Self {
+        Self::with_http_config(privacy_config, &HttpConfig::default())
+            .expect("Failed to create HTTP client")
+    }
+
+    /// Create a connector with custom HTTP client settings (timeouts, proxy)
+    pub fn with_http_config(
+        privacy_config: PrivacyConfig,
+        http: &HttpConfig,
+    ) -> Result<Self, ConnectorError> {
+        let client = http.client_builder()?
+            .build()
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        Ok(Self {
+            client,
+            base_url: "https://a.4cdn.org".to_string(),
+            media_url: "https://i.4cdn.org".to_string(),
+            boards: Vec::new(),
+            privacy_config,
+            last_request: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    /// Boards searched when `extra_params["boards"]` is absent
+    pub fn set_boards(&mut self, boards: Vec<String>) {
+        self.boards = boards;
+    }
+
+    /// Read from another site running the same API
+    pub fn set_base_urls(&mut self, base_url: &str, media_url: &str) {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self.media_url = media_url.trim_end_matches('/').to_string();
+    }
+
+    /// Boards a search covers
+    fn search_boards(&self, params: &SearchParams) -> Vec<String> {
+        let boards = match params.extra_params.get("boards") {
+            Some(boards) => boards.split(',').map(str::to_string).collect(),
+            None => self.boards.clone(),
+        };
+
+        boards
+            .iter()
+            .map(|board| board.trim().trim_matches('/').to_string())
+            .filter(|board| !board.is_empty())
+            .collect()
+    }
+
+    /// GET a JSON document, at most one request per second
+    ///
+    /// A 404 (deleted thread, unknown board) is None. `operation` labels
+    /// the request's latency metrics.
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, operation: &str, url: &str) -> Result<Option<T>, ConnectorError> {
+        {
+            let mut last_request = self.last_request.lock().await;
+            if let Some(wait) = last_request.and_then(|last| MIN_REQUEST_SPACING.checked_sub(last.elapsed())) {
+                tokio::time::sleep(wait).await;
+            }
+            *last_request = Some(Instant::now());
+        }
+
+        debug!("Imageboard request: {}", url);
+        let response = super::utils::send_timed(self.platform_name(), operation, self.client.get(url))
+            .await
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+
+            return Err(ConnectorError::ApiError {
+                code: status.as_u16(),
+                message: format!("Imageboard API error: {}", error_text),
+            });
+        }
+
+        response
+            .json()
+            .await
+            .map(Some)
+            .map_err(|e| ConnectorError::DeserializationError { message: e.to_string() })
+    }
+
+    /// Every post of a thread, opener first; empty if the thread is gone
+    pub async fn get_thread(&self, board: &str, thread_no: u64) -> Result<Vec<SocialPost>, ConnectorError> {
+        let url = format!("{}/{}/thread/{}.json", self.base_url, board, thread_no);
+        let thread: Option<ChanThread> = self.get_json("get_thread", &url).await?;
+
+        Ok(thread
+            .map(|thread| self.convert_thread(board, thread))
+            .unwrap_or_default())
+    }
+
+    /// Convert every post of a thread
+    fn convert_thread(&self, board: &str, thread: ChanThread) -> Vec<SocialPost> {
+        thread.posts
+            .iter()
+            .map(|post| self.convert_post_to_social_post(board, post))
+            .collect()
+    }
+
+    /// Convert an imageboard post to normalized SocialPost
+    fn convert_post_to_social_post(&self, board: &str, post: &ChanPost) -> SocialPost {
+        let id = format!("{}-{}", board, post.no);
+        let content = strip_comment(post.com.as_deref().unwrap_or_default());
+
+        // A tripcode is the only stable identity a poster can have
+        let author = match post.trip.as_deref().filter(|trip| !trip.is_empty()) {
+            Some(trip) => AuthorInfo {
+                id_hash: super::utils::anonymize_user_id(trip, &self.privacy_config),
+                username: trip.to_string(),
+                verified: false,
+                follower_count: None,
+                account_created: None,
+                account_type: Some("chan".to_string()),
+            },
+            None => AuthorInfo {
+                id_hash: super::utils::anonymize_user_id("anonymous", &self.privacy_config),
+                username: "anonymous".to_string(),
+                verified: false,
+                follower_count: None,
+                account_created: None,
+                account_type: Some("chan".to_string()),
+            },
+        };
+
+        let metrics = PostMetrics {
+            likes: 0,
+            shares: 0,
+            comments: post.replies.unwrap_or(0),
+            views: None,
+            engagement_rate: None, // Will be calculated later
+        };
+
+        let media = match (post.tim, &post.ext) {
+            (Some(tim), Some(ext)) => {
+                let media_type = if matches!(ext.as_str(), ".webm" | ".mp4") { "video" } else { "image" };
+                vec![MediaAttachment {
+                    media_type: media_type.to_string(),
+                    url: format!("{}/{}/{}{}", self.media_url, board, tim, ext),
+                    alt_text: post.filename.as_ref().map(|name| format!("{}{}", name, ext)),
+                    dimensions: post.w.zip(post.h).map(|(width, height)| MediaDimensions { width, height }),
+                    file_size: post.fsize,
+                }]
+            },
+            _ => Vec::new(),
+        };
+
+        let thread_no = if post.resto == 0 { post.no } else { post.resto };
+        let mut metadata = HashMap::new();
+        metadata.insert("board".to_string(), serde_json::json!(board));
+        metadata.insert("thread_id".to_string(), serde_json::json!(format!("{}-{}", board, thread_no)));
+        if let Some(subject) = post.sub.as_deref().map(strip_comment).filter(|subject| !subject.is_empty()) {
+            metadata.insert("subject".to_string(), serde_json::json!(subject));
+        }
+        let quoted = quoted_posts(&content);
+        if !quoted.is_empty() {
+            metadata.insert("quoted_posts".to_string(), serde_json::json!(quoted));
+        }
+        if let Some(images) = post.images {
+            metadata.insert("images".to_string(), serde_json::json!(images));
+        }
+        if let Some(country) = &post.country {
+            metadata.insert("country".to_string(), serde_json::json!(country));
+        }
+        if post.sticky == Some(1) {
+            metadata.insert("sticky".to_string(), serde_json::Value::Bool(true));
+        }
+        if post.closed == Some(1) {
+            metadata.insert("closed".to_string(), serde_json::Value::Bool(true));
+        }
+
+        let created_at = super::utils::resolve_created_at(
+            post.time.filter(|time| *time > 0).and_then(|time| Utc.timestamp_opt(time, 0).single()),
+            &id,
+            &mut metadata,
+        );
+
+        let privacy_flags = PrivacyFlags {
+            anonymized: true,
+            sensitive_content: super::utils::contains_sensitive_content(&content),
+            location_generalized: false, // Only a country flag at most
+            retention_policy: self.privacy_config.retention_policy.clone(),
+            consent_status: ConsentStatus::Implied, // Public boards imply consent
+        };
+
+        let mut social_post = SocialPost {
+            id,
+            platform: "chan".to_string(),
+            content: content.clone(),
+            author,
+            created_at,
+            metrics,
+            location: None,
+            language: None,
+            media,
+            hashtags: super::utils::extract_hashtags(&content),
+            mentions: Vec::new(),
+            urls: super::utils::extract_urls(&content),
+            metadata,
+            privacy_flags,
+        };
+
+        super::utils::assign_canonical_id(&mut social_post);
+        super::utils::apply_privacy_compliance(&mut social_post, &self.privacy_config);
+        social_post
+    }
+}
+
+/// Plain text of a comment: line breaks kept, tags dropped, entities decoded
+fn strip_comment(html: &str) -> String {
+    static BREAK: OnceLock<Regex> = OnceLock::new();
+    let line_break = BREAK.get_or_init(|| Regex::new(r"(?i)<br\s*/?>").unwrap());
+
+    let html = line_break.replace_all(html, "\n");
+    let text: String = Html::parse_fragment(&html).root_element().text().collect();
+    super::utils::normalize_text(&text)
+}
+
+/// Post numbers quoted with ">>123" in a comment
+fn quoted_posts(content: &str) -> Vec<u64> {
+    static QUOTE: OnceLock<Regex> = OnceLock::new();
+    let quote = QUOTE.get_or_init(|| Regex::new(r">>(\d+)").unwrap());
+
+    let mut quoted: Vec<u64> = quote
+        .captures_iter(content)
+        .filter_map(|captures| captures[1].parse().ok())
+        .collect();
+    quoted.dedup();
+    quoted
+}
+
+#[async_trait]
+impl ApiConnector for ChanConnector {
+    fn platform_name(&self) -> &'static str {
+        "chan"
+    }
+
+    fn is_configured(&self) -> bool {
+        // Public read-only API, nothing to configure
+        true
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_search: true,
+            supports_user_posts: false,
+            supports_trending: false,
+            supports_streaming: false,
+            supports_post_by_id: true,
+        }
+    }
+
+    async fn get_rate_limit_status(&self) -> Result<RateLimitInfo, ConnectorError> {
+        // The API only asks for one request per second, which is enforced client-side
+        Ok(RateLimitInfo {
+            remaining: 60,
+            limit: 60,
+            reset_time: Utc::now() + chrono::Duration::minutes(1),
+            window_duration: chrono::Duration::minutes(1),
+        })
+    }
+
+    async fn search_posts(&self, params: &SearchParams) -> Result<Vec<SocialPost>, ConnectorError> {
+        info!("Searching imageboard catalogs for: {}", params.query);
+
+        let boards = self.search_boards(params);
+        if boards.is_empty() {
+            return Err(ConnectorError::ConfigError {
+                message: "No imageboard boards configured to search".to_string(),
+            });
+        }
+
+        let needle = params.query.trim().to_lowercase();
+        let max_results = params.max_results.map(|max| max as usize);
+        let mut posts = Vec::new();
+
+        for board in &boards {
+            let url = format!("{}/{}/catalog.json", self.base_url, board);
+            let Some(pages) = self.get_json::<Vec<ChanCatalogPage>>("search", &url).await? else {
+                debug!("Board /{}/ has no catalog", board);
+                continue;
+            };
+
+            for thread in pages.iter().flat_map(|page| &page.threads) {
+                let text = format!(
+                    "{}\n{}",
+                    strip_comment(thread.sub.as_deref().unwrap_or_default()),
+                    strip_comment(thread.com.as_deref().unwrap_or_default()),
+                );
+                if text.to_lowercase().contains(&needle) {
+                    posts.push(self.convert_post_to_social_post(board, thread));
+                }
+            }
+        }
+
+        let mut posts = super::utils::filter_by_content_type(posts, params.content_type.as_ref());
+        posts.retain(|post| {
+            params.start_date.is_none_or(|start| post.created_at >= start)
+                && params.end_date.is_none_or(|end| post.created_at < end)
+        });
+        if let Some(max_results) = max_results {
+            posts.truncate(max_results);
+        }
+
+        info!("Retrieved {} threads from {} boards", posts.len(), boards.len());
+        Ok(posts)
+    }
+
+    async fn get_post_by_id(&self, post_id: &str) -> Result<Option<SocialPost>, ConnectorError> {
+        // "{board}-{no}"; boards never contain '-'
+        let Some((board, no)) = post_id.split_once('-') else {
+            return Ok(None);
+        };
+        let Ok(no) = no.parse::<u64>() else {
+            return Ok(None);
+        };
+
+        Ok(self.get_thread(board, no).await?.into_iter().next())
+    }
+
+    async fn get_user_posts(&self, _user_id: &str, _limit: Option<u32>) -> Result<Vec<SocialPost>, ConnectorError> {
+        Err(ConnectorError::Unsupported { platform: "chan".to_string(), operation: "get_user_posts" })
+    }
+
+    async fn get_trending_topics(&self, _location: Option<&str>) -> Result<Vec<String>, ConnectorError> {
+        Err(ConnectorError::Unsupported { platform: "chan".to_string(), operation: "get_trending_topics" })
+    }
+
+    async fn validate_credentials(&self) -> Result<bool, ConnectorError> {
+        // No credentials to check
+        Ok(true)
+    }
+
+    fn get_config_requirements(&self) -> Vec<ConfigRequirement> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THREAD: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/chan/thread.json"));
+
+    #[test]
+    fn test_thread_fixture_converts_to_posts() {
+        let privacy = PrivacyConfig::default();
+        let connector = ChanConnector::new(privacy.clone());
+        let thread: ChanThread = serde_json::from_str(THREAD).unwrap();
+
+        let posts = connector.convert_thread("news", thread);
+        assert_eq!(posts.len(), 3);
+
+        let opener = &posts[0];
+        assert_eq!(opener.id, "chan:news-570368");
+        assert_eq!(opener.platform, "chan");
+        assert_eq!(opener.content, "Dam gates opened upstream, river rising fast near the old bridge.\nStay away from the embankment & share updates here.");
+        assert_eq!(opener.metadata["subject"], "Flood thread");
+        assert_eq!(opener.metrics.comments, 2);
+        assert_eq!(opener.author.username, "!Ep8pui8Vw2");
+        assert_eq!(opener.author.id_hash, super::super::utils::anonymize_user_id("!Ep8pui8Vw2", &privacy));
+        assert_eq!(opener.created_at, Utc.timestamp_opt(1709294400, 0).unwrap());
+        assert_eq!(opener.media[0].url, "https://i.4cdn.org/news/1709294400123.jpg");
+        assert_eq!(opener.media[0].dimensions.as_ref().map(|d| (d.width, d.height)), Some((1280, 720)));
+
+        let reply = &posts[1];
+        assert_eq!(reply.author.username, "anonymous");
+        assert_eq!(reply.content, ">>570368\n>stay away\nAlready flooded on our street, see https://news.example/flood");
+        assert_eq!(reply.metadata["quoted_posts"], serde_json::json!([570368]));
+        assert_eq!(reply.metadata["thread_id"], "news-570368");
+        assert_eq!(reply.urls, vec!["https://news.example/flood"]);
+        assert!(reply.media.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_filters_catalog_by_query() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/news/catalog.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "page": 1, "threads": [
+                    { "no": 1, "resto": 0, "time": 1709294400, "sub": "FLOOD thread", "com": "updates", "replies": 40 },
+                    { "no": 2, "resto": 0, "time": 1709294500, "com": "Traffic on the bypass", "replies": 3 }
+                ]},
+                { "page": 2, "threads": [
+                    { "no": 3, "resto": 0, "time": 1709294600, "com": "Any flood relief camps<br>near the station?", "replies": 0 }
+                ]}
+            ])))
+            .mount(&server)
+            .await;
+
+        let mut connector = ChanConnector::new(PrivacyConfig::default());
+        connector.set_base_urls(&server.uri(), "https://i.4cdn.org");
+        connector.set_boards(vec!["/news/".to_string()]);
+
+        let posts = connector.search_posts(&super::super::test_utils::search_params("flood")).await.unwrap();
+        let ids: Vec<&str> = posts.iter().map(SocialPost::native_id).collect();
+        assert_eq!(ids, vec!["news-1", "news-3"]);
+        assert_eq!(posts[0].metrics.comments, 40);
+    }
+}
@@ -0,0 +1,256 @@
+/*
+ * =============================================================================
+ * Debug Capture of Raw Connector Traffic
+ * =============================================================================
+ *
+ * This module logs the raw requests connectors send and the bodies they get
+ * back, for debugging API quirks. It is off by default; enable it per
+ * connector with `[platforms.<name>.http.debug_capture]` or everywhere with
+ * SENTINEL_DEBUG_CAPTURE=1. Lines are logged at debug level under the
+ * `sentinel::http_capture` target, so RUST_LOG must include it.
+ *
+ * Redaction:
+ * - Authorization, Proxy-Authorization and Cookie header values are masked,
+ *   keeping only the scheme (e.g. "Basic ***")
+ * - access_token and refresh_token fields in response bodies are masked
+ * - Request bodies (which may hold passwords) are never logged
+ * - Usernames and user ids in URL paths (Reddit /user/{name}/..., Twitter
+ *   users/by/username/{name} and users/{id}) go through
+ *   `PrivacyConfig::log_identifier`, so they're hashed when
+ *   mask_identifiers_in_logs is on
+ * - Headers a client adds by default (e.g. Twitter's bearer token) are not
+ *   part of the request as built, so they don't appear at all
+ *
+ * Captured responses are read in full before being logged and handed back,
+ * so streaming endpoints don't go through the capture.
+ *
+ * Every request sent through `DebugCapture::send`, captured or not, has its
+ * latency recorded under the given operation (see metrics.rs).
+ *
+ * =============================================================================
+ */
+
+use super::*;
+use regex::Regex;
+use reqwest::{Request, RequestBuilder, Response, StatusCode};
+use std::sync::OnceLock;
+use tracing::debug;
+
+/// Environment variable enabling capture on every connector
+pub const DEBUG_CAPTURE_ENV: &str = "SENTINEL_DEBUG_CAPTURE";
+
+/// Headers whose values are masked in captured output
+const MASKED_HEADERS: [&str; 3] = ["authorization", "proxy-authorization", "cookie"];
+
+/// Path segments followed by a username or user id
+const USER_PATH_SEGMENTS: [&str; 4] = ["user", "u", "username", "users"];
+
+/// Debug capture settings for a connector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DebugCapture {
+    /// Log each request URL and response body at debug level
+    pub enabled: bool,
+
+    /// Longest part of a response body logged, in bytes
+    pub max_body_bytes: usize,
+}
+
+impl Default for DebugCapture {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_body_bytes: 2048,
+        }
+    }
+}
+
+impl DebugCapture {
+    /// Whether capture is on, from config or SENTINEL_DEBUG_CAPTURE
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+            || std::env::var(DEBUG_CAPTURE_ENV)
+                .is_ok_and(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+    }
+
+    /// Send a request, logging it and its response when capture is on, and
+    /// record its latency under `operation`
+    ///
+    /// User identifiers in the logged URL are rendered with `privacy`.
+    pub async fn send(
+        &self,
+        platform: &str,
+        operation: &str,
+        request: RequestBuilder,
+        privacy: &PrivacyConfig,
+    ) -> Result<Response, ConnectorError> {
+        let started = std::time::Instant::now();
+        let result = self.send_untimed(platform, request, privacy).await;
+        let status = result.as_ref().ok().map(|response| response.status());
+        super::utils::record_request_duration(platform, operation, status, started.elapsed());
+        result
+    }
+
+    async fn send_untimed(&self, platform: &str, request: RequestBuilder, privacy: &PrivacyConfig) -> Result<Response, ConnectorError> {
+        if !self.is_enabled() {
+            return request.send().await.map_err(|e| ConnectorError::NetworkError { source: e });
+        }
+
+        let (client, request) = request.build_split();
+        let request = request.map_err(|e| ConnectorError::NetworkError { source: e })?;
+        debug!(target: "sentinel::http_capture", platform, "{}", describe_request(&request, privacy));
+
+        let response = client.execute(request).await.map_err(|e| ConnectorError::NetworkError { source: e })?;
+        let status = response.status();
+        let version = response.version();
+        let headers = response.headers().clone();
+        let body = response.bytes().await.map_err(|e| ConnectorError::NetworkError { source: e })?;
+        debug!(target: "sentinel::http_capture", platform, "{}", describe_response(status, &body, self.max_body_bytes));
+
+        // Hand back an equivalent response for the caller to read
+        let mut rebuilt = hyper::http::Response::new(body);
+        *rebuilt.status_mut() = status;
+        *rebuilt.version_mut() = version;
+        *rebuilt.headers_mut() = headers;
+        Ok(Response::from(rebuilt))
+    }
+}
+
+/// Method, URL and headers of a request, with credentials and user
+/// identifiers masked
+pub fn describe_request(request: &Request, privacy: &PrivacyConfig) -> String {
+    let headers: Vec<String> = request.headers()
+        .iter()
+        .map(|(name, value)| {
+            let value = value.to_str().unwrap_or("<binary>");
+            if MASKED_HEADERS.contains(&name.as_str()) {
+                format!("{}: {}", name, mask_credential(value))
+            } else {
+                format!("{}: {}", name, value)
+            }
+        })
+        .collect();
+
+    format!("--> {} {} [{}]", request.method(), mask_user_path(request.url(), privacy), headers.join(", "))
+}
+
+/// URL with the username or user id after each user path segment replaced
+/// by its log identifier
+fn mask_user_path(url: &reqwest::Url, privacy: &PrivacyConfig) -> String {
+    let Some(segments) = url.path_segments() else {
+        return url.to_string();
+    };
+
+    let mut masked = Vec::new();
+    let mut previous: Option<&str> = None;
+    for segment in segments {
+        // Twitter's users/by/... and users/me aren't identifiers
+        let is_identifier = previous.is_some_and(|previous| USER_PATH_SEGMENTS.contains(&previous))
+            && !segment.is_empty()
+            && !matches!(segment, "by" | "me");
+        masked.push(if is_identifier { privacy.log_identifier(segment) } else { segment.to_string() });
+        previous = Some(segment);
+    }
+
+    // Splice the masked path in as text; set_path would percent-encode the '#'
+    let full = url.as_str();
+    let path_end = full.len()
+        - url.query().map_or(0, |query| query.len() + 1)
+        - url.fragment().map_or(0, |fragment| fragment.len() + 1);
+    let path_start = path_end - url.path().len();
+    format!("{}/{}{}", &full[..path_start], masked.join("/"), &full[path_end..])
+}
+
+/// Status and (truncated, token-masked) body of a response
+pub fn describe_response(status: StatusCode, body: &[u8], max_body_bytes: usize) -> String {
+    static TOKEN: OnceLock<Regex> = OnceLock::new();
+    let token = TOKEN.get_or_init(|| Regex::new(r#""(access_token|refresh_token)"\s*:\s*"[^"]*""#).unwrap());
+
+    let text = String::from_utf8_lossy(body);
+    let masked = token.replace_all(&text, r#""$1":"***""#);
+    let shown = super::utils::safe_slice(&masked, 0..max_body_bytes);
+    let elided = if shown.len() < masked.len() {
+        format!(" ... ({} bytes)", body.len())
+    } else {
+        String::new()
+    };
+
+    format!("<-- {} {}{}", status, shown, elided)
+}
+
+/// Keep the auth scheme, hide the credential
+fn mask_credential(value: &str) -> String {
+    match value.split_once(' ') {
+        Some((scheme, _)) => format!("{} ***", scheme),
+        None => "***".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_masks_credentials() {
+        let client = reqwest::Client::new();
+        let bearer = client.get("https://oauth.reddit.com/search?q=flood")
+            .bearer_auth("secret-access-token")
+            .header("user-agent", "SentinelBERT/1.0")
+            .build()
+            .unwrap();
+        let privacy = PrivacyConfig::default();
+        let described = describe_request(&bearer, &privacy);
+        assert_eq!(
+            described,
+            "--> GET https://oauth.reddit.com/search?q=flood [authorization: Bearer ***, user-agent: SentinelBERT/1.0]"
+        );
+
+        // Reddit's token request authenticates the app with Basic auth
+        let token_request = client.post("https://www.reddit.com/api/v1/access_token")
+            .basic_auth("client-id", Some("client-secret"))
+            .build()
+            .unwrap();
+        let described = describe_request(&token_request, &privacy);
+        assert!(described.contains("authorization: Basic ***"), "{}", described);
+        assert!(!described.contains("Y2xpZW50"), "{}", described);
+
+        let body = br#"{"access_token": "secret-access-token", "token_type": "bearer", "expires_in": 86400}"#;
+        assert_eq!(
+            describe_response(StatusCode::OK, body, 2048),
+            r#"<-- 200 OK {"access_token":"***", "token_type": "bearer", "expires_in": 86400}"#
+        );
+        assert_eq!(describe_response(StatusCode::OK, body, 5), r#"<-- 200 OK {"acc ... (84 bytes)"#);
+    }
+
+    #[test]
+    fn test_capture_masks_usernames_in_paths() {
+        let client = reqwest::Client::new();
+        let privacy = PrivacyConfig::default();
+        let masked = privacy.log_identifier("alice_investigated");
+
+        let reddit = client.get("https://oauth.reddit.com/user/alice_investigated/submitted?limit=25").build().unwrap();
+        assert_eq!(
+            describe_request(&reddit, &privacy),
+            format!("--> GET https://oauth.reddit.com/user/{}/submitted?limit=25 []", masked)
+        );
+
+        let twitter = client.get("https://api.twitter.com/2/users/by/username/alice_investigated").build().unwrap();
+        let described = describe_request(&twitter, &privacy);
+        assert_eq!(described, format!("--> GET https://api.twitter.com/2/users/by/username/{} []", masked));
+
+        let by_id = client.get("https://api.twitter.com/2/users/404404/tweets").build().unwrap();
+        let described = describe_request(&by_id, &privacy);
+        assert!(!described.contains("404404"), "{}", described);
+        assert!(described.ends_with("/tweets []"), "{}", described);
+
+        // Paths without user identifiers are logged as they are
+        let search = client.get("https://oauth.reddit.com/search?q=flood").build().unwrap();
+        assert_eq!(describe_request(&search, &privacy), "--> GET https://oauth.reddit.com/search?q=flood []");
+
+        let unmasked = PrivacyConfig { mask_identifiers_in_logs: false, ..PrivacyConfig::default() };
+        assert_eq!(
+            describe_request(&reddit, &unmasked),
+            "--> GET https://oauth.reddit.com/user/alice_investigated/submitted?limit=25 []"
+        );
+    }
+}
@@ -0,0 +1,747 @@
+/*
+ * =============================================================================
+ * Facebook Graph API Connector for SentinelBERT
+ * =============================================================================
+ *
+ * This module implements a connector for public Facebook pages (government
+ * bodies, organizations) through the Graph API.
+ *
+ * Graph API:
+ * - Page posts: GET /{page-id}/posts (the page's own posts)
+ * - Page feed: GET /{page-id}/feed (also includes visitor posts)
+ * - Results are paged with cursors; `paging.next` is followed until
+ *   `max_results` posts are collected or the page runs out
+ * - Reactions, comments and shares come back as summaries on each post
+ *
+ * Searching:
+ * The Graph API has no public keyword search, so `search_posts` reads the
+ * pages in `extra_params["page_ids"]` (comma-separated, falling back to the
+ * configured page ids) and keeps posts whose message matches a query term.
+ * `extra_params["edge"] = "feed"` reads the feed instead of posts.
+ *
+ * Authentication:
+ * - A page or user access token (FACEBOOK_ACCESS_TOKEN), or
+ * - An app id and secret, from which an app access token is requested
+ * - An expired token (error code 190) is dropped; app tokens are then
+ *   requested again and the call retried once
+ *
+ * Rate Limits:
+ * - Usage is reported as a percentage in x-page-usage / x-app-usage
+ *   headers, tracked for `get_rate_limit_status`
+ * - Error codes 4, 17, 32 and 613 mean the limit was reached
+ *
+ * Setup Instructions:
+ * 1. Create an app at https://developers.facebook.com/apps/
+ * 2. Request Page Public Content Access, or use a token of a page you manage
+ * 3. Add to environment:
+ *    FACEBOOK_ACCESS_TOKEN=your_page_access_token
+ *
+ * Privacy Compliance:
+ * - Poster ids are hashed for the author id
+ * - Sensitive content detection on post messages
+ *
+ * =============================================================================
+ */
+
+use super::*;
+use async_trait::async_trait;
+use reqwest::{Client, header::{HeaderMap, AUTHORIZATION}};
+use serde::Deserialize;
+use tracing::{info, warn, error, debug};
+
+/// Graph API version used when none is configured
+const DEFAULT_API_VERSION: &str = "v19.0";
+
+/// Most posts the Graph API returns per page
+const MAX_PAGE_SIZE: u32 = 100;
+
+/// Graph API error code for an expired or invalid access token
+const TOKEN_ERROR_CODE: i64 = 190;
+
+/// Graph API error codes for exhausted rate limits
+const RATE_LIMIT_ERROR_CODES: &[i64] = &[4, 17, 32, 613];
+
+/// Post fields requested from every endpoint
+const POST_FIELDS: &str = "id,message,created_time,permalink_url,from{id,name},\
+    reactions.summary(total_count).limit(0),comments.summary(total_count).limit(0),shares,\
+    attachments{media_type,url,media}";
+
+/// Facebook Graph API connector implementation
+pub struct FacebookConnector {
+    /// HTTP client for API requests
+    client: Client,
+
+    /// Configured page or user access token
+    access_token: String,
+
+    /// App credentials for requesting app access tokens
+    app_id: String,
+    app_secret: String,
+
+    /// App access token requested with the app credentials
+    app_token: tokio::sync::RwLock<Option<String>>,
+
+    /// Pages searched when a search names none
+    page_ids: Vec<String>,
+
+    /// Highest usage percentage reported by the last response
+    usage_percent: tokio::sync::RwLock<u32>,
+
+    /// Privacy configuration
+    privacy_config: PrivacyConfig,
+
+    /// Versioned Graph API base URL
+    base_url: String,
+}
+
+/// One page of results from an edge
+#[derive(Debug, Deserialize)]
+struct GraphPage {
+    #[serde(default)]
+    data: Vec<FacebookPost>,
+    #[serde(default)]
+    paging: Option<GraphPaging>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphPaging {
+    /// Absolute URL of the next page, absent on the last page
+    #[serde(default)]
+    next: Option<String>,
+}
+
+/// Error envelope returned with non-2xx responses
+#[derive(Debug, Deserialize)]
+struct GraphErrorResponse {
+    error: GraphError,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphError {
+    message: String,
+    #[serde(default)]
+    code: i64,
+}
+
+/// App access token response
+#[derive(Debug, Deserialize)]
+struct AppTokenResponse {
+    access_token: String,
+}
+
+/// Usage header payload (percentages of the hourly budget)
+#[derive(Debug, Deserialize)]
+struct GraphUsage {
+    #[serde(default)]
+    call_count: u32,
+    #[serde(default)]
+    total_cputime: u32,
+    #[serde(default)]
+    total_time: u32,
+}
+
+/// A page post or feed entry
+#[derive(Debug, Deserialize)]
+struct FacebookPost {
+    id: String,
+    #[serde(default)]
+    message: Option<String>,
+    created_time: String,
+    #[serde(default)]
+    permalink_url: Option<String>,
+    #[serde(default)]
+    from: Option<FacebookProfile>,
+    #[serde(default)]
+    reactions: Option<FacebookSummaryEdge>,
+    #[serde(default)]
+    comments: Option<FacebookSummaryEdge>,
+    #[serde(default)]
+    shares: Option<FacebookShares>,
+    #[serde(default)]
+    attachments: Option<FacebookAttachments>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FacebookProfile {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FacebookSummaryEdge {
+    summary: FacebookSummary,
+}
+
+#[derive(Debug, Deserialize)]
+struct FacebookSummary {
+    #[serde(default)]
+    total_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct FacebookShares {
+    #[serde(default)]
+    count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct FacebookAttachments {
+    #[serde(default)]
+    data: Vec<FacebookAttachment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FacebookAttachment {
+    #[serde(default)]
+    media_type: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    media: Option<FacebookMedia>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FacebookMedia {
+    #[serde(default)]
+    image: Option<FacebookImage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FacebookImage {
+    src: String,
+    #[serde(default)]
+    width: Option<u32>,
+    #[serde(default)]
+    height: Option<u32>,
+}
+
+impl FacebookConnector {
+    pub fn new(access_token: String, privacy_config: PrivacyConfig) -> Self {
+        Self::with_http_config(access_token, privacy_config, &HttpConfig::default())
+            .expect("Failed to create HTTP client")
+    }
+
+    /// Create a connector with custom HTTP client settings (timeouts, proxy)
+    pub fn with_http_config(
+        access_token: String,
+        privacy_config: PrivacyConfig,
+        http: &HttpConfig,
+    ) -> Result<Self, ConnectorError> {
+        let client = http.client_builder()?
+            .build()
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        Ok(Self {
+            client,
+            access_token,
+            app_id: String::new(),
+            app_secret: String::new(),
+            app_token: tokio::sync::RwLock::new(None),
+            page_ids: Vec::new(),
+            usage_percent: tokio::sync::RwLock::new(0),
+            privacy_config,
+            base_url: format!("https://graph.facebook.com/{}", DEFAULT_API_VERSION),
+        })
+    }
+
+    /// Request app access tokens from these credentials when no access token is set
+    pub fn set_app_credentials(&mut self, app_id: String, app_secret: String) {
+        self.app_id = app_id;
+        self.app_secret = app_secret;
+    }
+
+    /// Pages searched when `extra_params["page_ids"]` is absent
+    pub fn set_page_ids(&mut self, page_ids: Vec<String>) {
+        self.page_ids = page_ids;
+    }
+
+    /// Use another Graph API version (e.g. "v20.0")
+    pub fn set_api_version(&mut self, version: &str) {
+        self.base_url = format!("https://graph.facebook.com/{}", version);
+    }
+
+    fn has_app_credentials(&self) -> bool {
+        !self.app_id.is_empty() && !self.app_secret.is_empty()
+    }
+
+    /// The configured token, or an app access token requested on first use
+    async fn get_access_token(&self) -> Result<String, ConnectorError> {
+        if !self.access_token.is_empty() {
+            return Ok(self.access_token.clone());
+        }
+        if let Some(token) = self.app_token.read().await.as_ref() {
+            return Ok(token.clone());
+        }
+        if !self.has_app_credentials() {
+            return Err(ConnectorError::ConfigError {
+                message: "Facebook needs an access token or app credentials".to_string(),
+            });
+        }
+
+        info!("Requesting new Facebook app access token");
+        let url = format!("{}/oauth/access_token", self.base_url);
+        let request = self.client
+            .get(&url)
+            .query(&[
+                ("client_id", self.app_id.as_str()),
+                ("client_secret", self.app_secret.as_str()),
+                ("grant_type", "client_credentials"),
+            ]);
+        let response = super::utils::send_timed(self.platform_name(), "auth", request)
+            .await
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(ConnectorError::AuthenticationFailed {
+                message: format!("Facebook app token request failed: {}", error_text),
+            });
+        }
+
+        let token: AppTokenResponse = response
+            .json()
+            .await
+            .map_err(|e| ConnectorError::DeserializationError { message: e.to_string() })?;
+
+        *self.app_token.write().await = Some(token.access_token.clone());
+        Ok(token.access_token)
+    }
+
+    /// GET a Graph API URL, re-authenticating once if an app token expired
+    ///
+    /// `operation` labels the request's latency metrics.
+    async fn graph_get<T: for<'de> Deserialize<'de>>(&self, operation: &str, url: &str) -> Result<T, ConnectorError> {
+        match self.graph_get_once(operation, url).await {
+            Err(ConnectorError::InvalidCredentials) if self.access_token.is_empty() && self.has_app_credentials() => {
+                warn!("Facebook app token rejected, requesting a new one");
+                *self.app_token.write().await = None;
+                self.graph_get_once(operation, url).await
+            },
+            result => result,
+        }
+    }
+
+    async fn graph_get_once<T: for<'de> Deserialize<'de>>(&self, operation: &str, url: &str) -> Result<T, ConnectorError> {
+        let token = self.get_access_token().await?;
+        let request = self.client
+            .get(url)
+            .header(AUTHORIZATION, format!("Bearer {}", token));
+        let response = super::utils::send_timed(self.platform_name(), operation, request)
+            .await
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        self.update_usage(response.headers()).await;
+
+        let status = response.status();
+        let text = response.text().await.map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        if !status.is_success() {
+            return Err(match serde_json::from_str::<GraphErrorResponse>(&text) {
+                Ok(GraphErrorResponse { error }) if error.code == TOKEN_ERROR_CODE => {
+                    error!("Facebook access token expired or invalid: {}", error.message);
+                    ConnectorError::InvalidCredentials
+                },
+                Ok(GraphErrorResponse { error }) if RATE_LIMIT_ERROR_CODES.contains(&error.code) => {
+                    ConnectorError::RateLimitExceeded { reset_time: Utc::now() + chrono::Duration::hours(1) }
+                },
+                Ok(GraphErrorResponse { error }) => ConnectorError::ApiError {
+                    code: status.as_u16(),
+                    message: format!("Facebook API error {}: {}", error.code, error.message),
+                },
+                Err(_) => ConnectorError::ApiError {
+                    code: status.as_u16(),
+                    message: format!("Facebook API error: {}", text),
+                },
+            });
+        }
+
+        serde_json::from_str(&text).map_err(|e| ConnectorError::DeserializationError { message: e.to_string() })
+    }
+
+    /// Record the highest percentage reported by the usage headers
+    async fn update_usage(&self, headers: &HeaderMap) {
+        let usage = ["x-page-usage", "x-app-usage"]
+            .iter()
+            .filter_map(|name| headers.get(*name)?.to_str().ok())
+            .filter_map(|value| serde_json::from_str::<GraphUsage>(value).ok())
+            .map(|usage| usage.call_count.max(usage.total_cputime).max(usage.total_time))
+            .max();
+
+        if let Some(usage) = usage {
+            *self.usage_percent.write().await = usage.min(100);
+        }
+    }
+
+    /// Read up to `limit` posts from a page edge, following cursors
+    async fn read_edge(
+        &self,
+        page_id: &str,
+        edge: &str,
+        limit: u32,
+        params: Option<&SearchParams>,
+    ) -> Result<Vec<FacebookPost>, ConnectorError> {
+        let mut url = format!(
+            "{}/{}/{}?fields={}&limit={}",
+            self.base_url,
+            urlencoding::encode(page_id),
+            edge,
+            urlencoding::encode(POST_FIELDS),
+            limit.clamp(1, MAX_PAGE_SIZE),
+        );
+        if let Some(start) = params.and_then(|p| p.start_date) {
+            url.push_str(&format!("&since={}", start.timestamp()));
+        }
+        if let Some(end) = params.and_then(|p| p.end_date) {
+            url.push_str(&format!("&until={}", end.timestamp()));
+        }
+
+        let mut posts = Vec::new();
+        let mut next = Some(url);
+        while let Some(url) = next.take() {
+            debug!("Facebook page request: {}", url);
+            let operation = if params.is_some() { "search" } else { "user_posts" };
+            let page: GraphPage = self.graph_get(operation, &url).await?;
+            let empty = page.data.is_empty();
+            posts.extend(page.data);
+
+            if posts.len() < limit as usize && !empty {
+                next = page.paging.and_then(|paging| paging.next);
+            }
+        }
+
+        posts.truncate(limit as usize);
+        Ok(posts)
+    }
+
+    /// Pages to search: `extra_params["page_ids"]`, else the configured ones
+    fn search_page_ids(&self, params: &SearchParams) -> Vec<String> {
+        match params.extra_params.get("page_ids") {
+            Some(ids) => ids.split(',').map(str::trim).filter(|id| !id.is_empty()).map(str::to_string).collect(),
+            None => self.page_ids.clone(),
+        }
+    }
+
+    /// Convert a Graph API post to normalized SocialPost
+    fn convert_post_to_social_post(&self, post: &FacebookPost) -> SocialPost {
+        let content = super::utils::normalize_text(post.message.as_deref().unwrap_or_default());
+
+        // Page posts carry "{page-id}_{post-id}" ids; the page is the poster
+        let (poster_id, poster_name) = match &post.from {
+            Some(from) => (from.id.clone(), from.name.clone().unwrap_or_else(|| from.id.clone())),
+            None => {
+                let page_id = post.id.split('_').next().unwrap_or(&post.id).to_string();
+                (page_id.clone(), page_id)
+            },
+        };
+
+        let author = AuthorInfo {
+            id_hash: super::utils::anonymize_user_id(&poster_id, &self.privacy_config),
+            username: poster_name,
+            verified: false,
+            follower_count: None, // Needs a separate page request
+            account_created: None,
+            account_type: Some("page".to_string()),
+        };
+
+        let metrics = PostMetrics {
+            likes: post.reactions.as_ref().map_or(0, |edge| edge.summary.total_count),
+            shares: post.shares.as_ref().map_or(0, |shares| shares.count),
+            comments: post.comments.as_ref().map_or(0, |edge| edge.summary.total_count),
+            views: None, // Only available to page admins through insights
+            engagement_rate: None, // Will be calculated later
+        };
+
+        let media = post.attachments.as_ref()
+            .map(|attachments| attachments.data.iter().filter_map(|attachment| {
+                let image = attachment.media.as_ref().and_then(|media| media.image.as_ref());
+                let url = image.map(|image| image.src.clone()).or_else(|| attachment.url.clone())?;
+                Some(MediaAttachment {
+                    media_type: attachment.media_type.clone().unwrap_or_else(|| "link".to_string()).to_lowercase(),
+                    url,
+                    alt_text: None,
+                    dimensions: image.and_then(|image| Some(MediaDimensions {
+                        width: image.width?,
+                        height: image.height?,
+                    })),
+                    file_size: None,
+                })
+            }).collect())
+            .unwrap_or_default();
+
+        // Graph timestamps look like 2024-03-01T12:00:00+0000
+        let created_at = DateTime::parse_from_str(&post.created_time, "%Y-%m-%dT%H:%M:%S%z")
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now());
+
+        let mentions = super::utils::extract_mentions(&content)
+            .into_iter()
+            .map(|mention| super::utils::anonymize_user_id(&mention, &self.privacy_config))
+            .collect();
+
+        let mut metadata = HashMap::new();
+        if let Some(permalink) = &post.permalink_url {
+            metadata.insert("permalink".to_string(), serde_json::Value::String(permalink.clone()));
+        }
+
+        let privacy_flags = PrivacyFlags {
+            anonymized: true,
+            sensitive_content: super::utils::contains_sensitive_content(&content),
+            location_generalized: false,
+            retention_policy: self.privacy_config.retention_policy.clone(),
+            consent_status: ConsentStatus::Implied, // Public pages imply consent
+        };
+
+        let mut social_post = SocialPost {
+            id: post.id.clone(),
+            platform: "facebook".to_string(),
+            content: content.clone(),
+            author,
+            created_at,
+            metrics,
+            location: None,
+            language: None,
+            media,
+            hashtags: super::utils::extract_hashtags(&content),
+            mentions,
+            urls: super::utils::extract_urls(&content),
+            metadata,
+            privacy_flags,
+        };
+
+        super::utils::assign_canonical_id(&mut social_post);
+        super::utils::apply_privacy_compliance(&mut social_post, &self.privacy_config);
+        social_post
+    }
+}
+
+#[async_trait]
+impl ApiConnector for FacebookConnector {
+    fn platform_name(&self) -> &'static str {
+        "facebook"
+    }
+
+    fn is_configured(&self) -> bool {
+        !self.access_token.is_empty() || self.has_app_credentials()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_search: true,
+            supports_user_posts: true,
+            supports_trending: false,
+            supports_streaming: false,
+            supports_post_by_id: true,
+        }
+    }
+
+    async fn get_rate_limit_status(&self) -> Result<RateLimitInfo, ConnectorError> {
+        // Usage is reported as a percentage of an hourly budget
+        let used = *self.usage_percent.read().await;
+        Ok(RateLimitInfo {
+            remaining: 100 - used,
+            limit: 100,
+            reset_time: Utc::now() + chrono::Duration::hours(1),
+            window_duration: chrono::Duration::hours(1),
+        })
+    }
+
+    async fn search_posts(&self, params: &SearchParams) -> Result<Vec<SocialPost>, ConnectorError> {
+        info!("Searching Facebook pages for: {}", params.query);
+
+        let page_ids = self.search_page_ids(params);
+        if page_ids.is_empty() {
+            return Err(ConnectorError::ConfigError {
+                message: "Facebook search needs page ids (extra_params[\"page_ids\"] or configured page_ids)".to_string(),
+            });
+        }
+        let edge = match params.extra_params.get("edge").map(String::as_str) {
+            Some("feed") => "feed",
+            _ => "posts",
+        };
+        let limit = params.max_results.unwrap_or(25);
+        let terms = super::utils::query_terms(&params.query);
+
+        let mut posts = Vec::new();
+        for page_id in &page_ids {
+            for post in self.read_edge(page_id, edge, limit, Some(params)).await? {
+                let post = self.convert_post_to_social_post(&post);
+                if terms.is_empty() || !super::utils::match_spans(&post.content, &terms).is_empty() {
+                    posts.push(post);
+                }
+            }
+        }
+
+        let posts = super::utils::filter_by_content_type(posts, params.content_type.as_ref());
+
+        info!("Retrieved {} posts from {} Facebook pages", posts.len(), page_ids.len());
+        Ok(posts)
+    }
+
+    async fn get_post_by_id(&self, post_id: &str) -> Result<Option<SocialPost>, ConnectorError> {
+        info!("Getting Facebook post by ID: {}", post_id);
+
+        let url = format!("{}/{}?fields={}", self.base_url, urlencoding::encode(post_id), urlencoding::encode(POST_FIELDS));
+        match self.graph_get::<FacebookPost>("get_by_id", &url).await {
+            Ok(post) => Ok(Some(self.convert_post_to_social_post(&post))),
+            Err(ConnectorError::ApiError { code: 404, .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn get_user_posts(&self, user_id: &str, limit: Option<u32>) -> Result<Vec<SocialPost>, ConnectorError> {
+        // Only pages are readable; user profiles aren't exposed
+        let posts = self.read_edge(user_id, "posts", limit.unwrap_or(25), None).await?;
+        Ok(posts.iter().map(|post| self.convert_post_to_social_post(post)).collect())
+    }
+
+    async fn get_trending_topics(&self, _location: Option<&str>) -> Result<Vec<String>, ConnectorError> {
+        Err(ConnectorError::Generic {
+            message: "Facebook Graph API does not provide trending topics".to_string(),
+        })
+    }
+
+    async fn validate_credentials(&self) -> Result<bool, ConnectorError> {
+        info!("Validating Facebook API credentials");
+
+        let url = format!("{}/me?fields=id", self.base_url);
+        let result = if self.access_token.is_empty() {
+            // App tokens have no /me; obtaining one proves the credentials
+            self.get_access_token().await.map(|_| ())
+        } else {
+            self.graph_get::<serde_json::Value>("validate", &url).await.map(|_| ())
+        };
+
+        match result {
+            Ok(()) => {
+                info!("Facebook API credentials are valid");
+                Ok(true)
+            },
+            Err(ConnectorError::InvalidCredentials) | Err(ConnectorError::AuthenticationFailed { .. }) => {
+                error!("Facebook API credentials are invalid");
+                Ok(false)
+            },
+            Err(e) => Err(e),
+        }
+    }
+
+    fn get_config_requirements(&self) -> Vec<ConfigRequirement> {
+        vec![
+            ConfigRequirement {
+                key: "FACEBOOK_ACCESS_TOKEN".to_string(),
+                description: "Facebook page or user access token with access to public page content".to_string(),
+                required: true,
+                example: Some("EAAB...".to_string()),
+                source_url: Some("https://developers.facebook.com/tools/explorer/".to_string()),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn params(query: &str, page_ids: &str) -> SearchParams {
+        SearchParams {
+            query: query.to_string(),
+            max_results: Some(10),
+            extra_params: HashMap::from([("page_ids".to_string(), page_ids.to_string())]),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_follows_cursors_and_maps_summaries() {
+        let server = MockServer::start().await;
+        let next = format!("{}/v19.0/1234/posts?after=CURSOR", server.uri());
+        Mock::given(method("GET"))
+            .and(path("/v19.0/1234/posts"))
+            .and(query_param("after", "CURSOR"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{
+                    "id": "1234_2", "message": "Road closures lifted", "created_time": "2024-03-01T09:00:00+0000"
+                }],
+                "paging": { "cursors": { "before": "B", "after": "C" } }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v19.0/1234/posts"))
+            .and(header("authorization", "Bearer page-token"))
+            .respond_with(ResponseTemplate::new(200)
+                .insert_header("x-page-usage", r#"{"call_count":12,"total_cputime":3,"total_time":5}"#)
+                .set_body_json(serde_json::json!({
+                    "data": [{
+                        "id": "1234_1",
+                        "message": "Flood warning issued for the river district",
+                        "created_time": "2024-03-01T12:00:00+0000",
+                        "permalink_url": "https://www.facebook.com/1234/posts/1",
+                        "from": { "id": "1234", "name": "City Disaster Management" },
+                        "reactions": { "data": [], "summary": { "total_count": 340 } },
+                        "comments": { "data": [], "summary": { "total_count": 27 } },
+                        "shares": { "count": 58 }
+                    }],
+                    "paging": { "cursors": { "before": "A", "after": "CURSOR" }, "next": next }
+                })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut connector = FacebookConnector::new("page-token".to_string(), PrivacyConfig::default());
+        connector.base_url = format!("{}/v19.0", server.uri());
+
+        // Both result pages are read; only the post matching the query is kept
+        let posts = connector.search_posts(&params("flood", "1234")).await.unwrap();
+
+        assert_eq!(posts.len(), 1);
+        let post = &posts[0];
+        assert_eq!((post.native_id(), post.platform.as_str()), ("1234_1", "facebook"));
+        assert_eq!(post.content, "Flood warning issued for the river district");
+        assert_eq!((post.metrics.likes, post.metrics.comments, post.metrics.shares), (340, 27, 58));
+        assert_eq!(post.created_at.to_rfc3339(), "2024-03-01T12:00:00+00:00");
+        assert_eq!(post.metadata["permalink"], "https://www.facebook.com/1234/posts/1");
+        assert_eq!(connector.get_rate_limit_status().await.unwrap().remaining, 88);
+    }
+
+    #[tokio::test]
+    async fn test_expired_app_token_is_renewed_once() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v19.0/oauth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "app|token", "token_type": "bearer"
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v19.0/1234_1"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "error": { "message": "Session has expired", "type": "OAuthException", "code": 190 }
+            })))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v19.0/1234_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "1234_1", "message": "Shelters open", "created_time": "2024-03-01T12:00:00+0000"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut connector = FacebookConnector::new(String::new(), PrivacyConfig::default());
+        connector.set_app_credentials("app".to_string(), "secret".to_string());
+        connector.base_url = format!("{}/v19.0", server.uri());
+
+        let post = connector.get_post_by_id("1234_1").await.unwrap().unwrap();
+        assert_eq!(post.content, "Shelters open");
+        assert_eq!(post.author.username, "1234");
+    }
+}
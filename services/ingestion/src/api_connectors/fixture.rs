@@ -0,0 +1,196 @@
+/*
+ * =============================================================================
+ * Fixture Connector for SentinelBERT
+ * =============================================================================
+ *
+ * This module implements a deterministic, network-free connector for
+ * end-to-end tests of the orchestrator, deduplication and storage layers.
+ * It serves posts from a directory of JSON files instead of a platform API.
+ *
+ * Fixture Layout:
+ * - Each `*.json` file holds a serialized `Vec<SocialPost>`
+ * - A search reads the file named after its query: lowercased, with every
+ *   run of non-alphanumeric characters replaced by `_`
+ *   (e.g. "Mumbai flood!" -> `mumbai_flood.json`)
+ * - A query without a file returns no posts, like a search with no hits
+ * - `get_post_by_id` looks through every file
+ *
+ * Like a real connector, searches honour max_results, content_type and the
+ * start/end dates. Files are loaded once, when the connector is created.
+ *
+ * Only compiled for tests or with the `test-fixtures` feature.
+ *
+ * =============================================================================
+ */
+
+use super::*;
+use async_trait::async_trait;
+use std::path::Path;
+use tracing::debug;
+
+/// Connector serving posts from fixture files
+pub struct FixtureConnector {
+    /// Posts per fixture name (file stem)
+    fixtures: HashMap<String, Vec<SocialPost>>,
+}
+
+impl FixtureConnector {
+    /// Load every `*.json` file in `directory`
+    ///
+    /// Fails with a ConfigError if the directory can't be read and with a
+    /// DeserializationError naming the file if one isn't a post list.
+    pub fn from_dir(directory: impl AsRef<Path>) -> Result<Self, ConnectorError> {
+        let directory = directory.as_ref();
+        let config_error = |e: std::io::Error| ConnectorError::ConfigError {
+            message: format!("Failed to read fixtures from {}: {}", directory.display(), e),
+        };
+
+        let mut fixtures = HashMap::new();
+        for entry in std::fs::read_dir(directory).map_err(config_error)? {
+            let path = entry.map_err(config_error)?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let text = std::fs::read_to_string(&path).map_err(config_error)?;
+            let posts: Vec<SocialPost> = serde_json::from_str(&text).map_err(|e| ConnectorError::DeserializationError {
+                message: format!("{}: {}", path.display(), e),
+            })?;
+            fixtures.insert(name.to_string(), posts);
+        }
+
+        Ok(Self { fixtures })
+    }
+
+    /// Fixture name a query maps to
+    pub fn fixture_name(query: &str) -> String {
+        query
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("_")
+    }
+}
+
+#[async_trait]
+impl ApiConnector for FixtureConnector {
+    fn platform_name(&self) -> &'static str {
+        "fixture"
+    }
+
+    fn is_configured(&self) -> bool {
+        true
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_post_by_id: true,
+            ..Capabilities::search_only()
+        }
+    }
+
+    async fn get_rate_limit_status(&self) -> Result<RateLimitInfo, ConnectorError> {
+        Ok(RateLimitInfo {
+            remaining: u32::MAX,
+            limit: u32::MAX,
+            reset_time: Utc::now(),
+            window_duration: chrono::Duration::minutes(1),
+        })
+    }
+
+    async fn search_posts(&self, params: &SearchParams) -> Result<Vec<SocialPost>, ConnectorError> {
+        let name = Self::fixture_name(&params.query);
+        let posts: Vec<SocialPost> = self.fixtures
+            .get(&name)
+            .into_iter()
+            .flatten()
+            .filter(|post| params.start_date.is_none_or(|start| post.created_at >= start))
+            .filter(|post| params.end_date.is_none_or(|end| post.created_at < end))
+            .cloned()
+            .collect();
+
+        let mut posts = super::utils::filter_by_content_type(posts, params.content_type.as_ref());
+        if let Some(limit) = params.max_results {
+            posts.truncate(limit as usize);
+        }
+
+        debug!("Fixture {} matched {} posts for {:?}", name, posts.len(), params.query);
+        Ok(posts)
+    }
+
+    async fn get_post_by_id(&self, post_id: &str) -> Result<Option<SocialPost>, ConnectorError> {
+        Ok(self.fixtures.values().flatten().find(|post| post.id == post_id).cloned())
+    }
+
+    async fn get_user_posts(&self, _user_id: &str, _limit: Option<u32>) -> Result<Vec<SocialPost>, ConnectorError> {
+        Err(ConnectorError::ConfigError {
+            message: "Fixture connector doesn't support user timelines".to_string(),
+        })
+    }
+
+    async fn get_trending_topics(&self, _location: Option<&str>) -> Result<Vec<String>, ConnectorError> {
+        Err(ConnectorError::ConfigError {
+            message: "Fixture connector doesn't provide trending topics".to_string(),
+        })
+    }
+
+    async fn validate_credentials(&self) -> Result<bool, ConnectorError> {
+        Ok(true)
+    }
+
+    fn get_config_requirements(&self) -> Vec<ConfigRequirement> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils::search_params;
+    use crate::dedup::DedupScope;
+    use crate::ingestion::IngestionService;
+    use crate::storage::InMemoryPostStore;
+    use std::sync::Arc;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/connector");
+
+    #[tokio::test]
+    async fn test_search_serves_fixture_with_filters() {
+        let connector = FixtureConnector::from_dir(FIXTURES).unwrap();
+        assert_eq!(FixtureConnector::fixture_name("Mumbai  flood!"), "mumbai_flood");
+
+        let ids = |posts: Vec<SocialPost>| posts.into_iter().map(|post| post.id).collect::<Vec<_>>();
+        let mut params = search_params("Mumbai flood");
+        assert_eq!(ids(connector.search_posts(&params).await.unwrap()), vec!["fx-1", "fx-2", "fx-3"]);
+
+        params.max_results = Some(2);
+        assert_eq!(ids(connector.search_posts(&params).await.unwrap()), vec!["fx-1", "fx-2"]);
+
+        params.max_results = None;
+        params.content_type = Some(ContentType::Image);
+        assert_eq!(ids(connector.search_posts(&params).await.unwrap()), vec!["fx-2"]);
+
+        assert!(connector.search_posts(&search_params("no such fixture")).await.unwrap().is_empty());
+        assert_eq!(connector.get_post_by_id("fx-3").await.unwrap().unwrap().metrics.likes, 95);
+        assert!(connector.get_post_by_id("missing").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fixture_drives_ingestion_end_to_end() {
+        let connector: Arc<dyn ApiConnector> = Arc::new(FixtureConnector::from_dir(FIXTURES).unwrap());
+        let store = Arc::new(InMemoryPostStore::new());
+        let service = IngestionService::new(vec![connector], store.clone(), DedupScope::PerRun);
+
+        let params = search_params("mumbai flood");
+        let first = service.ingest(&params).await.unwrap();
+        let second = service.ingest(&params).await.unwrap();
+
+        assert_eq!((first.platform("fixture").unwrap().count, first.stored), (3, 3));
+        assert_eq!((second.platform("fixture").unwrap().count, second.stored), (3, 0));
+        assert_eq!(store.posts().await.len(), 3);
+    }
+}
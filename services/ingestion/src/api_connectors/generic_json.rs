@@ -0,0 +1,529 @@
+/*
+ * =============================================================================
+ * Generic JSON Connector for SentinelBERT
+ * =============================================================================
+ *
+ * This module implements a configurable connector for bespoke REST APIs that
+ * return JSON (partner feeds, internal systems). Instead of writing a new
+ * connector per endpoint, a JsonMapping describes where each SocialPost
+ * field lives in the response.
+ *
+ * Path Syntax (JSONPath-like):
+ * - `$` is the document root and may be omitted
+ * - `.field` selects an object field
+ * - `[n]` selects an array element
+ * - e.g. `$.data.items`, `author.profile.handle`, `stats[0].likes`
+ *
+ * Timestamps:
+ * - Strings are parsed as RFC 3339
+ * - Numbers are treated as Unix seconds
+ *
+ * Setup Instructions:
+ * 1. Point `url` at the endpoint returning the records
+ * 2. Set `items_path` to the array of records in the response
+ * 3. Map at least `id` and `content`; other fields are optional
+ * 4. Add an auth header if the endpoint requires one
+ *
+ * =============================================================================
+ */
+
+use super::*;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
+/// Where each SocialPost field is found within a single record
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonMapping {
+    /// Path from the response root to the array of records (root if empty)
+    pub items_path: String,
+
+    /// Record id (required)
+    pub id: String,
+
+    /// Post text (required)
+    pub content: String,
+
+    /// Author identifier, hashed before storage
+    pub author_id: Option<String>,
+
+    /// Author display name
+    pub author_name: Option<String>,
+
+    /// Creation timestamp (RFC 3339 string or Unix seconds)
+    pub created_at: Option<String>,
+
+    /// Like/reaction count
+    pub likes: Option<String>,
+
+    /// Share/repost count
+    pub shares: Option<String>,
+
+    /// Comment/reply count
+    pub comments: Option<String>,
+
+    /// View count
+    pub views: Option<String>,
+
+    /// Language code
+    pub language: Option<String>,
+}
+
+/// Header injected into every request for authentication
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthHeader {
+    /// Header name, e.g. `Authorization` or `X-Api-Key`
+    pub name: String,
+
+    /// Header value, e.g. `Bearer <token>`
+    pub value: String,
+}
+
+/// Generic JSON connector configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenericJsonConfig {
+    /// Source name, recorded as the post platform
+    pub name: String,
+
+    /// Endpoint returning the records
+    pub url: String,
+
+    /// Query parameter carrying the search query (query not sent if absent)
+    pub query_param: Option<String>,
+
+    /// Query parameter carrying the result limit (limit not sent if absent)
+    pub limit_param: Option<String>,
+
+    /// Optional authentication header
+    pub auth: Option<AuthHeader>,
+
+    /// Field mapping for each record
+    pub mapping: JsonMapping,
+
+    /// HTTP client settings
+    #[serde(default)]
+    pub http: HttpConfig,
+}
+
+pub struct GenericJsonConnector {
+    client: Client,
+    config: GenericJsonConfig,
+    privacy_config: PrivacyConfig,
+}
+
+impl GenericJsonConnector {
+    /// Create a connector; fails if the HTTP settings (e.g. proxy) are invalid
+    pub fn new(config: GenericJsonConfig, privacy_config: PrivacyConfig) -> Result<Self, ConnectorError> {
+        let client = config.http.client_builder()?
+            .build()
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        Ok(Self { client, config, privacy_config })
+    }
+
+    /// Fetch the configured endpoint and return the parsed JSON body
+    async fn fetch(&self, operation: &str, query: &[(String, String)]) -> Result<Value, ConnectorError> {
+        let mut request = self.client.get(&self.config.url).query(query);
+
+        if let Some(auth) = &self.config.auth {
+            request = request.header(auth.name.as_str(), auth.value.as_str());
+        }
+
+        let response = super::utils::send_timed(self.platform_name(), operation, request)
+            .await
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        if !status.is_success() {
+            return Err(ConnectorError::ApiError {
+                code: status.as_u16(),
+                message: format!("{} API error: {}", self.config.name, body),
+            });
+        }
+
+        serde_json::from_str(&body).map_err(|e| ConnectorError::ParseError { source: e })
+    }
+
+    /// Map every record in a response into normalized posts
+    ///
+    /// Records missing the id or content are skipped with a warning rather
+    /// than failing the whole batch.
+    pub fn map_response(&self, response: &Value) -> Result<Vec<SocialPost>, ConnectorError> {
+        let items = select(response, &self.config.mapping.items_path)
+            .and_then(Value::as_array)
+            .ok_or_else(|| ConnectorError::ConfigError {
+                message: format!(
+                    "items_path '{}' does not point to an array in the {} response",
+                    self.config.mapping.items_path, self.config.name
+                ),
+            })?;
+
+        let posts: Vec<SocialPost> = items
+            .iter()
+            .filter_map(|record| {
+                let post = self.map_record(record);
+                if post.is_none() {
+                    warn!("Skipping {} record without id or content", self.config.name);
+                }
+                post
+            })
+            .collect();
+
+        Ok(posts)
+    }
+
+    /// Convert a single record using the configured mapping
+    fn map_record(&self, record: &Value) -> Option<SocialPost> {
+        let mapping = &self.config.mapping;
+
+        let id = select_string(record, &mapping.id)?;
+        let content = select_string(record, &mapping.content)?;
+
+        let field = |path: &Option<String>| path.as_deref().and_then(|p| select(record, p));
+        let count = |path: &Option<String>| field(path).and_then(value_as_u64);
+
+        let author_id = field(&mapping.author_id).and_then(value_as_string);
+        let author = AuthorInfo {
+            id_hash: super::utils::anonymize_user_id(
+                author_id.as_deref().unwrap_or("unknown"),
+                &self.privacy_config
+            ),
+            username: field(&mapping.author_name)
+                .and_then(value_as_string)
+                .unwrap_or_else(|| "unknown".to_string()),
+            verified: false,
+            follower_count: None,
+            account_created: None,
+            account_type: Some(self.config.name.clone()),
+        };
+
+        let metrics = PostMetrics {
+            likes: count(&mapping.likes).unwrap_or(0),
+            shares: count(&mapping.shares).unwrap_or(0),
+            comments: count(&mapping.comments).unwrap_or(0),
+            views: count(&mapping.views),
+            engagement_rate: None,
+        };
+
+        let created_at = field(&mapping.created_at)
+            .and_then(value_as_datetime)
+            .unwrap_or_else(Utc::now);
+
+        let hashtags = super::utils::extract_hashtags(&content);
+        let mentions = super::utils::extract_mentions(&content)
+            .into_iter()
+            .map(|mention| super::utils::anonymize_user_id(&mention, &self.privacy_config))
+            .collect();
+        let urls = super::utils::extract_urls(&content);
+
+        let mut metadata = HashMap::new();
+        metadata.insert("source".to_string(), Value::String(self.config.name.clone()));
+        metadata.insert("source_url".to_string(), Value::String(self.config.url.clone()));
+
+        let privacy_flags = PrivacyFlags {
+            anonymized: true,
+            sensitive_content: super::utils::contains_sensitive_content(&content),
+            location_generalized: false,
+            retention_policy: self.privacy_config.retention_policy.clone(),
+            consent_status: ConsentStatus::Unknown,
+        };
+
+        let mut post = SocialPost {
+            id,
+            platform: self.config.name.clone(),
+            content,
+            author,
+            created_at,
+            metrics,
+            location: None,
+            language: field(&mapping.language).and_then(value_as_string),
+            media: Vec::new(),
+            hashtags,
+            mentions,
+            urls,
+            metadata,
+            privacy_flags,
+        };
+
+        super::utils::assign_canonical_id(&mut post);
+        super::utils::apply_privacy_compliance(&mut post, &self.privacy_config);
+
+        Some(post)
+    }
+}
+
+/// Resolve a JSONPath-like expression against a value
+///
+/// Supports `$` (root), `.field` and `[index]` segments. Returns `None` if
+/// any segment is missing or the path is malformed.
+pub fn select<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+
+    let mut current = value;
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (field, indexes) = match segment.find('[') {
+            Some(pos) => segment.split_at(pos),
+            None => (segment, ""),
+        };
+
+        if !field.is_empty() {
+            current = current.get(field)?;
+        }
+
+        let mut rest = indexes;
+        while let Some(stripped) = rest.strip_prefix('[') {
+            let end = stripped.find(']')?;
+            let index: usize = super::utils::safe_slice(stripped, 0..end).trim().parse().ok()?;
+            current = current.get(index)?;
+            rest = super::utils::safe_slice(stripped, end + 1..stripped.len());
+        }
+
+        if !rest.is_empty() {
+            return None;
+        }
+    }
+
+    Some(current)
+}
+
+fn select_string(value: &Value, path: &str) -> Option<String> {
+    select(value, path).and_then(value_as_string)
+}
+
+/// Render scalars as strings so numeric ids map cleanly
+fn value_as_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Accept counts sent as numbers or numeric strings
+fn value_as_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64().or_else(|| n.as_f64().map(|f| f.max(0.0) as u64)),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn value_as_datetime(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::String(s) => DateTime::parse_from_rfc3339(s)
+            .map(|dt| dt.with_timezone(&Utc))
+            .ok(),
+        Value::Number(n) => n.as_i64().and_then(|secs| DateTime::from_timestamp(secs, 0)),
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl ApiConnector for GenericJsonConnector {
+    fn platform_name(&self) -> &'static str {
+        "generic_json"
+    }
+
+    fn is_configured(&self) -> bool {
+        !self.config.url.is_empty()
+            && !self.config.mapping.id.is_empty()
+            && !self.config.mapping.content.is_empty()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::search_only()
+    }
+
+    async fn get_rate_limit_status(&self) -> Result<RateLimitInfo, ConnectorError> {
+        // Arbitrary endpoints don't report limits; assume a conservative budget
+        Ok(RateLimitInfo {
+            remaining: 60,
+            limit: 60,
+            reset_time: Utc::now() + chrono::Duration::minutes(1),
+            window_duration: chrono::Duration::minutes(1),
+        })
+    }
+
+    async fn search_posts(&self, params: &SearchParams) -> Result<Vec<SocialPost>, ConnectorError> {
+        let mut query = Vec::new();
+        if let Some(param) = &self.config.query_param {
+            query.push((param.clone(), params.query.clone()));
+        }
+        if let (Some(param), Some(limit)) = (&self.config.limit_param, params.max_results) {
+            query.push((param.clone(), limit.to_string()));
+        }
+
+        debug!("{} request: {} {:?}", self.config.name, self.config.url, query);
+
+        let response = self.fetch("search", &query).await?;
+        let mut posts = self.map_response(&response)?;
+
+        if let Some(limit) = params.max_results {
+            posts.truncate(limit as usize);
+        }
+
+        info!("Retrieved {} posts from {}", posts.len(), self.config.name);
+        Ok(posts)
+    }
+
+    async fn get_post_by_id(&self, _post_id: &str) -> Result<Option<SocialPost>, ConnectorError> {
+        Err(ConnectorError::ConfigError {
+            message: "Generic JSON connector doesn't support post lookup".to_string(),
+        })
+    }
+
+    async fn get_user_posts(&self, _user_id: &str, _limit: Option<u32>) -> Result<Vec<SocialPost>, ConnectorError> {
+        Err(ConnectorError::ConfigError {
+            message: "Generic JSON connector doesn't support user timelines".to_string(),
+        })
+    }
+
+    async fn get_trending_topics(&self, _location: Option<&str>) -> Result<Vec<String>, ConnectorError> {
+        Err(ConnectorError::ConfigError {
+            message: "Generic JSON connector doesn't provide trending topics".to_string(),
+        })
+    }
+
+    async fn validate_credentials(&self) -> Result<bool, ConnectorError> {
+        match self.fetch("validate", &[]).await {
+            Ok(_) => Ok(true),
+            Err(ConnectorError::ApiError { code: 401, .. }) | Err(ConnectorError::ApiError { code: 403, .. }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn get_config_requirements(&self) -> Vec<ConfigRequirement> {
+        vec![
+            ConfigRequirement {
+                key: "url".to_string(),
+                description: "Endpoint returning the JSON records".to_string(),
+                required: true,
+                example: Some("https://partner.example.org/api/v1/posts".to_string()),
+                source_url: None,
+            },
+            ConfigRequirement {
+                key: "mapping".to_string(),
+                description: "Paths to the records array and to the id/content fields".to_string(),
+                required: true,
+                example: Some("items_path = \"$.data.items\", id = \"uid\", content = \"body.text\"".to_string()),
+                source_url: None,
+            },
+            ConfigRequirement {
+                key: "auth".to_string(),
+                description: "Header injected into every request for authentication".to_string(),
+                required: false,
+                example: Some("name = \"X-Api-Key\", value = \"your_api_key_here\"".to_string()),
+                source_url: None,
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn sample_config(url: String) -> GenericJsonConfig {
+        GenericJsonConfig {
+            name: "partner_feed".to_string(),
+            url,
+            query_param: Some("q".to_string()),
+            limit_param: None,
+            auth: Some(AuthHeader {
+                name: "X-Api-Key".to_string(),
+                value: "secret-key".to_string(),
+            }),
+            mapping: JsonMapping {
+                items_path: "$.data.items".to_string(),
+                id: "uid".to_string(),
+                content: "body.text".to_string(),
+                author_id: Some("author.id".to_string()),
+                author_name: Some("author.handle".to_string()),
+                created_at: Some("posted".to_string()),
+                likes: Some("stats[0].value".to_string()),
+                shares: Some("stats[1].value".to_string()),
+                views: Some("views".to_string()),
+                ..JsonMapping::default()
+            },
+            http: HttpConfig::default(),
+        }
+    }
+
+    #[test]
+    fn test_select_paths() {
+        let value = serde_json::json!({ "a": { "b": [ { "c": 1 }, { "c": 2 } ] } });
+
+        assert_eq!(select(&value, "$.a.b[1].c"), Some(&serde_json::json!(2)));
+        assert_eq!(select(&value, "a.b[0].c"), Some(&serde_json::json!(1)));
+        assert_eq!(select(&value, "$"), Some(&value));
+        assert!(select(&value, "a.missing").is_none());
+        assert!(select(&value, "a.b[5]").is_none());
+        assert!(select(&value, "a.b[x]").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_maps_custom_payload() {
+        let server = MockServer::start().await;
+        let payload = serde_json::json!({
+            "data": {
+                "items": [
+                    {
+                        "uid": 9001,
+                        "body": { "text": "Flooding reported downtown" },
+                        "author": { "id": "a-17", "handle": "citywatch" },
+                        "posted": "2024-03-01T12:30:00Z",
+                        "stats": [ { "value": 42 }, { "value": "7" } ],
+                        "views": 1500
+                    },
+                    { "uid": "no-content" }
+                ]
+            }
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/posts"))
+            .and(query_param("q", "flood"))
+            .and(header("X-Api-Key", "secret-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(payload))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let connector = GenericJsonConnector::new(
+            sample_config(format!("{}/posts", server.uri())),
+            PrivacyConfig::default()
+        ).unwrap();
+
+        let params = SearchParams {
+            query: "flood".to_string(),
+            ..Default::default()
+        };
+
+        let posts = connector.search_posts(&params).await.unwrap();
+        assert_eq!(posts.len(), 1);
+
+        let post = &posts[0];
+        assert_eq!(post.id, "partner_feed:9001");
+        assert_eq!(post.platform, "partner_feed");
+        assert_eq!(post.content, "Flooding reported downtown");
+        assert_eq!(post.author.username, "citywatch");
+        assert_ne!(post.author.id_hash, "a-17");
+        assert_eq!(post.created_at.to_rfc3339(), "2024-03-01T12:30:00+00:00");
+        assert_eq!(post.metrics.likes, 42);
+        assert_eq!(post.metrics.shares, 7);
+        assert_eq!(post.metrics.comments, 0);
+        assert_eq!(post.metrics.views, Some(1500));
+    }
+}
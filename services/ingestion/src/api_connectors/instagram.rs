@@ -44,6 +44,11 @@ impl ApiConnector for InstagramConnector {
         !self.access_token.is_empty()
     }
 
+    fn capabilities(&self) -> Capabilities {
+        // Placeholder until the Graph API integration lands
+        Capabilities::default()
+    }
+
     async fn get_rate_limit_status(&self) -> Result<RateLimitInfo, ConnectorError> {
         // TODO: Implement Instagram rate limit tracking
         Ok(RateLimitInfo {
@@ -0,0 +1,251 @@
+/*
+ * =============================================================================
+ * Nitter Fallback for the Twitter Connector
+ * =============================================================================
+ *
+ * This module scrapes search results from a Nitter instance (an alternative
+ * Twitter frontend) so collection can continue, best effort, when the
+ * official API quota is exhausted. TwitterConnector only calls it after a
+ * 429 or rate limit error, and only when the fallback is enabled.
+ *
+ * Limitations:
+ * - Counts are read from the rendered page and are approximate
+ * - No author ids: authors are hashed by username, so they won't match
+ *   the same author collected through the API
+ * - No language, location, follower counts or views
+ *
+ * Scraped posts carry metadata `source: "nitter"` and
+ * `metrics_approximate: true`, which lowers their completeness score.
+ *
+ * =============================================================================
+ */
+
+use super::*;
+use chrono::NaiveDateTime;
+use reqwest::Client;
+use scraper::{ElementRef, Html, Selector};
+use tracing::info;
+
+/// Nitter fallback settings (`[platforms.twitter.nitter]`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NitterConfig {
+    /// Scrape the instance when the API quota is exhausted
+    pub enabled: bool,
+
+    /// Base URL of the instance, e.g. "https://nitter.example.org"
+    pub instance_url: String,
+}
+
+/// Search client for one Nitter instance
+pub struct NitterClient {
+    client: Client,
+    instance_url: String,
+}
+
+impl NitterClient {
+    /// Create a client for `instance_url` with the given HTTP settings
+    pub fn new(instance_url: &str, http: &HttpConfig) -> Result<Self, ConnectorError> {
+        let client = http.client_builder()?
+            .build()
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        Ok(Self {
+            client,
+            instance_url: instance_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Scrape the first page of search results for `params.query`
+    pub async fn search(&self, params: &SearchParams, privacy: &PrivacyConfig) -> Result<Vec<SocialPost>, ConnectorError> {
+        let request = self.client
+            .get(format!("{}/search", self.instance_url))
+            .query(&[("f", "tweets"), ("q", params.query.as_str())]);
+        let response = super::utils::send_timed("nitter", "search", request)
+            .await
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ConnectorError::ApiError {
+                code: status.as_u16(),
+                message: format!("Nitter instance {} returned {}", self.instance_url, status),
+            });
+        }
+
+        let html = response.text().await.map_err(|e| ConnectorError::NetworkError { source: e })?;
+        let mut posts = parse_search_page(&html, &self.instance_url, privacy);
+        if let Some(max_results) = params.max_results {
+            posts.truncate(max_results as usize);
+        }
+
+        info!("Scraped {} tweets from Nitter", posts.len());
+        Ok(posts)
+    }
+}
+
+/// Parse a Nitter search results page into posts
+///
+/// Items without a status link (e.g. "Load more") are skipped. Relative
+/// media links are resolved against `instance_url`.
+pub fn parse_search_page(html: &str, instance_url: &str, privacy: &PrivacyConfig) -> Vec<SocialPost> {
+    let document = Html::parse_document(html);
+    let item = Selector::parse(".timeline-item").unwrap();
+
+    document
+        .select(&item)
+        .filter_map(|item| parse_item(item, instance_url, privacy))
+        .collect()
+}
+
+/// Parse one `.timeline-item` into a post
+fn parse_item(item: ElementRef, instance_url: &str, privacy: &PrivacyConfig) -> Option<SocialPost> {
+    let select = |selector: &str| item.select(&Selector::parse(selector).unwrap()).next();
+    let text = |element: ElementRef| element.text().collect::<String>().trim().to_string();
+
+    let link = select("a.tweet-link")?.value().attr("href")?;
+    let id = status_id(link)?;
+
+    let username = select("a.username").map(text)?.trim_start_matches('@').to_string();
+    let parsed_created_at = select("span.tweet-date a")
+        .and_then(|date| date.value().attr("title"))
+        .and_then(parse_date);
+
+    let content = super::utils::normalize_text(&select(".tweet-content").map(text).unwrap_or_default());
+
+    // Each stat is an icon followed by a count; blank counts are zero
+    let mut metrics = PostMetrics { likes: 0, shares: 0, comments: 0, views: None, engagement_rate: None };
+    for stat in item.select(&Selector::parse(".tweet-stat").unwrap()) {
+        let Some(icon) = stat.select(&Selector::parse("span[class^='icon-']").unwrap()).next() else {
+            continue;
+        };
+        let count = parse_count(&text(stat));
+        match icon.value().attr("class").unwrap_or_default() {
+            "icon-comment" => metrics.comments += count,
+            "icon-retweet" | "icon-quote" => metrics.shares += count,
+            "icon-heart" => metrics.likes += count,
+            _ => {},
+        }
+    }
+
+    let media = item
+        .select(&Selector::parse(".attachment.image img").unwrap())
+        .filter_map(|img| {
+            let src = img.value().attr("src")?;
+            Some(MediaAttachment {
+                media_type: "photo".to_string(),
+                url: format!("{}{}", instance_url.trim_end_matches('/'), src),
+                alt_text: img.value().attr("alt").filter(|alt| !alt.is_empty()).map(str::to_string),
+                dimensions: None,
+                file_size: None,
+            })
+        })
+        .collect();
+
+    // Link text is shortened, so take external URLs from the hrefs
+    let urls = item
+        .select(&Selector::parse(".tweet-content a").unwrap())
+        .filter_map(|link| link.value().attr("href"))
+        .filter(|href| href.starts_with("http://") || href.starts_with("https://"))
+        .map(str::to_string)
+        .collect();
+
+    let mut metadata = HashMap::new();
+    metadata.insert("tweet_id".to_string(), serde_json::Value::String(id.clone()));
+    metadata.insert("source".to_string(), serde_json::Value::String("nitter".to_string()));
+    metadata.insert("metrics_approximate".to_string(), serde_json::Value::Bool(true));
+    let created_at = super::utils::resolve_created_at(parsed_created_at, &id, &mut metadata);
+
+    let mut post = SocialPost {
+        id,
+        platform: "twitter".to_string(),
+        author: AuthorInfo {
+            id_hash: super::utils::anonymize_user_id(&username, privacy),
+            username,
+            verified: select(".verified-icon").is_some(),
+            follower_count: None,
+            account_created: None,
+            account_type: Some("twitter".to_string()),
+        },
+        created_at,
+        metrics,
+        location: None,
+        language: None,
+        media,
+        hashtags: super::utils::extract_hashtags(&content),
+        mentions: super::utils::extract_mentions(&content)
+            .iter()
+            .map(|mention| super::utils::anonymize_user_id(mention, privacy))
+            .collect(),
+        urls,
+        metadata,
+        privacy_flags: PrivacyFlags {
+            anonymized: true,
+            sensitive_content: super::utils::contains_sensitive_content(&content),
+            location_generalized: false,
+            retention_policy: privacy.retention_policy.clone(),
+            consent_status: ConsentStatus::Implied,
+        },
+        content,
+    };
+
+    super::utils::assign_canonical_id(&mut post);
+    super::utils::apply_privacy_compliance(&mut post, privacy);
+    Some(post)
+}
+
+/// Tweet id from a status link such as "/user/status/123#m"
+fn status_id(link: &str) -> Option<String> {
+    let (_, rest) = link.split_once("/status/")?;
+    let id: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    (!id.is_empty()).then_some(id)
+}
+
+/// Parse a date tooltip such as "Jul 26, 2024 · 3:04 PM UTC"
+fn parse_date(title: &str) -> Option<DateTime<Utc>> {
+    let title = title.trim().trim_end_matches("UTC").trim().replace(" · ", " ");
+    NaiveDateTime::parse_from_str(&title, "%b %d, %Y %I:%M %p")
+        .ok()
+        .map(|naive| naive.and_utc())
+}
+
+/// Parse a displayed count such as "1,234" (blank or unreadable is zero)
+fn parse_count(text: &str) -> u64 {
+    text.replace(',', "").trim().parse().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/nitter/search_flood.html"));
+
+    #[test]
+    fn test_parse_search_page_fixture() {
+        let posts = parse_search_page(FIXTURE, "https://nitter.example.org/", &PrivacyConfig::default());
+        assert_eq!(posts.len(), 2);
+
+        let first = &posts[0];
+        assert_eq!(first.id, "twitter:1816851032487211264");
+        assert_eq!(first.native_id(), "1816851032487211264");
+        assert_eq!(first.platform, "twitter");
+        assert_eq!(first.author.username, "mumbairains");
+        assert!(first.author.verified);
+        assert_eq!(first.created_at, Utc.with_ymd_and_hms(2024, 7, 26, 15, 4, 0).unwrap());
+        assert!(first.content.starts_with("Waterlogging at #Andheri subway"));
+        assert_eq!(first.hashtags, vec!["Andheri"]);
+        assert_eq!(first.urls, vec!["https://mcgm.gov.in/alerts"]);
+        assert_eq!((first.metrics.comments, first.metrics.shares, first.metrics.likes), (12, 1240, 4567));
+        assert_eq!(first.media.len(), 1);
+        assert_eq!(first.media[0].url, "https://nitter.example.org/pic/media%2FGTa1.jpg%3Fname%3Dsmall");
+        assert_eq!(first.media[0].alt_text.as_deref(), Some("Flooded subway"));
+        assert_eq!(first.metadata["source"], serde_json::json!("nitter"));
+        assert_eq!(first.metadata["metrics_approximate"], serde_json::json!(true));
+
+        let second = &posts[1];
+        assert_eq!(second.native_id(), "1816799911200407552");
+        assert!(!second.author.verified);
+        assert_eq!((second.metrics.comments, second.metrics.shares, second.metrics.likes), (0, 3, 9));
+        assert!(second.media.is_empty());
+    }
+}
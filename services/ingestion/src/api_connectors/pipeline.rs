@@ -0,0 +1,290 @@
+/*
+ * =============================================================================
+ * Post Processing Pipeline for SentinelBERT
+ * =============================================================================
+ *
+ * This module runs the per-post transformations every connector applies
+ * after converting a post (`utils::apply_privacy_compliance`), in an order
+ * operators can change. Some deployments need a different order than the
+ * default, e.g. truncating before classification so only the stored text
+ * is scored.
+ *
+ * Built-in Steps (by config name, default order):
+ * - `canonicalize_urls`: strip tracking parameters from `urls`, keeping the
+ *   originals in metadata["original_urls"]
+ * - `classify_sensitivity`: metadata["content_warnings"] from the lexicon
+ * - `truncate`: cut content to `max_content_chars` on a word boundary
+ * - `generalize_location`: snap locations to `location_precision_km`
+ * - `required_fields`: mark posts lacking required fields for dropping
+ * - `normalize_text`: normalize content again (off by default; connectors
+ *   already normalize while converting)
+ *
+ * A step left out of `processing.pipeline` doesn't run. Deployments can
+ * append their own `ProcessStep`s with `Pipeline::push`.
+ *
+ * Deduplication and the enrichers work on whole batches and run later, in
+ * the IngestionService.
+ *
+ * =============================================================================
+ */
+
+use super::utils::{
+    canonicalize_url_with, default_classifier, generalize_location, normalize_text, truncate_on_word_boundary,
+};
+use super::{PrivacyConfig, SocialPost};
+use serde::{Deserialize, Serialize};
+
+/// One transformation applied to every converted post
+pub trait ProcessStep: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &str;
+
+    /// Transform one post in place
+    fn process(&self, post: &mut SocialPost, config: &PrivacyConfig);
+}
+
+/// Built-in steps, as named in `processing.pipeline`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuiltInStep {
+    NormalizeText,
+    CanonicalizeUrls,
+    ClassifySensitivity,
+    Truncate,
+    GeneralizeLocation,
+    RequiredFields,
+}
+
+impl BuiltInStep {
+    /// Order used when the config doesn't set one
+    pub const DEFAULT_ORDER: [BuiltInStep; 5] = [
+        BuiltInStep::CanonicalizeUrls,
+        BuiltInStep::ClassifySensitivity,
+        BuiltInStep::Truncate,
+        BuiltInStep::GeneralizeLocation,
+        BuiltInStep::RequiredFields,
+    ];
+
+    fn step(self) -> Box<dyn ProcessStep> {
+        match self {
+            BuiltInStep::NormalizeText => Box::new(NormalizeTextStep),
+            BuiltInStep::CanonicalizeUrls => Box::new(CanonicalizeUrlsStep),
+            BuiltInStep::ClassifySensitivity => Box::new(ClassifySensitivityStep),
+            BuiltInStep::Truncate => Box::new(TruncateStep),
+            BuiltInStep::GeneralizeLocation => Box::new(GeneralizeLocationStep),
+            BuiltInStep::RequiredFields => Box::new(RequiredFieldsStep),
+        }
+    }
+}
+
+/// Ordered processing steps
+pub struct Pipeline {
+    steps: Vec<Box<dyn ProcessStep>>,
+}
+
+impl Pipeline {
+    /// Pipeline with no steps
+    pub fn empty() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// Pipeline running the given built-in steps, in order
+    pub fn from_steps(steps: &[BuiltInStep]) -> Self {
+        Self {
+            steps: steps.iter().map(|step| step.step()).collect(),
+        }
+    }
+
+    /// Append a step, run after those already added
+    pub fn push(&mut self, step: Box<dyn ProcessStep>) {
+        self.steps.push(step);
+    }
+
+    /// Step names, in run order
+    pub fn step_names(&self) -> Vec<&str> {
+        self.steps.iter().map(|step| step.name()).collect()
+    }
+
+    /// Run every step over a post
+    pub fn run(&self, post: &mut SocialPost, config: &PrivacyConfig) {
+        for step in &self.steps {
+            step.process(post, config);
+        }
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::from_steps(&BuiltInStep::DEFAULT_ORDER)
+    }
+}
+
+impl std::fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.step_names()).finish()
+    }
+}
+
+/// Normalizes content (NFKC, invisible characters, whitespace)
+struct NormalizeTextStep;
+
+impl ProcessStep for NormalizeTextStep {
+    fn name(&self) -> &str {
+        "normalize_text"
+    }
+
+    fn process(&self, post: &mut SocialPost, _config: &PrivacyConfig) {
+        post.content = normalize_text(&post.content);
+    }
+}
+
+/// Strips tracking parameters from the post's links
+struct CanonicalizeUrlsStep;
+
+impl ProcessStep for CanonicalizeUrlsStep {
+    fn name(&self) -> &str {
+        "canonicalize_urls"
+    }
+
+    fn process(&self, post: &mut SocialPost, config: &PrivacyConfig) {
+        let canonical: Vec<String> = post.urls.iter().map(|url| canonicalize_url_with(url, &config.tracking_params)).collect();
+        if canonical != post.urls {
+            let original = std::mem::replace(&mut post.urls, canonical);
+            post.metadata.insert("original_urls".to_string(), serde_json::json!(original));
+        }
+    }
+}
+
+/// Records the lexicon categories the content matches
+struct ClassifySensitivityStep;
+
+impl ProcessStep for ClassifySensitivityStep {
+    fn name(&self) -> &str {
+        "classify_sensitivity"
+    }
+
+    fn process(&self, post: &mut SocialPost, _config: &PrivacyConfig) {
+        let sensitivity = default_classifier().classify(&post.content);
+        if !sensitivity.categories.is_empty() {
+            post.metadata.insert("content_warnings".to_string(), serde_json::json!(sensitivity.categories));
+        }
+    }
+}
+
+/// Shortens content to `max_content_chars`
+struct TruncateStep;
+
+impl ProcessStep for TruncateStep {
+    fn name(&self) -> &str {
+        "truncate"
+    }
+
+    fn process(&self, post: &mut SocialPost, config: &PrivacyConfig) {
+        // Entities were extracted from the full text and are left as they are
+        let Some(max_chars) = config.max_content_chars else {
+            return;
+        };
+
+        let original_length = post.content.chars().count();
+        if let Some(truncated) = truncate_on_word_boundary(&post.content, max_chars) {
+            post.content = truncated;
+            post.metadata.insert("content_truncated".to_string(), serde_json::Value::Bool(true));
+            post.metadata.insert("original_content_length".to_string(), serde_json::Value::from(original_length));
+        }
+    }
+}
+
+/// Snaps the location to the configured grid
+struct GeneralizeLocationStep;
+
+impl ProcessStep for GeneralizeLocationStep {
+    fn name(&self) -> &str {
+        "generalize_location"
+    }
+
+    fn process(&self, post: &mut SocialPost, config: &PrivacyConfig) {
+        if let Some(location) = &post.location {
+            if let Some(generalized) = generalize_location(location, config.location_precision_km) {
+                post.location = Some(generalized);
+                post.privacy_flags.location_generalized = true;
+            }
+        }
+    }
+}
+
+/// Marks posts lacking required fields
+struct RequiredFieldsStep;
+
+impl ProcessStep for RequiredFieldsStep {
+    fn name(&self) -> &str {
+        "required_fields"
+    }
+
+    fn process(&self, post: &mut SocialPost, config: &PrivacyConfig) {
+        // Marked here, dropped (and counted) by the ingestion service
+        let missing = config.required_fields.missing(post);
+        if !missing.is_empty() {
+            post.metadata.insert("missing_required_fields".to_string(), serde_json::json!(missing));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils::post;
+    use std::sync::{Arc, Mutex};
+
+    /// Appends its name to a shared trace
+    struct TraceStep {
+        name: &'static str,
+        trace: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl ProcessStep for TraceStep {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn process(&self, post: &mut SocialPost, _config: &PrivacyConfig) {
+            self.trace.lock().unwrap().push(format!("{}:{}", self.name, post.content));
+            post.content.push_str(self.name);
+        }
+    }
+
+    #[test]
+    fn test_steps_run_in_configured_order() {
+        let trace = Arc::new(Mutex::new(Vec::new()));
+        let mut pipeline = Pipeline::empty();
+        for name in ["scrub", "detect", "enrich"] {
+            pipeline.push(Box::new(TraceStep { name, trace: trace.clone() }));
+        }
+
+        let mut post = post("1", "");
+        pipeline.run(&mut post, &PrivacyConfig::default());
+
+        assert_eq!(pipeline.step_names(), vec!["scrub", "detect", "enrich"]);
+        assert_eq!(*trace.lock().unwrap(), vec!["scrub:", "detect:scrub", "enrich:scrubdetect"]);
+    }
+
+    #[test]
+    fn test_configured_order_changes_the_result() {
+        let config = PrivacyConfig { max_content_chars: Some(12), ..PrivacyConfig::default() };
+        let content = "Stay indoors, graphic violence reported downtown";
+
+        // By default the full text is classified before truncation
+        let mut classified_first = post("1", content);
+        Pipeline::default().run(&mut classified_first, &config);
+        assert!(classified_first.metadata.contains_key("content_warnings"));
+
+        let steps: Vec<BuiltInStep> = serde_json::from_str(r#"["truncate", "classify_sensitivity"]"#).unwrap();
+        let truncated_first_pipeline = Pipeline::from_steps(&steps);
+        let mut truncated_first = post("1", content);
+        truncated_first_pipeline.run(&mut truncated_first, &config);
+
+        assert_eq!(truncated_first_pipeline.step_names(), vec!["truncate", "classify_sensitivity"]);
+        assert_eq!(truncated_first.content, classified_first.content);
+        assert!(!truncated_first.metadata.contains_key("content_warnings"));
+        assert!(serde_json::from_str::<Vec<BuiltInStep>>(r#"["detect_language"]"#).is_err());
+    }
+}
@@ -0,0 +1,293 @@
+/*
+ * =============================================================================
+ * Connector Registry for SentinelBERT
+ * =============================================================================
+ *
+ * This module is the single place that maps configuration to connectors.
+ * The ingestion service and the CLI both take their connectors from here, so
+ * adding a platform means adding it to `from_config` and nowhere else.
+ *
+ * Behaviour:
+ * - Only enabled platforms are constructed
+ * - Enabled platforms whose required credentials are missing are skipped
+ *   with a warning; they are still listed by `skipped()` so the validate
+ *   command can report them
+ * - All connectors share one post cache and one rate limit scheduler
+ *
+ * =============================================================================
+ */
+
+use super::*;
+use super::nitter::NitterClient;
+use crate::config::Config;
+use crate::rate_limiter::RateLimitScheduler;
+use anyhow::Context;
+use std::sync::Arc;
+use tracing::warn;
+
+/// A connector built from config, with the settings the CLI reports against
+#[derive(Clone)]
+pub struct ConfiguredConnector {
+    /// Name shown in reports (the configured source name for generic JSON)
+    pub name: String,
+
+    /// Whether a failure of this connector should fail validation
+    pub required: bool,
+
+    pub connector: Arc<dyn ApiConnector>,
+}
+
+/// Connectors for every enabled, configured platform
+#[derive(Default)]
+pub struct ConnectorRegistry {
+    entries: Vec<ConfiguredConnector>,
+    skipped: Vec<ConfiguredConnector>,
+}
+
+impl ConnectorRegistry {
+    /// Construct a connector for every enabled platform
+    ///
+    /// Fails if a platform's HTTP settings are invalid (e.g. a malformed proxy URL).
+    pub fn from_config(config: &Config) -> anyhow::Result<Self> {
+        let privacy = config.privacy_config();
+        let post_cache = PostCache::new(&config.post_cache);
+        let rate_limiter = Arc::new(config.rate_limit_scheduler());
+        let platforms = &config.platforms;
+        let mut registry = Self::default();
+
+        if platforms.twitter.enabled {
+            let twitter = Self::build_twitter(config, post_cache.clone(), Arc::clone(&rate_limiter))?;
+            registry.add(!platforms.twitter.optional, Arc::new(twitter));
+        }
+        if platforms.reddit.enabled {
+            let reddit = Self::build_reddit(config, post_cache.clone(), Arc::clone(&rate_limiter))?;
+            registry.add(!platforms.reddit.optional, Arc::new(reddit));
+        }
+        if platforms.youtube.enabled {
+            registry.add(!platforms.youtube.optional, Arc::new(YouTubeConnector::new(
+                platforms.youtube.api_key.clone(),
+                privacy.clone(),
+            )));
+        }
+        if platforms.instagram.enabled {
+            registry.add(!platforms.instagram.optional, Arc::new(InstagramConnector::new(
+                platforms.instagram.access_token.clone(),
+                privacy.clone(),
+            )));
+        }
+        if platforms.telegram.enabled {
+            registry.add(!platforms.telegram.optional, Arc::new(TelegramConnector::new(
+                platforms.telegram.bot_token.clone(),
+                privacy.clone(),
+            )));
+        }
+
+        if platforms.tiktok.enabled {
+            let tiktok = TikTokConnector::with_http_config(
+                platforms.tiktok.client_key.clone(),
+                platforms.tiktok.client_secret.clone(),
+                privacy.clone(),
+                &platforms.tiktok.http,
+            ).context("Invalid TikTok HTTP settings")?;
+            registry.add(!platforms.tiktok.optional, Arc::new(tiktok));
+        }
+
+        if platforms.facebook.enabled {
+            let mut facebook = FacebookConnector::with_http_config(
+                platforms.facebook.access_token.clone(),
+                privacy.clone(),
+                &platforms.facebook.http,
+            ).context("Invalid Facebook HTTP settings")?;
+            facebook.set_app_credentials(platforms.facebook.app_id.clone(), platforms.facebook.app_secret.clone());
+            facebook.set_page_ids(platforms.facebook.page_ids.clone());
+            if let Some(version) = &platforms.facebook.api_version {
+                facebook.set_api_version(version);
+            }
+            registry.add(!platforms.facebook.optional, Arc::new(facebook));
+        }
+
+        if platforms.chan.enabled {
+            let mut chan = ChanConnector::with_http_config(privacy.clone(), &platforms.chan.http)
+                .context("Invalid imageboard HTTP settings")?;
+            chan.set_boards(platforms.chan.boards.clone());
+            if let (Some(base_url), Some(media_url)) = (&platforms.chan.base_url, &platforms.chan.media_url) {
+                chan.set_base_urls(base_url, media_url);
+            }
+            registry.add(!platforms.chan.optional, Arc::new(chan));
+        }
+
+        for endpoint in platforms.generic_json.iter().filter(|e| e.enabled) {
+            let connector = GenericJsonConnector::new(endpoint.connector.clone(), privacy.clone())
+                .with_context(|| format!("Invalid HTTP settings for {}", endpoint.connector.name))?;
+            registry.add_named(endpoint.connector.name.clone(), !endpoint.optional, Arc::new(connector));
+        }
+
+        Ok(registry)
+    }
+
+    /// The Twitter connector on its own, for the filtered stream
+    ///
+    /// Built even when Twitter is disabled for collection; fails if no
+    /// bearer token is configured.
+    pub fn twitter_from_config(config: &Config) -> anyhow::Result<TwitterConnector> {
+        let twitter = Self::build_twitter(
+            config,
+            PostCache::new(&config.post_cache),
+            Arc::new(config.rate_limit_scheduler()),
+        )?;
+        if !twitter.is_configured() {
+            anyhow::bail!("Twitter stream needs platforms.twitter.bearer_token");
+        }
+        Ok(twitter)
+    }
+
+    fn build_twitter(
+        config: &Config,
+        post_cache: PostCache,
+        rate_limiter: Arc<RateLimitScheduler>,
+    ) -> anyhow::Result<TwitterConnector> {
+        let settings = &config.platforms.twitter;
+        let mut twitter = TwitterConnector::with_http_config(
+            settings.bearer_token.clone(),
+            config.privacy_config(),
+            &settings.http,
+        ).context("Invalid Twitter HTTP settings")?;
+        if let Some(chain) = &settings.author_resolution {
+            twitter.set_author_resolution(chain.clone());
+        }
+        let nitter = &settings.nitter;
+        if nitter.enabled && !nitter.instance_url.is_empty() {
+            twitter.set_nitter_fallback(
+                NitterClient::new(&nitter.instance_url, &settings.http)
+                    .context("Invalid Nitter HTTP settings")?,
+            );
+        }
+        twitter.set_post_cache(post_cache);
+        twitter.set_rate_limiter(rate_limiter);
+        Ok(twitter)
+    }
+
+    /// The Reddit connector on its own, for listings and comment threads
+    ///
+    /// Built even when Reddit is disabled for collection; fails if no
+    /// client credentials are configured.
+    pub fn reddit_from_config(config: &Config) -> anyhow::Result<RedditConnector> {
+        let reddit = Self::build_reddit(
+            config,
+            PostCache::new(&config.post_cache),
+            Arc::new(config.rate_limit_scheduler()),
+        )?;
+        if !reddit.is_configured() {
+            anyhow::bail!("Reddit needs platforms.reddit.client_id and client_secret");
+        }
+        Ok(reddit)
+    }
+
+    fn build_reddit(
+        config: &Config,
+        post_cache: PostCache,
+        rate_limiter: Arc<RateLimitScheduler>,
+    ) -> anyhow::Result<RedditConnector> {
+        let settings = &config.platforms.reddit;
+        let mut reddit = RedditConnector::with_http_config(
+            settings.client_id.clone(),
+            settings.client_secret.clone(),
+            config.privacy_config(),
+            &settings.http,
+        ).context("Invalid Reddit HTTP settings")?;
+        reddit.set_post_cache(post_cache);
+        reddit.set_rate_limiter(rate_limiter);
+        reddit.set_author_enrichment(settings.enrich_authors);
+        reddit.set_user_agents(settings.user_agents.clone())
+            .context("Invalid Reddit user agents")?;
+        if let Some(user_auth) = settings.user_auth() {
+            reddit.set_user_auth(Some(user_auth));
+        }
+        Ok(reddit)
+    }
+
+    fn add(&mut self, required: bool, connector: Arc<dyn ApiConnector>) {
+        self.add_named(connector.platform_name().to_string(), required, connector);
+    }
+
+    fn add_named(&mut self, name: String, required: bool, connector: Arc<dyn ApiConnector>) {
+        let entry = ConfiguredConnector { name, required, connector };
+
+        if entry.connector.is_configured() {
+            self.entries.push(entry);
+        } else {
+            warn!("Skipping {}: platform is enabled but its credentials are missing", entry.name);
+            self.skipped.push(entry);
+        }
+    }
+
+    /// Connector registered under a report name (e.g. "reddit")
+    pub fn get(&self, platform: &str) -> Option<&dyn ApiConnector> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name == platform)
+            .map(|entry| entry.connector.as_ref())
+    }
+
+    /// Configured connectors, in config order
+    pub fn entries(&self) -> &[ConfiguredConnector] {
+        &self.entries
+    }
+
+    /// Enabled connectors skipped for missing credentials
+    pub fn skipped(&self) -> &[ConfiguredConnector] {
+        &self.skipped
+    }
+
+    /// Shared handles to the configured connectors
+    pub fn connectors(&self) -> Vec<Arc<dyn ApiConnector>> {
+        self.entries.iter().map(|entry| Arc::clone(&entry.connector)).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_reddit_enabled() {
+        let mut config = Config::default();
+        config.platforms.reddit.enabled = true;
+        config.platforms.reddit.client_id = "client".to_string();
+        config.platforms.reddit.client_secret = "secret".to_string();
+
+        let registry = ConnectorRegistry::from_config(&config).unwrap();
+
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.get("reddit").map(|c| c.platform_name()), Some("reddit"));
+        assert!(registry.get("twitter").is_none());
+        assert!(registry.skipped().is_empty());
+    }
+
+    #[test]
+    fn test_missing_credentials_are_skipped() {
+        let mut config = Config::default();
+        config.platforms.reddit.enabled = true;
+        config.platforms.reddit.optional = true;
+        config.platforms.reddit.client_id = "client".to_string();
+        config.platforms.reddit.client_secret = "secret".to_string();
+        config.platforms.telegram.enabled = true;
+
+        let registry = ConnectorRegistry::from_config(&config).unwrap();
+        let names: Vec<&str> = registry.entries().iter().map(|c| c.name.as_str()).collect();
+
+        assert_eq!(names, vec!["reddit"]);
+        assert!(!registry.entries()[0].required);
+        assert_eq!(registry.skipped().len(), 1);
+        assert_eq!(registry.skipped()[0].name, "telegram");
+        assert!(registry.skipped()[0].required);
+    }
+}
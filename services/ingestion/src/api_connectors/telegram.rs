@@ -45,6 +45,11 @@ impl ApiConnector for TelegramConnector {
         !self.bot_token.is_empty()
     }
 
+    fn capabilities(&self) -> Capabilities {
+        // Placeholder until the Bot API integration lands
+        Capabilities::default()
+    }
+
     async fn get_rate_limit_status(&self) -> Result<RateLimitInfo, ConnectorError> {
         // Telegram has generous rate limits
         Ok(RateLimitInfo {
@@ -0,0 +1,642 @@
+/*
+ * =============================================================================
+ * TikTok Research API Connector for SentinelBERT
+ * =============================================================================
+ *
+ * This module implements a connector for TikTok's Research API, which gives
+ * approved research accounts keyword search over public videos.
+ *
+ * TikTok Research API:
+ * - Client access token from client_key/client_secret (client_credentials)
+ * - Video query: POST /v2/research/video/query/
+ * - 1,000 requests per day, up to 100 videos per request
+ * - Every query needs a start and end date no more than 30 days apart
+ *
+ * Date Ranges:
+ * - With neither date set, the last 30 days are searched
+ * - With one date set, the other is filled in 30 days away (never past today)
+ * - Ranges that are reversed or longer than 30 days are a ConfigError
+ *
+ * Setup Instructions:
+ * 1. Apply for Research API access at https://developers.tiktok.com/products/research-api/
+ * 2. Note the client key and client secret of the approved app
+ * 3. Add to environment:
+ *    TIKTOK_CLIENT_KEY=your_client_key
+ *    TIKTOK_CLIENT_SECRET=your_client_secret
+ *
+ * Privacy Compliance:
+ * - Author usernames are hashed for the author id
+ * - Region codes are kept in metadata; no coordinates are collected
+ * - Sensitive content detection on video descriptions
+ *
+ * =============================================================================
+ */
+
+use super::*;
+use async_trait::async_trait;
+use chrono::{NaiveDate, TimeZone};
+use reqwest::{Client, header::AUTHORIZATION};
+use serde::{Deserialize, Serialize};
+use tracing::{info, error, debug};
+
+/// Longest date range the video query accepts
+const MAX_RANGE_DAYS: i64 = 30;
+
+/// Most videos returned by one query request
+const MAX_COUNT: u32 = 100;
+
+/// Requests allowed per day
+const DAILY_LIMIT: u32 = 1000;
+
+/// Video fields requested from the query endpoint
+const VIDEO_FIELDS: &str =
+    "id,video_description,create_time,region_code,share_count,view_count,like_count,comment_count,hashtag_names,username";
+
+/// TikTok Research API connector implementation
+pub struct TikTokConnector {
+    /// HTTP client for API requests
+    client: Client,
+
+    /// TikTok client key
+    client_key: String,
+
+    /// TikTok client secret
+    client_secret: String,
+
+    /// Client access token
+    access_token: tokio::sync::RwLock<Option<String>>,
+
+    /// Token expiration time
+    token_expires_at: tokio::sync::RwLock<Option<DateTime<Utc>>>,
+
+    /// Privacy configuration
+    privacy_config: PrivacyConfig,
+
+    /// Base API URL
+    base_url: String,
+
+    /// OAuth token URL
+    oauth_url: String,
+}
+
+/// Client access token response
+#[derive(Debug, Deserialize)]
+struct TikTokTokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+/// Video query request body
+#[derive(Debug, Serialize)]
+struct TikTokVideoQuery {
+    query: TikTokQuery,
+    start_date: String,
+    end_date: String,
+    max_count: u32,
+}
+
+/// Query conditions, all of which must match
+#[derive(Debug, Serialize)]
+struct TikTokQuery {
+    and: Vec<TikTokCondition>,
+}
+
+#[derive(Debug, Serialize)]
+struct TikTokCondition {
+    operation: &'static str,
+    field_name: &'static str,
+    field_values: Vec<String>,
+}
+
+/// Video query response envelope
+#[derive(Debug, Deserialize)]
+struct TikTokVideoResponse {
+    #[serde(default)]
+    data: Option<TikTokVideoData>,
+    error: TikTokError,
+}
+
+#[derive(Debug, Deserialize)]
+struct TikTokVideoData {
+    #[serde(default)]
+    videos: Vec<TikTokVideo>,
+}
+
+/// Error object included in every response ("ok" on success)
+#[derive(Debug, Deserialize)]
+struct TikTokError {
+    code: String,
+    #[serde(default)]
+    message: String,
+}
+
+/// A video from the query endpoint
+#[derive(Debug, Deserialize)]
+struct TikTokVideo {
+    id: serde_json::Value,
+    #[serde(default)]
+    video_description: Option<String>,
+    create_time: i64,
+    #[serde(default)]
+    region_code: Option<String>,
+    #[serde(default)]
+    like_count: Option<u64>,
+    #[serde(default)]
+    comment_count: Option<u64>,
+    #[serde(default)]
+    share_count: Option<u64>,
+    #[serde(default)]
+    view_count: Option<u64>,
+    #[serde(default)]
+    hashtag_names: Vec<String>,
+    #[serde(default)]
+    username: Option<String>,
+}
+
+impl TikTokConnector {
+    pub fn new(client_key: String, client_secret: String, privacy_config: PrivacyConfig) -> Self {
+        Self::with_http_config(client_key, client_secret, privacy_config, &HttpConfig::default())
+            .expect("Failed to create HTTP client")
+    }
+
+    /// Create a connector with custom HTTP client settings (timeouts, proxy)
+    pub fn with_http_config(
+        client_key: String,
+        client_secret: String,
+        privacy_config: PrivacyConfig,
+        http: &HttpConfig,
+    ) -> Result<Self, ConnectorError> {
+        let client = http.client_builder()?
+            .build()
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        Ok(Self {
+            client,
+            client_key,
+            client_secret,
+            access_token: tokio::sync::RwLock::new(None),
+            token_expires_at: tokio::sync::RwLock::new(None),
+            privacy_config,
+            base_url: "https://open.tiktokapis.com/v2".to_string(),
+            oauth_url: "https://open.tiktokapis.com/v2/oauth/token/".to_string(),
+        })
+    }
+
+    /// Get a client access token, reusing the cached one until it expires
+    async fn get_access_token(&self) -> Result<String, ConnectorError> {
+        {
+            let token = self.access_token.read().await;
+            let expires_at = self.token_expires_at.read().await;
+
+            if let (Some(token), Some(expires_at)) = (token.as_ref(), expires_at.as_ref()) {
+                if Utc::now() < *expires_at {
+                    return Ok(token.clone());
+                }
+            }
+        }
+
+        info!("Requesting new TikTok client access token");
+
+        let params = [
+            ("client_key", self.client_key.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+            ("grant_type", "client_credentials"),
+        ];
+
+        let request = self.client
+            .post(&self.oauth_url)
+            .form(&params);
+        let response = super::utils::send_timed(self.platform_name(), "auth", request)
+            .await
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(ConnectorError::AuthenticationFailed {
+                message: format!("TikTok token request failed: {}", error_text),
+            });
+        }
+
+        // TikTok reports bad credentials with a 200 and an error body
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| ConnectorError::DeserializationError { message: e.to_string() })?;
+        let token_response: TikTokTokenResponse = serde_json::from_value(body.clone())
+            .map_err(|_| ConnectorError::AuthenticationFailed {
+                message: format!("TikTok token request failed: {}", body),
+            })?;
+
+        // Subtract 5 minutes for safety
+        let expires_at = Utc::now() + chrono::Duration::seconds(token_response.expires_in as i64 - 300);
+
+        {
+            let mut token = self.access_token.write().await;
+            let mut expires = self.token_expires_at.write().await;
+            *token = Some(token_response.access_token.clone());
+            *expires = Some(expires_at);
+        }
+
+        info!("Successfully obtained TikTok client access token");
+        Ok(token_response.access_token)
+    }
+
+    /// Resolve the search date range, as the YYYYMMDD strings the API takes
+    ///
+    /// Missing dates are filled in to give a 30 day range ending no later
+    /// than today; a reversed or longer range is a ConfigError.
+    fn date_range(params: &SearchParams) -> Result<(String, String), ConnectorError> {
+        let today = Utc::now().date_naive();
+        let max_range = chrono::Duration::days(MAX_RANGE_DAYS);
+
+        let (start, end) = match (params.start_date, params.end_date) {
+            (Some(start), Some(end)) => (start.date_naive(), end.date_naive()),
+            (Some(start), None) => {
+                let start = start.date_naive();
+                (start, (start + max_range).min(today))
+            },
+            (None, Some(end)) => {
+                let end = end.date_naive();
+                (end - max_range, end)
+            },
+            (None, None) => (today - max_range, today),
+        };
+
+        if end < start {
+            return Err(ConnectorError::ConfigError {
+                message: format!("TikTok search end date {} is before start date {}", end, start),
+            });
+        }
+        if end - start > max_range {
+            return Err(ConnectorError::ConfigError {
+                message: format!(
+                    "TikTok search range {} to {} exceeds the Research API limit of {} days",
+                    start, end, MAX_RANGE_DAYS
+                ),
+            });
+        }
+
+        let format = |date: NaiveDate| date.format("%Y%m%d").to_string();
+        Ok((format(start), format(end)))
+    }
+
+    /// Run a video query and convert the results
+    ///
+    /// `operation` labels the request's latency metrics.
+    async fn query_videos(
+        &self,
+        operation: &str,
+        condition: TikTokCondition,
+        params: &SearchParams,
+    ) -> Result<Vec<SocialPost>, ConnectorError> {
+        let (start_date, end_date) = Self::date_range(params)?;
+        let body = TikTokVideoQuery {
+            query: TikTokQuery { and: vec![condition] },
+            start_date,
+            end_date,
+            max_count: params.max_results.unwrap_or(20).clamp(1, MAX_COUNT),
+        };
+
+        let url = format!("{}/research/video/query/?fields={}", self.base_url, VIDEO_FIELDS);
+        debug!("TikTok video query: {:?}", body);
+
+        let token = self.get_access_token().await?;
+        let request = self.client
+            .post(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .json(&body);
+        let response = super::utils::send_timed(self.platform_name(), operation, request)
+            .await
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            // Drop the token so the next request re-authenticates
+            *self.access_token.write().await = None;
+            return Err(ConnectorError::InvalidCredentials);
+        }
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(ConnectorError::RateLimitExceeded { reset_time: Self::next_quota_reset() });
+        }
+
+        let text = response.text().await.map_err(|e| ConnectorError::NetworkError { source: e })?;
+        let video_response: TikTokVideoResponse = match serde_json::from_str(&text) {
+            Ok(parsed) => parsed,
+            Err(_) if !status.is_success() => {
+                return Err(ConnectorError::ApiError {
+                    code: status.as_u16(),
+                    message: format!("TikTok API error: {}", text),
+                });
+            },
+            Err(e) => return Err(ConnectorError::DeserializationError { message: e.to_string() }),
+        };
+
+        if !status.is_success() || video_response.error.code != "ok" {
+            return Err(ConnectorError::ApiError {
+                code: status.as_u16(),
+                message: format!(
+                    "TikTok API error: {} - {}",
+                    video_response.error.code, video_response.error.message
+                ),
+            });
+        }
+
+        let videos = video_response.data.map(|data| data.videos).unwrap_or_default();
+        Ok(videos.iter().map(|video| self.convert_video_to_social_post(video)).collect())
+    }
+
+    /// Daily quota resets at midnight UTC
+    fn next_quota_reset() -> DateTime<Utc> {
+        let tomorrow = Utc::now().date_naive() + chrono::Duration::days(1);
+        Utc.from_utc_datetime(&tomorrow.and_hms_opt(0, 0, 0).unwrap_or_default())
+    }
+
+    /// Convert a TikTok video to normalized SocialPost
+    fn convert_video_to_social_post(&self, video: &TikTokVideo) -> SocialPost {
+        // Ids are 64-bit integers; keep them as strings to avoid precision loss downstream
+        let id = match &video.id {
+            serde_json::Value::String(id) => id.clone(),
+            other => other.to_string(),
+        };
+        let username = video.username.clone().unwrap_or_else(|| "unknown".to_string());
+        let content = video.video_description.clone().unwrap_or_default();
+
+        let author = AuthorInfo {
+            id_hash: super::utils::anonymize_user_id(&username, &self.privacy_config),
+            username: username.clone(),
+            verified: false,
+            follower_count: None, // Needs a separate user info request
+            account_created: None,
+            account_type: Some("tiktok".to_string()),
+        };
+
+        let metrics = PostMetrics {
+            likes: video.like_count.unwrap_or(0),
+            shares: video.share_count.unwrap_or(0),
+            comments: video.comment_count.unwrap_or(0),
+            views: video.view_count,
+            engagement_rate: None, // Will be calculated later
+        };
+
+        let hashtags = if video.hashtag_names.is_empty() {
+            super::utils::extract_hashtags(&content)
+        } else {
+            video.hashtag_names.clone()
+        };
+        let mentions = super::utils::extract_mentions(&content)
+            .into_iter()
+            .map(|mention| super::utils::anonymize_user_id(&mention, &self.privacy_config))
+            .collect();
+
+        let video_url = format!("https://www.tiktok.com/@{}/video/{}", username, id);
+        let media = vec![MediaAttachment {
+            media_type: "video".to_string(),
+            url: video_url.clone(),
+            alt_text: None,
+            dimensions: None,
+            file_size: None,
+        }];
+
+        let created_at = Utc.timestamp_opt(video.create_time, 0)
+            .single()
+            .unwrap_or_else(Utc::now);
+
+        let mut metadata = HashMap::new();
+        metadata.insert("video_url".to_string(), serde_json::Value::String(video_url));
+        if let Some(region_code) = &video.region_code {
+            metadata.insert("region_code".to_string(), serde_json::Value::String(region_code.clone()));
+        }
+
+        let privacy_flags = PrivacyFlags {
+            anonymized: true,
+            sensitive_content: super::utils::contains_sensitive_content(&content),
+            location_generalized: false, // Only a region code is available
+            retention_policy: self.privacy_config.retention_policy.clone(),
+            consent_status: ConsentStatus::Implied, // Public videos imply consent
+        };
+
+        let mut post = SocialPost {
+            id,
+            platform: "tiktok".to_string(),
+            content: content.clone(),
+            author,
+            created_at,
+            metrics,
+            location: None,
+            language: None,
+            media,
+            hashtags,
+            mentions,
+            urls: super::utils::extract_urls(&content),
+            metadata,
+            privacy_flags,
+        };
+
+        super::utils::assign_canonical_id(&mut post);
+        super::utils::apply_privacy_compliance(&mut post, &self.privacy_config);
+        post
+    }
+}
+
+#[async_trait]
+impl ApiConnector for TikTokConnector {
+    fn platform_name(&self) -> &'static str {
+        "tiktok"
+    }
+
+    fn is_configured(&self) -> bool {
+        !self.client_key.is_empty() && !self.client_secret.is_empty()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_search: true,
+            supports_user_posts: true,
+            supports_trending: false,
+            supports_streaming: false,
+            supports_post_by_id: false,
+        }
+    }
+
+    async fn get_rate_limit_status(&self) -> Result<RateLimitInfo, ConnectorError> {
+        // The Research API doesn't report remaining quota
+        Ok(RateLimitInfo {
+            remaining: DAILY_LIMIT,
+            limit: DAILY_LIMIT,
+            reset_time: Self::next_quota_reset(),
+            window_duration: chrono::Duration::hours(24),
+        })
+    }
+
+    async fn search_posts(&self, params: &SearchParams) -> Result<Vec<SocialPost>, ConnectorError> {
+        info!("Searching TikTok for: {}", params.query);
+
+        let condition = TikTokCondition {
+            operation: "IN",
+            field_name: "keyword",
+            field_values: vec![params.query.clone()],
+        };
+        let posts = self.query_videos("search", condition, params).await?;
+        let posts = super::utils::filter_by_content_type(posts, params.content_type.as_ref());
+
+        info!("Retrieved {} videos from TikTok", posts.len());
+        Ok(posts)
+    }
+
+    async fn get_post_by_id(&self, _post_id: &str) -> Result<Option<SocialPost>, ConnectorError> {
+        // Video queries always need a date range, which an id alone doesn't give
+        Err(ConnectorError::Generic {
+            message: "TikTok Research API does not support lookup by video id".to_string(),
+        })
+    }
+
+    async fn get_user_posts(&self, user_id: &str, limit: Option<u32>) -> Result<Vec<SocialPost>, ConnectorError> {
+        let condition = TikTokCondition {
+            operation: "EQ",
+            field_name: "username",
+            field_values: vec![user_id.to_string()],
+        };
+        let params = SearchParams {
+            max_results: limit,
+            ..Default::default()
+        };
+
+        self.query_videos("user_posts", condition, &params).await
+    }
+
+    async fn get_trending_topics(&self, _location: Option<&str>) -> Result<Vec<String>, ConnectorError> {
+        Err(ConnectorError::Generic {
+            message: "TikTok Research API does not provide trending topics".to_string(),
+        })
+    }
+
+    async fn validate_credentials(&self) -> Result<bool, ConnectorError> {
+        info!("Validating TikTok API credentials");
+
+        match self.get_access_token().await {
+            Ok(_) => {
+                info!("TikTok API credentials are valid");
+                Ok(true)
+            },
+            Err(ConnectorError::AuthenticationFailed { message }) => {
+                error!("TikTok API credentials are invalid: {}", message);
+                Ok(false)
+            },
+            Err(e) => Err(e),
+        }
+    }
+
+    fn get_config_requirements(&self) -> Vec<ConfigRequirement> {
+        vec![
+            ConfigRequirement {
+                key: "TIKTOK_CLIENT_KEY".to_string(),
+                description: "TikTok Research API client key".to_string(),
+                required: true,
+                example: Some("awabcdef12345678".to_string()),
+                source_url: Some("https://developers.tiktok.com/products/research-api/".to_string()),
+            },
+            ConfigRequirement {
+                key: "TIKTOK_CLIENT_SECRET".to_string(),
+                description: "TikTok Research API client secret".to_string(),
+                required: true,
+                example: Some("AbCdEfGhIjKlMnOpQrStUvWxYz123456".to_string()),
+                source_url: Some("https://developers.tiktok.com/products/research-api/".to_string()),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(start: Option<&str>, end: Option<&str>) -> SearchParams {
+        let date = |d: &str| DateTime::parse_from_rfc3339(d).unwrap().with_timezone(&Utc);
+        SearchParams {
+            query: "flood".to_string(),
+            max_results: Some(10),
+            start_date: start.map(date),
+            end_date: end.map(date),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_date_range_limits() {
+        let range = TikTokConnector::date_range(&params(Some("2024-03-01T00:00:00Z"), Some("2024-03-31T12:00:00Z")));
+        assert_eq!(range.unwrap(), ("20240301".to_string(), "20240331".to_string()));
+
+        for (start, end) in [
+            ("2024-03-01T00:00:00Z", "2024-04-01T00:00:00Z"),
+            ("2024-03-10T00:00:00Z", "2024-03-01T00:00:00Z"),
+        ] {
+            let result = TikTokConnector::date_range(&params(Some(start), Some(end)));
+            assert!(matches!(result, Err(ConnectorError::ConfigError { .. })), "{} to {}", start, end);
+        }
+
+        let (start, end) = TikTokConnector::date_range(&params(None, None)).unwrap();
+        let span = NaiveDate::parse_from_str(&end, "%Y%m%d").unwrap()
+            - NaiveDate::parse_from_str(&start, "%Y%m%d").unwrap();
+        assert_eq!(span.num_days(), MAX_RANGE_DAYS);
+    }
+
+    #[tokio::test]
+    async fn test_search_maps_videos_to_posts() {
+        use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v2/oauth/token/"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "clt.token", "expires_in": 7200, "token_type": "Bearer"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v2/research/video/query/"))
+            .and(query_param("fields", VIDEO_FIELDS))
+            .and(header("authorization", "Bearer clt.token"))
+            .and(body_partial_json(serde_json::json!({
+                "query": { "and": [{ "operation": "IN", "field_name": "keyword", "field_values": ["flood"] }] },
+                "start_date": "20240301",
+                "end_date": "20240315",
+                "max_count": 10
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "videos": [{
+                    "id": 7341234567890123456u64,
+                    "video_description": "River rising fast #flood",
+                    "create_time": 1709294400,
+                    "region_code": "IN",
+                    "like_count": 120, "comment_count": 8, "share_count": 15, "view_count": 4000,
+                    "hashtag_names": ["flood"],
+                    "username": "reporter"
+                }], "cursor": 1, "has_more": false },
+                "error": { "code": "ok", "message": "", "log_id": "1" }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut connector = TikTokConnector::new("key".to_string(), "secret".to_string(), PrivacyConfig::default());
+        connector.base_url = format!("{}/v2", server.uri());
+        connector.oauth_url = format!("{}/v2/oauth/token/", server.uri());
+
+        let posts = connector
+            .search_posts(&params(Some("2024-03-01T00:00:00Z"), Some("2024-03-15T00:00:00Z")))
+            .await
+            .unwrap();
+
+        assert_eq!(posts.len(), 1);
+        let post = &posts[0];
+        assert_eq!(post.native_id(), "7341234567890123456");
+        assert_eq!(post.platform, "tiktok");
+        assert_eq!(post.content, "River rising fast #flood");
+        assert_eq!((post.metrics.likes, post.metrics.comments, post.metrics.shares), (120, 8, 15));
+        assert_eq!(post.metrics.views, Some(4000));
+        assert_eq!(post.hashtags, vec!["flood"]);
+        assert_eq!(post.metadata["region_code"], "IN");
+    }
+}
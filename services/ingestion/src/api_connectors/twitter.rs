@@ -18,6 +18,10 @@
  * - Tweet search: 300 requests/15min, 1 request/second
  * - User lookup: 300 requests/15min
  * - Tweet lookup: 300 requests/15min
+ * - Requests are budgeted by the shared RateLimitScheduler, which also
+ *   honours the x-rate-limit-* response headers
+ * - A 429 is retried once after sleeping until x-rate-limit-reset (capped
+ *   by http.max_retry_wait_secs)
  * 
  * Setup Instructions:
  * 1. Visit https://developer.twitter.com/
@@ -32,17 +36,113 @@
  * - Sensitive content detection and filtering
  * - GDPR-compliant data processing
  * 
+ * Author Resolution:
+ * - Authors come from the includes.users expansion, then from a cache of
+ *   authors seen earlier, then (optionally) a separate users lookup
+ * - Enabling the lookup step costs quota: up to one extra request per 100
+ *   unresolved authors per search, against the 300/15min user lookup limit
+ * - Authors no step resolves are stored as anonymous
+ * 
+ * Conversations:
+ * - Replies carry their parent tweet id in metadata.reply_to and the
+ *   conversation (root tweet) id in metadata.thread_id
+ * - `get_conversation` collects a whole thread via the conversation_id:
+ *   search operator (last 7 days only, like all recent search) and orders
+ *   it parents-first
+ * - Quote tweets get the quoted tweet's text appended to their content
+ *   (after QUOTED_TWEET_DELIMITER) when includes.tweets has it, and its id
+ *   in metadata.quoted_tweet_id; metrics stay the quoting tweet's own
+ * 
+ * Nitter Fallback (off by default):
+ * - With `[platforms.twitter.nitter]` enabled, a search that fails with a
+ *   429 or rate limit error is retried by scraping the configured Nitter
+ *   instance; such posts carry metadata source "nitter" (see nitter.rs)
+ * 
+ * Trending Topics:
+ * - v2 has no trends endpoint, so trends use v1.1 trends/place.json, which
+ *   needs a token with v1.1 access (Elevated tier or higher)
+ * - Locations are resolved to WOEIDs via trends/available.json once and
+ *   cached; no location means worldwide (WOEID 1)
+ * 
  * =============================================================================
  */
 
 use super::*;
+use super::debug_capture::DebugCapture;
+use super::nitter::NitterClient;
+use crate::rate_limiter::RateLimitScheduler;
+use crate::models::{Platform, UserProfile};
 use async_trait::async_trait;
 use reqwest::{Client, header::{HeaderMap, HeaderValue, AUTHORIZATION, USER_AGENT}};
 use serde::{Deserialize, Serialize};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::time::sleep;
 use chrono::{DateTime, Utc, TimeZone};
-use tracing::{info, warn, error, debug};
+use futures::{Stream, StreamExt};
+use tracing::{info, warn, error, debug, instrument};
+
+/// Initial delay before reconnecting a dropped filtered stream
+const STREAM_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Maximum delay between filtered stream reconnect attempts
+const STREAM_MAX_BACKOFF: Duration = Duration::from_secs(64);
+
+/// Minimum delay after the stream endpoint returns 429
+const STREAM_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Twitter sends keep-alive newlines every ~20s; silence beyond this means a stalled connection
+const STREAM_STALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Overall lifetime of one stream connection (overrides the client's request timeout)
+const STREAM_CONNECTION_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Upper bound on cached authors before the cache is reset
+const AUTHOR_CACHE_CAPACITY: usize = 10_000;
+
+/// Maximum ids accepted by one `GET /users` lookup
+const USER_LOOKUP_BATCH: usize = 100;
+
+/// Maximum ids accepted by one `GET /tweets` lookup
+const TWEET_LOOKUP_BATCH: usize = 100;
+
+/// Separates a quote tweet's own text from the quoted text appended to it
+const QUOTED_TWEET_DELIMITER: &str = "\n\n[Quoted tweet] ";
+
+/// Fields and expansions requested when looking tweets up by id
+const TWEET_LOOKUP_FIELDS: &str = "tweet.fields=id,text,author_id,created_at,public_metrics,geo,lang,entities,attachments,context_annotations,referenced_tweets,conversation_id,edit_history_tweet_ids,edit_controls,possibly_sensitive&user.fields=id,username,name,verified,public_metrics,created_at&media.fields=media_key,type,url,preview_image_url,alt_text,width,height&expansions=author_id,attachments.media_keys,referenced_tweets.id";
+
+/// Recent search only covers the last 7 days
+const RECENT_SEARCH_DAYS: i64 = 7;
+
+/// Minimum gap between requests (1 request/second)
+const MIN_REQUEST_SPACING: Duration = Duration::from_secs(1);
+
+/// WOEID used for trends when no location is given
+const WORLDWIDE_WOEID: u64 = 1;
+
+/// A step in the author resolution chain
+///
+/// Steps are tried in order; an author no step resolves is anonymous.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthorResolutionStep {
+    /// Use the `includes.users` expansion from the same response (free)
+    Includes,
+
+    /// Use authors seen in earlier responses (free)
+    Cache,
+
+    /// Look up missing authors via `GET /users?ids=` (costs quota: one
+    /// request per 100 unresolved authors per search, counted against the
+    /// users lookup rate limit)
+    Fetch,
+}
+
+/// Default chain, which never spends extra quota
+pub fn default_author_resolution() -> Vec<AuthorResolutionStep> {
+    vec![AuthorResolutionStep::Includes, AuthorResolutionStep::Cache]
+}
 
 /// Twitter API v2 connector implementation
 /// 
@@ -58,11 +158,38 @@ pub struct TwitterConnector {
     /// Privacy configuration
     privacy_config: PrivacyConfig,
     
-    /// Rate limiting state
+    /// Rate limit as last reported by the API (for status reporting)
     rate_limit_state: tokio::sync::RwLock<RateLimitState>,
     
+    /// Request budget, possibly shared with other connectors and tasks
+    rate_limiter: Arc<RateLimitScheduler>,
+    
     /// Base API URL
     base_url: String,
+    
+    /// Base URL for v1.1 endpoints (trends)
+    v1_base_url: String,
+    
+    /// Trend location names (lowercased) resolved to WOEIDs
+    woeid_cache: std::sync::RwLock<HashMap<String, u64>>,
+    
+    /// Author resolution chain
+    author_resolution: Vec<AuthorResolutionStep>,
+    
+    /// Authors resolved from earlier responses, keyed by Twitter user id
+    author_cache: std::sync::RwLock<HashMap<String, AuthorInfo>>,
+    
+    /// Posts fetched by id, served without touching the rate limit
+    post_cache: PostCache,
+
+    /// Longest sleep honoured from a 429 before retrying
+    max_retry_wait: Duration,
+
+    /// Opt-in logging of raw requests and responses
+    debug_capture: DebugCapture,
+
+    /// Scraper used for searches once the API quota is exhausted
+    nitter: Option<NitterClient>,
 }
 
 /// Internal rate limiting state
@@ -92,6 +219,50 @@ impl Default for RateLimitState {
     }
 }
 
+/// Location entry from v1.1 trends/available.json
+#[derive(Debug, Deserialize)]
+struct TwitterTrendLocation {
+    name: String,
+    woeid: u64,
+    country: Option<String>,
+}
+
+/// Single trend from v1.1 trends/place.json
+#[derive(Debug, Deserialize)]
+struct TwitterTrend {
+    name: String,
+}
+
+/// v1.1 trends/place.json entry (the endpoint returns a one-element array)
+#[derive(Debug, Deserialize)]
+struct TwitterTrendsResponse {
+    trends: Vec<TwitterTrend>,
+}
+
+/// Twitter users lookup response structure
+#[derive(Debug, Deserialize)]
+struct TwitterUsersResponse {
+    data: Option<Vec<TwitterUser>>,
+}
+
+/// Twitter single user lookup response structure
+///
+/// An unknown or suspended user comes back with `errors` and no `data`.
+#[derive(Debug, Deserialize)]
+struct TwitterUserResponse {
+    data: Option<TwitterUser>,
+}
+
+/// Twitter tweets lookup response structure
+///
+/// Ids that don't resolve (deleted, protected) appear in `errors`, not `data`.
+#[derive(Debug, Deserialize)]
+struct TwitterLookupResponse {
+    data: Option<Vec<TwitterTweet>>,
+    includes: Option<TwitterIncludes>,
+    errors: Option<Vec<TwitterError>>,
+}
+
 /// Twitter API v2 search response structure
 #[derive(Debug, Deserialize)]
 struct TwitterSearchResponse {
@@ -115,6 +286,20 @@ struct TwitterTweet {
     attachments: Option<TwitterAttachments>,
     context_annotations: Option<Vec<TwitterContextAnnotation>>,
     referenced_tweets: Option<Vec<TwitterReferencedTweet>>,
+    conversation_id: Option<String>,
+    edit_history_tweet_ids: Option<Vec<String>>,
+    edit_controls: Option<TwitterEditControls>,
+
+    /// Twitter's own label for tweets with possibly sensitive media or links
+    possibly_sensitive: Option<bool>,
+}
+
+/// Twitter edit controls for an editable tweet
+#[derive(Debug, Deserialize)]
+struct TwitterEditControls {
+    edits_remaining: Option<u32>,
+    is_edit_eligible: Option<bool>,
+    editable_until: Option<String>,
 }
 
 /// Twitter user data structure
@@ -128,6 +313,8 @@ struct TwitterUser {
     created_at: Option<String>,
     description: Option<String>,
     profile_image_url: Option<String>,
+    location: Option<String>,
+    url: Option<String>,
 }
 
 /// Twitter metrics structure
@@ -302,6 +489,28 @@ struct TwitterMeta {
     oldest_id: Option<String>,
 }
 
+/// Twitter filtered stream event (one JSON object per line)
+#[derive(Debug, Deserialize)]
+struct TwitterStreamEvent {
+    data: Option<TwitterTweet>,
+    includes: Option<TwitterIncludes>,
+    errors: Option<Vec<TwitterError>>,
+}
+
+/// Twitter filtered stream rules response
+#[derive(Debug, Deserialize)]
+struct TwitterStreamRulesResponse {
+    data: Option<Vec<TwitterStreamRule>>,
+    errors: Option<Vec<TwitterError>>,
+}
+
+/// Twitter filtered stream rule
+#[derive(Debug, Deserialize)]
+struct TwitterStreamRule {
+    id: String,
+    value: String,
+}
+
 /// Twitter API error
 #[derive(Debug, Deserialize)]
 struct TwitterError {
@@ -329,6 +538,16 @@ impl TwitterConnector {
     /// );
     /// ```
     pub fn new(bearer_token: String, privacy_config: PrivacyConfig) -> Self {
+        Self::with_http_config(bearer_token, privacy_config, &HttpConfig::default())
+            .expect("Failed to create HTTP client")
+    }
+
+    /// Create a connector with custom HTTP client settings (timeouts, proxy)
+    pub fn with_http_config(
+        bearer_token: String,
+        privacy_config: PrivacyConfig,
+        http: &HttpConfig,
+    ) -> Result<Self, ConnectorError> {
         let mut headers = HeaderMap::new();
         headers.insert(
             AUTHORIZATION,
@@ -340,52 +559,50 @@ impl TwitterConnector {
             HeaderValue::from_static("SentinelBERT/1.0 (Law Enforcement Analytics)")
         );
 
-        let client = Client::builder()
+        let client = http.client_builder()?
             .default_headers(headers)
-            .timeout(Duration::from_secs(30))
             .build()
-            .expect("Failed to create HTTP client");
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
 
-        Self {
+        Ok(Self {
             client,
             bearer_token,
             privacy_config,
             rate_limit_state: tokio::sync::RwLock::new(RateLimitState::default()),
+            rate_limiter: Arc::new(RateLimitScheduler::with_platform_defaults()),
             base_url: "https://api.twitter.com/2".to_string(),
-        }
+            v1_base_url: "https://api.twitter.com/1.1".to_string(),
+            woeid_cache: std::sync::RwLock::new(HashMap::new()),
+            author_resolution: default_author_resolution(),
+            author_cache: std::sync::RwLock::new(HashMap::new()),
+            post_cache: PostCache::default(),
+            max_retry_wait: http.max_retry_wait(),
+            debug_capture: http.debug_capture.clone(),
+            nitter: None,
+        })
     }
 
-    /// Wait for rate limit if necessary
-    async fn wait_for_rate_limit(&self) -> Result<(), ConnectorError> {
-        let state = self.rate_limit_state.read().await;
-        
-        if state.remaining == 0 {
-            let now = Utc::now();
-            if now < state.reset_time {
-                let wait_duration = (state.reset_time - now).to_std()
-                    .map_err(|_| ConnectorError::Generic { 
-                        message: "Invalid wait duration".to_string() 
-                    })?;
-                
-                warn!("Rate limit exceeded, waiting {:?} until reset", wait_duration);
-                drop(state); // Release the lock before sleeping
-                sleep(wait_duration).await;
-            }
-        }
-        
-        // Ensure minimum 1 second between requests for Essential Access
-        if let Some(last_request) = state.last_request {
-            let elapsed = Utc::now() - last_request;
-            if elapsed < chrono::Duration::seconds(1) {
-                let wait_time = chrono::Duration::seconds(1) - elapsed;
-                if let Ok(wait_duration) = wait_time.to_std() {
-                    drop(state);
-                    sleep(wait_duration).await;
-                }
-            }
-        }
-        
-        Ok(())
+    /// Use a (possibly shared) post cache for get_post_by_id
+    pub fn set_post_cache(&mut self, cache: PostCache) {
+        self.post_cache = cache;
+    }
+
+    /// Use a (possibly shared) scheduler for request budgeting
+    pub fn set_rate_limiter(&mut self, rate_limiter: Arc<RateLimitScheduler>) {
+        self.rate_limiter = rate_limiter;
+    }
+
+    /// Fall back to scraping this Nitter instance when search quota runs out
+    pub fn set_nitter_fallback(&mut self, nitter: NitterClient) {
+        self.nitter = Some(nitter);
+    }
+
+    /// Set the author resolution chain
+    ///
+    /// Including `Fetch` trades quota for completeness: each search may make
+    /// an extra users lookup for authors missing from includes and the cache.
+    pub fn set_author_resolution(&mut self, chain: Vec<AuthorResolutionStep>) {
+        self.author_resolution = chain;
     }
 
     /// Update rate limit state from response headers
@@ -418,241 +635,406 @@ impl TwitterConnector {
         }
         
         state.last_request = Some(Utc::now());
+
+        // Only fresh numbers adjust the shared budget
+        if headers.contains_key("x-rate-limit-remaining") {
+            self.rate_limiter.update(self.platform_name(), state.remaining, state.reset_time);
+        }
     }
 
     /// Convert Twitter tweet to normalized SocialPost
     fn convert_tweet_to_post(
         &self,
         tweet: &TwitterTweet,
-        users: &Option<Vec<TwitterUser>>,
-        media: &Option<Vec<TwitterMedia>>,
-        places: &Option<Vec<TwitterPlace>>,
+        users: Option<&[TwitterUser]>,
+        media: Option<&[TwitterMedia]>,
+        places: Option<&[TwitterPlace]>,
+        referenced: Option<&[TwitterTweet]>,
     ) -> SocialPost {
         // Find author information
-        let author = if let Some(author_id) = &tweet.author_id {
-            if let Some(users) = users {
-                users.iter()
-                    .find(|u| u.id == *author_id)
-                    .map(|u| self.convert_user_to_author(u))
-                    .unwrap_or_else(|| self.create_anonymous_author(author_id))
-            } else {
-                self.create_anonymous_author(author_id)
-            }
-        } else {
-            self.create_anonymous_author("unknown")
+        let author = match &tweet.author_id {
+            Some(author_id) => self.resolve_author(author_id, users),
+            None => anonymous_author("unknown", &self.privacy_config),
         };
 
-        // Extract metrics
-        let metrics = if let Some(public_metrics) = &tweet.public_metrics {
-            PostMetrics {
-                likes: public_metrics.like_count.unwrap_or(0),
-                shares: public_metrics.retweet_count.unwrap_or(0) + 
-                       public_metrics.quote_count.unwrap_or(0),
-                comments: public_metrics.reply_count.unwrap_or(0),
-                views: public_metrics.impression_count,
-                engagement_rate: None, // Will be calculated later
-            }
-        } else {
-            PostMetrics {
-                likes: 0,
-                shares: 0,
-                comments: 0,
-                views: None,
-                engagement_rate: None,
+        tweet_to_post(tweet, author, media, referenced, &self.privacy_config)
+    }
+
+    /// Resolve an author through the configured chain, falling back to anonymous
+    ///
+    /// The `Fetch` step is satisfied by `prefetch_authors`, which fills the
+    /// cache before conversion, so here it reads from the cache too.
+    fn resolve_author(&self, author_id: &str, users: Option<&[TwitterUser]>) -> AuthorInfo {
+        for step in &self.author_resolution {
+            let resolved = match step {
+                AuthorResolutionStep::Includes => users
+                    .and_then(|users| users.iter().find(|u| u.id == author_id))
+                    .map(|user| self.cache_author(user)),
+                AuthorResolutionStep::Cache | AuthorResolutionStep::Fetch => self.author_cache
+                    .read()
+                    .unwrap()
+                    .get(author_id)
+                    .cloned(),
+            };
+
+            if let Some(author) = resolved {
+                return author;
             }
-        };
+        }
 
-        // Extract location
-        let location = tweet.geo.as_ref().and_then(|geo| {
-            geo.coordinates.as_ref().map(|coords| {
-                if coords.coordinates.len() >= 2 {
-                    GeoLocation {
-                        longitude: coords.coordinates[0],
-                        latitude: coords.coordinates[1],
-                        radius_km: 1.0, // Default radius
-                        name: None,
-                    }
-                } else {
-                    GeoLocation {
-                        latitude: 0.0,
-                        longitude: 0.0,
-                        radius_km: 1.0,
-                        name: None,
-                    }
-                }
-            })
-        });
+        anonymous_author(author_id, &self.privacy_config)
+    }
 
-        // Extract hashtags
-        let hashtags = tweet.entities.as_ref()
-            .and_then(|e| e.hashtags.as_ref())
-            .map(|tags| tags.iter().map(|t| t.tag.clone()).collect())
-            .unwrap_or_default();
-
-        // Extract mentions
-        let mentions = tweet.entities.as_ref()
-            .and_then(|e| e.mentions.as_ref())
-            .map(|mentions| mentions.iter().map(|m| {
-                super::utils::anonymize_user_id(&m.username, &self.privacy_config.salt)
-            }).collect())
-            .unwrap_or_default();
-
-        // Extract URLs
-        let urls = tweet.entities.as_ref()
-            .and_then(|e| e.urls.as_ref())
-            .map(|urls| urls.iter().map(|u| {
-                u.expanded_url.as_ref()
-                    .or(u.unwound_url.as_ref())
-                    .unwrap_or(&u.url)
-                    .clone()
-            }).collect())
-            .unwrap_or_default();
-
-        // Extract media attachments
-        let media_attachments = if let Some(attachments) = &tweet.attachments {
-            if let Some(media_keys) = &attachments.media_keys {
-                if let Some(media_list) = media {
-                    media_keys.iter()
-                        .filter_map(|key| {
-                            media_list.iter().find(|m| m.media_key == *key)
-                        })
-                        .map(|m| self.convert_twitter_media_to_attachment(m))
-                        .collect()
-                } else {
-                    Vec::new()
-                }
-            } else {
-                Vec::new()
-            }
-        } else {
-            Vec::new()
+    /// Convert a user and remember it for later responses
+    fn cache_author(&self, user: &TwitterUser) -> AuthorInfo {
+        let author = user_to_author(user, &self.privacy_config);
+
+        let mut cache = self.author_cache.write().unwrap();
+        if cache.len() >= AUTHOR_CACHE_CAPACITY && !cache.contains_key(&user.id) {
+            cache.clear();
+        }
+        cache.insert(user.id.clone(), author.clone());
+
+        author
+    }
+
+    /// Look up authors missing from includes and the cache (`Fetch` step only)
+    ///
+    /// Lookup failures are logged; affected authors fall back to anonymous.
+    async fn prefetch_authors(&self, tweets: &[TwitterTweet], users: Option<&[TwitterUser]>) {
+        if !self.author_resolution.contains(&AuthorResolutionStep::Fetch) {
+            return;
+        }
+
+        let mut missing: Vec<&str> = {
+            let cache = self.author_cache.read().unwrap();
+            tweets.iter()
+                .filter_map(|tweet| tweet.author_id.as_deref())
+                .filter(|id| !users.is_some_and(|users| users.iter().any(|u| u.id == *id)))
+                .filter(|id| !cache.contains_key(*id))
+                .collect()
         };
+        missing.sort_unstable();
+        missing.dedup();
 
-        // Parse creation date
-        let created_at = tweet.created_at.as_ref()
-            .and_then(|date_str| DateTime::parse_from_rfc3339(date_str).ok())
-            .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or_else(Utc::now);
+        for batch in missing.chunks(USER_LOOKUP_BATCH) {
+            if let Err(e) = self.fetch_authors(batch).await {
+                warn!("Twitter author lookup failed for {} users: {}", batch.len(), e);
+            }
+        }
+    }
+
+    /// Fetch a batch of users by id and add them to the author cache
+    async fn fetch_authors(&self, ids: &[&str]) -> Result<(), ConnectorError> {
+        debug!("Fetching {} Twitter authors missing from includes", ids.len());
+        self.rate_limiter.acquire(self.platform_name()).await;
+
+        let url = format!(
+            "{}/users?ids={}&user.fields=id,username,name,verified,public_metrics,created_at,description",
+            self.base_url, ids.join(",")
+        );
+
+        let response = self.get("user_lookup", &url).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+
+            return Err(ConnectorError::ApiError {
+                code: status.as_u16(),
+                message: format!("Twitter API error: {}", error_text),
+            });
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+        let lookup: TwitterUsersResponse = serde_json::from_str(&body)
+            .map_err(|e| ConnectorError::ParseError { source: e })?;
+
+        for user in lookup.data.unwrap_or_default() {
+            self.cache_author(&user);
+        }
+
+        Ok(())
+    }
 
-        // Create metadata
-        let mut metadata = HashMap::new();
-        metadata.insert("tweet_id".to_string(), serde_json::Value::String(tweet.id.clone()));
+    /// GET a URL, waiting out and retrying once a 429 that names its reset time
+    ///
+    /// `operation` labels the request's latency metrics.
+    async fn get(&self, operation: &str, url: &str) -> Result<reqwest::Response, ConnectorError> {
+        super::utils::send_with_retry_after(self.max_retry_wait, || {
+            self.debug_capture.send(self.platform_name(), operation, self.client.get(url), &self.privacy_config)
+        }).await
+    }
+
+    /// Search recent tweets through the API
+    async fn search_api(
+        &self,
+        params: &SearchParams,
+        page_token: Option<&str>,
+    ) -> Result<(Vec<SocialPost>, Option<String>), ConnectorError> {
+        let started = Instant::now();
+        info!("Searching Twitter for: {}", params.query);
         
-        if let Some(context_annotations) = &tweet.context_annotations {
-            let contexts: Vec<serde_json::Value> = context_annotations.iter()
-                .map(|ctx| serde_json::json!({
-                    "domain": ctx.domain.name,
-                    "entity": ctx.entity.name
-                }))
-                .collect();
-            metadata.insert("context_annotations".to_string(), serde_json::Value::Array(contexts));
+        // Wait for a slot in the shared request budget
+        self.rate_limiter.acquire(self.platform_name()).await;
+
+        // Build search URL
+        let mut url = self.build_search_url(params);
+        if let Some(token) = page_token {
+            url.push_str(&format!("&next_token={}", urlencoding::encode(token)));
         }
+        debug!("Twitter search URL: {}", url);
 
-        if let Some(referenced_tweets) = &tweet.referenced_tweets {
-            let refs: Vec<serde_json::Value> = referenced_tweets.iter()
-                .map(|rt| serde_json::json!({
-                    "type": rt.ref_type,
-                    "id": rt.id
-                }))
+        // Make API request
+        let response = self.get("search", &url).await?;
+
+        // Update rate limit state
+        self.update_rate_limit(response.headers()).await;
+
+        // Check response status
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            
+            return Err(ConnectorError::ApiError {
+                code: status.as_u16(),
+                message: format!("Twitter API error: {}", error_text),
+            });
+        }
+
+        // Parse response
+        let search_response: TwitterSearchResponse = response
+            .json()
+            .await
+            .map_err(|e| ConnectorError::DeserializationError { message: e.to_string() })?;
+
+        // Handle API errors
+        if let Some(errors) = search_response.errors {
+            let error_messages: Vec<String> = errors.iter()
+                .map(|e| format!("{}: {}", e.title, e.detail.as_deref().unwrap_or("No details")))
                 .collect();
-            metadata.insert("referenced_tweets".to_string(), serde_json::Value::Array(refs));
+            
+            return Err(ConnectorError::ApiError {
+                code: 400,
+                message: format!("Twitter API errors: {}", error_messages.join(", ")),
+            });
         }
 
-        // Create privacy flags
-        let privacy_flags = PrivacyFlags {
-            anonymized: true,
-            sensitive_content: super::utils::contains_sensitive_content(&tweet.text),
-            location_generalized: location.is_some(),
-            retention_policy: self.privacy_config.retention_policy.clone(),
-            consent_status: ConsentStatus::Implied, // Public tweets imply consent
-        };
+        // Convert tweets to normalized posts
+        let includes = search_response.includes.as_ref();
+        let users = includes.and_then(|i| i.users.as_deref());
+        let tweets = search_response.data.unwrap_or_default();
+
+        self.prefetch_authors(&tweets, users).await;
+
+        let posts: Vec<SocialPost> = tweets.iter()
+            .map(|tweet| self.convert_tweet_to_post(
+                tweet,
+                users,
+                includes.and_then(|i| i.media.as_deref()),
+                includes.and_then(|i| i.places.as_deref()),
+                includes.and_then(|i| i.tweets.as_deref()),
+            ))
+            .collect();
+
+        let posts = super::utils::filter_by_content_type(posts, params.content_type.as_ref());
+
+        info!(results = posts.len(), elapsed_ms = started.elapsed().as_millis() as u64, "Retrieved {} tweets from Twitter", posts.len());
+        Ok((posts, search_response.meta.next_token))
+    }
+
+    /// GET a v1.1 endpoint, mapping 403 to a tier error
+    async fn get_v1(&self, operation: &str, url: &str) -> Result<reqwest::Response, ConnectorError> {
+        let response = self.get(operation, url).await?;
+
+        let status = response.status();
+        if status.as_u16() == 403 {
+            return Err(ConnectorError::ConfigError {
+                message: "Trending topics require Twitter API v1.1 access (Elevated tier or higher); \
+                          the configured bearer token was rejected with 403".to_string(),
+            });
+        }
+
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+
+            return Err(ConnectorError::ApiError {
+                code: status.as_u16(),
+                message: format!("Twitter API error: {}", error_text),
+            });
+        }
+
+        Ok(response)
+    }
 
-        let mut post = SocialPost {
-            id: tweet.id.clone(),
-            platform: "twitter".to_string(),
-            content: tweet.text.clone(),
-            author,
-            created_at,
-            metrics,
-            location,
-            language: tweet.lang.clone(),
-            media: media_attachments,
-            hashtags,
-            mentions,
-            urls,
-            metadata,
-            privacy_flags,
+    /// Resolve a location to a WOEID
+    ///
+    /// Accepts a numeric WOEID, or a town/country name looked up in
+    /// trends/available.json. The location list is fetched once and cached.
+    async fn resolve_woeid(&self, location: Option<&str>) -> Result<u64, ConnectorError> {
+        let location = match location.map(str::trim) {
+            None | Some("") => return Ok(WORLDWIDE_WOEID),
+            Some(location) => location,
         };
 
-        // Apply privacy compliance processing
-        super::utils::apply_privacy_compliance(&mut post, &self.privacy_config);
+        if let Ok(woeid) = location.parse::<u64>() {
+            return Ok(woeid);
+        }
+
+        let key = location.to_lowercase();
+        if self.woeid_cache.read().unwrap().is_empty() {
+            let url = format!("{}/trends/available.json", self.v1_base_url);
+            let locations: Vec<TwitterTrendLocation> = self.get_v1("trending", &url)
+                .await?
+                .json()
+                .await
+                .map_err(|e| ConnectorError::DeserializationError { message: e.to_string() })?;
+
+            let mut cache = self.woeid_cache.write().unwrap();
+            for entry in locations {
+                // Towns win over countries of the same name
+                if let Some(country) = entry.country.filter(|c| !c.is_empty()) {
+                    cache.entry(country.to_lowercase()).or_insert(entry.woeid);
+                }
+                cache.insert(entry.name.to_lowercase(), entry.woeid);
+            }
+        }
+
+        self.woeid_cache.read().unwrap().get(&key).copied().ok_or_else(|| ConnectorError::ConfigError {
+            message: format!("No Twitter trends available for location '{}'", location),
+        })
+    }
+
+    /// Parse trend names from a trends/place.json body
+    fn parse_trends(body: &str) -> Result<Vec<String>, ConnectorError> {
+        let places: Vec<TwitterTrendsResponse> = serde_json::from_str(body)
+            .map_err(|e| ConnectorError::ParseError { source: e })?;
 
-        post
+        Ok(places
+            .into_iter()
+            .flat_map(|place| place.trends)
+            .map(|trend| trend.name)
+            .collect())
     }
 
-    /// Convert Twitter user to AuthorInfo
-    fn convert_user_to_author(&self, user: &TwitterUser) -> AuthorInfo {
-        let follower_count = user.public_metrics.as_ref()
-            .and_then(|m| m.followers_count);
+    /// Hydrate many tweets by id, up to 100 per request
+    ///
+    /// Ids Twitter can't return (deleted, protected, suspended authors) are
+    /// omitted from the result rather than failing the batch.
+    pub async fn get_posts_by_ids(&self, ids: &[String]) -> Result<Vec<SocialPost>, ConnectorError> {
+        let mut posts = Vec::with_capacity(ids.len());
 
-        let account_created = user.created_at.as_ref()
-            .and_then(|date_str| DateTime::parse_from_rfc3339(date_str).ok())
-            .map(|dt| dt.with_timezone(&Utc));
+        for batch in ids.chunks(TWEET_LOOKUP_BATCH) {
+            info!("Looking up {} Twitter posts by ID", batch.len());
+            self.rate_limiter.acquire(self.platform_name()).await;
+
+            let url = format!("{}/tweets?ids={}&{}", self.base_url, batch.join(","), TWEET_LOOKUP_FIELDS);
 
-        AuthorInfo {
-            id_hash: super::utils::anonymize_user_id(&user.id, &self.privacy_config.salt),
-            username: user.username.clone(),
-            verified: user.verified.unwrap_or(false),
-            follower_count,
-            account_created,
-            account_type: Some("twitter".to_string()),
+            let response = self.get("get_by_id", &url).await?;
+
+            self.update_rate_limit(response.headers()).await;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+
+                return Err(ConnectorError::ApiError {
+                    code: status.as_u16(),
+                    message: format!("Twitter API error: {}", error_text),
+                });
+            }
+
+            let lookup: TwitterLookupResponse = response
+                .json()
+                .await
+                .map_err(|e| ConnectorError::DeserializationError { message: e.to_string() })?;
+
+            for error in lookup.errors.iter().flatten() {
+                debug!(
+                    "Twitter post {} omitted from lookup: {}",
+                    error.value.as_deref().unwrap_or("unknown"),
+                    error.detail.as_deref().unwrap_or(&error.title)
+                );
+            }
+
+            let includes = lookup.includes.as_ref();
+            let users = includes.and_then(|i| i.users.as_deref());
+            let tweets = lookup.data.unwrap_or_default();
+
+            self.prefetch_authors(&tweets, users).await;
+
+            posts.extend(tweets.iter().map(|tweet| self.convert_tweet_to_post(
+                tweet,
+                users,
+                includes.and_then(|i| i.media.as_deref()),
+                includes.and_then(|i| i.places.as_deref()),
+                includes.and_then(|i| i.tweets.as_deref()),
+            )));
         }
+
+        Ok(posts)
     }
 
-    /// Create anonymous author info
-    fn create_anonymous_author(&self, user_id: &str) -> AuthorInfo {
-        AuthorInfo {
-            id_hash: super::utils::anonymize_user_id(user_id, &self.privacy_config.salt),
-            username: "anonymous".to_string(),
-            verified: false,
-            follower_count: None,
-            account_created: None,
-            account_type: Some("twitter".to_string()),
+    /// Collect every tweet in a conversation, root first
+    ///
+    /// Replies come from a `conversation_id:` search; the root tweet is
+    /// looked up separately since the search doesn't return it. Posts are
+    /// ordered so each parent precedes its replies (siblings oldest first);
+    /// replies whose parent wasn't returned follow the root's subtree.
+    pub async fn get_conversation(&self, conversation_id: &str) -> Result<Vec<SocialPost>, ConnectorError> {
+        info!("Getting Twitter conversation {}", conversation_id);
+
+        let params = SearchParams {
+            query: format!("conversation_id:{}", conversation_id),
+            max_results: Some(100),
+            ..Default::default()
+        };
+        let mut posts = self.search_posts(&params).await?;
+
+        if !posts.iter().any(|post| post.native_id() == conversation_id) {
+            if let Some(root) = self.get_post_by_id(conversation_id).await? {
+                posts.push(root);
+            }
         }
+
+        Ok(order_thread(posts, conversation_id))
     }
 
-    /// Convert Twitter media to MediaAttachment
-    fn convert_twitter_media_to_attachment(&self, media: &TwitterMedia) -> MediaAttachment {
-        let dimensions = if let (Some(width), Some(height)) = (media.width, media.height) {
-            Some(MediaDimensions { width, height })
-        } else {
-            None
+    /// Search query with operators narrowing results to the content type
+    /// and bounding box
+    ///
+    /// The operators only save bandwidth; results are still post-filtered
+    /// because Twitter's media/link classification is coarser than ours.
+    fn build_query(params: &SearchParams) -> String {
+        let mut operators = match params.content_type {
+            Some(ContentType::Image | ContentType::Video) => vec!["has:media".to_string()],
+            Some(ContentType::Link) => vec!["has:links -has:media".to_string()],
+            Some(ContentType::Text) => vec!["-has:media -has:links".to_string()],
+            Some(ContentType::All) | None => Vec::new(),
         };
+        operators.extend(params.bounds.as_ref().and_then(bounding_box_operator));
 
-        MediaAttachment {
-            media_type: media.media_type.clone(),
-            url: media.url.as_ref()
-                .or(media.preview_image_url.as_ref())
-                .unwrap_or(&"".to_string())
-                .clone(),
-            alt_text: media.alt_text.clone(),
-            dimensions,
-            file_size: None, // Not provided by Twitter API
+        if operators.is_empty() {
+            return params.query.clone();
         }
+        format!("({}) {}", params.query, operators.join(" "))
     }
 
     /// Build search query URL with parameters
     fn build_search_url(&self, params: &SearchParams) -> String {
         let mut url = format!("{}/tweets/search/recent", self.base_url);
         let mut query_params = vec![
-            ("query".to_string(), params.query.clone()),
-            ("max_results".to_string(), params.max_results.unwrap_or(10).min(100).to_string()),
+            ("query".to_string(), Self::build_query(params)),
+            ("max_results".to_string(), Platform::Twitter.results_per_request(params.max_results).to_string()),
         ];
 
         // Add tweet fields
         query_params.push((
             "tweet.fields".to_string(),
-            "id,text,author_id,created_at,public_metrics,geo,lang,entities,attachments,context_annotations,referenced_tweets".to_string()
+            "id,text,author_id,created_at,public_metrics,geo,lang,entities,attachments,context_annotations,referenced_tweets,conversation_id,edit_history_tweet_ids,edit_controls,possibly_sensitive".to_string()
         ));
 
         // Add user fields
@@ -688,6 +1070,11 @@ impl TwitterConnector {
             query_params.push(("end_time".to_string(), end_date.to_rfc3339()));
         }
 
+        // Only tweets newer than the last one collected
+        if let Some(since_id) = &params.since_id {
+            query_params.push(("since_id".to_string(), since_id.clone()));
+        }
+
         // Build final URL
         let query_string = query_params.iter()
             .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
@@ -696,115 +1083,302 @@ impl TwitterConnector {
 
         format!("{}?{}", url, query_string)
     }
-}
 
-#[async_trait]
-impl ApiConnector for TwitterConnector {
-    fn platform_name(&self) -> &'static str {
-        "twitter"
+    /// Build filtered stream URL with the same field set as search
+    fn build_stream_url(&self) -> String {
+        format!(
+            "{}/tweets/search/stream?tweet.fields=id,text,author_id,created_at,public_metrics,geo,lang,entities,attachments,context_annotations,referenced_tweets,conversation_id,edit_history_tweet_ids,edit_controls,possibly_sensitive&user.fields=id,username,name,verified,public_metrics,created_at,description&media.fields=media_key,type,url,preview_image_url,alt_text,width,height,duration_ms,public_metrics&place.fields=id,full_name,name,country,country_code,geo,place_type&expansions=author_id,attachments.media_keys,geo.place_id,referenced_tweets.id",
+            self.base_url
+        )
     }
 
-    fn is_configured(&self) -> bool {
-        !self.bearer_token.is_empty()
-    }
+    /// Map a stream rules response status to an error, if any
+    ///
+    /// The filtered stream endpoints need Elevated access; Essential tokens
+    /// are rejected with 401/403, which is a configuration problem rather
+    /// than a transient API failure.
+    async fn check_stream_rules_response(response: reqwest::Response) -> Result<reqwest::Response, ConnectorError> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
 
-    async fn get_rate_limit_status(&self) -> Result<RateLimitInfo, ConnectorError> {
-        let state = self.rate_limit_state.read().await;
-        Ok(RateLimitInfo {
-            remaining: state.remaining,
-            limit: state.limit,
-            reset_time: state.reset_time,
-            window_duration: chrono::Duration::minutes(15),
+        let error_text = response.text().await.unwrap_or_default();
+        if status.as_u16() == 401 || status.as_u16() == 403 {
+            return Err(ConnectorError::ConfigError {
+                message: format!("Filtered stream requires Twitter API Elevated access: {}", error_text),
+            });
+        }
+
+        Err(ConnectorError::ApiError {
+            code: status.as_u16(),
+            message: format!("Twitter API error: {}", error_text),
         })
     }
 
-    async fn search_posts(&self, params: &SearchParams) -> Result<Vec<SocialPost>, ConnectorError> {
-        info!("Searching Twitter for: {}", params.query);
-        
-        // Wait for rate limit if necessary
-        self.wait_for_rate_limit().await?;
+    /// Replace the filtered stream rules with the given rule values
+    async fn set_stream_rules(&self, rules: &[String]) -> Result<(), ConnectorError> {
+        let rules_url = format!("{}/tweets/search/stream/rules", self.base_url);
 
-        // Build search URL
-        let url = self.build_search_url(params);
-        debug!("Twitter search URL: {}", url);
+        // Wait for a slot in the shared request budget
+        self.rate_limiter.acquire(self.platform_name()).await;
 
-        // Make API request
-        let response = self.client
-            .get(&url)
-            .send()
+        // Fetch existing rules so they can be replaced rather than accumulated
+        let response = self.debug_capture.send(self.platform_name(), "stream_rules", self.client.get(&rules_url), &self.privacy_config).await?;
+        let response = Self::check_stream_rules_response(response).await?;
+
+        let existing: TwitterStreamRulesResponse = response
+            .json()
             .await
-            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+            .map_err(|e| ConnectorError::DeserializationError { message: e.to_string() })?;
+
+        let existing_ids: Vec<String> = existing.data
+            .unwrap_or_default()
+            .into_iter()
+            .map(|rule| rule.id)
+            .collect();
+
+        if !existing_ids.is_empty() {
+            debug!("Deleting {} existing Twitter stream rules", existing_ids.len());
+            let request = self.client
+                .post(&rules_url)
+                .json(&serde_json::json!({ "delete": { "ids": existing_ids } }));
+            let response = self.debug_capture.send(self.platform_name(), "stream_rules", request, &self.privacy_config).await?;
+            Self::check_stream_rules_response(response).await?;
+        }
 
-        // Update rate limit state
-        self.update_rate_limit(response.headers()).await;
+        // Add the requested rules
+        let additions: Vec<serde_json::Value> = rules.iter()
+            .map(|rule| serde_json::json!({ "value": rule }))
+            .collect();
 
-        // Check response status
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            
-            return Err(ConnectorError::ApiError {
-                code: status.as_u16(),
-                message: format!("Twitter API error: {}", error_text),
-            });
-        }
+        let request = self.client
+            .post(&rules_url)
+            .json(&serde_json::json!({ "add": additions }));
+        let response = self.debug_capture.send(self.platform_name(), "stream_rules", request, &self.privacy_config).await?;
+        let response = Self::check_stream_rules_response(response).await?;
 
-        // Parse response
-        let search_response: TwitterSearchResponse = response
+        let added: TwitterStreamRulesResponse = response
             .json()
             .await
-            .map_err(|e| ConnectorError::ParseError { 
-                source: serde_json::Error::custom(e.to_string()) 
-            })?;
+            .map_err(|e| ConnectorError::DeserializationError { message: e.to_string() })?;
 
-        // Handle API errors
-        if let Some(errors) = search_response.errors {
+        // Invalid rules are reported in `errors` with a 200/201 status
+        if let Some(errors) = added.errors {
             let error_messages: Vec<String> = errors.iter()
                 .map(|e| format!("{}: {}", e.title, e.detail.as_deref().unwrap_or("No details")))
                 .collect();
-            
+
             return Err(ConnectorError::ApiError {
                 code: 400,
-                message: format!("Twitter API errors: {}", error_messages.join(", ")),
+                message: format!("Twitter stream rule errors: {}", error_messages.join(", ")),
             });
         }
 
-        // Convert tweets to normalized posts
-        let posts = if let Some(tweets) = search_response.data {
-            tweets.iter()
-                .map(|tweet| self.convert_tweet_to_post(
-                    tweet,
-                    &search_response.includes.as_ref().and_then(|i| i.users.as_ref()),
-                    &search_response.includes.as_ref().and_then(|i| i.media.as_ref()),
-                    &search_response.includes.as_ref().and_then(|i| i.places.as_ref()),
-                ))
-                .collect()
-        } else {
-            Vec::new()
+        let values: Vec<String> = added.data.unwrap_or_default().into_iter().map(|rule| rule.value).collect();
+        info!("Set {} Twitter stream rules: {}", values.len(), values.join(", "));
+        Ok(())
+    }
+
+    /// Parse one line of the filtered stream
+    ///
+    /// Returns `None` for keep-alive blank lines and events without a tweet.
+    fn parse_stream_line(&self, line: &str) -> Option<Result<SocialPost, ConnectorError>> {
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+
+        let event: TwitterStreamEvent = match serde_json::from_str(line) {
+            Ok(event) => event,
+            Err(e) => return Some(Err(ConnectorError::ParseError { source: e })),
         };
 
-        info!("Retrieved {} tweets from Twitter", posts.len());
-        Ok(posts)
+        let tweet = match event.data {
+            Some(tweet) => tweet,
+            None => {
+                // Operational messages (e.g. forced disconnects) arrive as error-only events
+                if let Some(errors) = event.errors {
+                    let error_messages: Vec<String> = errors.iter()
+                        .map(|e| format!("{}: {}", e.title, e.detail.as_deref().unwrap_or("No details")))
+                        .collect();
+                    warn!("Twitter stream reported errors: {}", error_messages.join(", "));
+                }
+                return None;
+            }
+        };
+
+        let (users, media, places, referenced) = match event.includes {
+            Some(includes) => (includes.users, includes.media, includes.places, includes.tweets),
+            None => (None, None, None, None),
+        };
+
+        Some(Ok(self.convert_tweet_to_post(&tweet, users.as_deref(), media.as_deref(), places.as_deref(), referenced.as_deref())))
+    }
+
+    /// Stream tweets matching the given rules in real time
+    ///
+    /// Sets the filtered stream rules via `tweets/search/stream/rules`, then
+    /// connects to `tweets/search/stream` and yields each tweet as it arrives.
+    /// Dropped or stalled connections are reconnected with exponential backoff.
+    ///
+    /// Requires Elevated access; returns `ConfigError` up front if the token
+    /// cannot manage stream rules.
+    pub async fn stream_posts(
+        &self,
+        rules: Vec<String>,
+    ) -> Result<impl Stream<Item = Result<SocialPost, ConnectorError>> + '_, ConnectorError> {
+        info!("Starting Twitter filtered stream with {} rules", rules.len());
+
+        self.set_stream_rules(&rules).await?;
+        let url = self.build_stream_url();
+
+        Ok(async_stream::stream! {
+            let mut backoff = STREAM_INITIAL_BACKOFF;
+
+            loop {
+                let request = self.client.get(&url).timeout(STREAM_CONNECTION_TIMEOUT);
+                match super::utils::send_timed(self.platform_name(), "stream", request).await {
+                    Ok(response) if response.status().is_success() => {
+                        info!("Connected to Twitter filtered stream");
+                        backoff = STREAM_INITIAL_BACKOFF;
+
+                        let mut body = response.bytes_stream();
+                        let mut buffer: Vec<u8> = Vec::new();
+
+                        loop {
+                            match tokio::time::timeout(STREAM_STALL_TIMEOUT, body.next()).await {
+                                Ok(Some(Ok(chunk))) => {
+                                    buffer.extend_from_slice(&chunk);
+
+                                    // Tweets are newline-delimited; keep any partial line buffered
+                                    while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                                        let line: Vec<u8> = buffer.drain(..=pos).collect();
+                                        if let Some(result) = self.parse_stream_line(&String::from_utf8_lossy(&line)) {
+                                            yield result;
+                                        }
+                                    }
+                                },
+                                Ok(Some(Err(e))) => {
+                                    warn!("Twitter stream read error: {}", e);
+                                    break;
+                                },
+                                Ok(None) => {
+                                    warn!("Twitter stream closed by server");
+                                    break;
+                                },
+                                Err(_) => {
+                                    warn!("Twitter stream stalled for {:?}", STREAM_STALL_TIMEOUT);
+                                    break;
+                                }
+                            }
+                        }
+                    },
+                    Ok(response) => {
+                        let status = response.status().as_u16();
+                        let error_text = response.text().await.unwrap_or_default();
+
+                        // Authorization failures won't resolve by reconnecting
+                        if status == 401 || status == 403 {
+                            yield Err(ConnectorError::ConfigError {
+                                message: format!("Filtered stream requires Twitter API Elevated access: {}", error_text),
+                            });
+                            return;
+                        }
+
+                        if status == 429 {
+                            backoff = backoff.max(STREAM_RATE_LIMIT_BACKOFF);
+                        }
+
+                        warn!("Twitter stream connection rejected ({}): {}", status, error_text);
+                    },
+                    Err(e) => {
+                        warn!("Twitter stream connection failed: {}", e);
+                    }
+                }
+
+                warn!("Reconnecting to Twitter filtered stream in {:?}", backoff);
+                sleep(backoff).await;
+                backoff = (backoff * 2).min(STREAM_MAX_BACKOFF);
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl ApiConnector for TwitterConnector {
+    fn platform_name(&self) -> &'static str {
+        "twitter"
+    }
+
+    fn is_configured(&self) -> bool {
+        !self.bearer_token.is_empty()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_search: true,
+            supports_user_posts: false,
+            supports_trending: true,
+            supports_streaming: true,
+            supports_post_by_id: true,
+        }
+    }
+
+    async fn get_rate_limit_status(&self) -> Result<RateLimitInfo, ConnectorError> {
+        let state = self.rate_limit_state.read().await;
+        Ok(RateLimitInfo {
+            remaining: state.remaining,
+            limit: state.limit,
+            reset_time: state.reset_time,
+            window_duration: chrono::Duration::minutes(15),
+        })
+    }
+
+    #[instrument(skip_all, fields(platform = "twitter", query = %params.query))]
+    async fn search_posts(&self, params: &SearchParams) -> Result<Vec<SocialPost>, ConnectorError> {
+        // Fail fast on queries the platform would reject with a bare 400
+        validate_query(&params.query, self.platform_name())?;
+
+        let result = self.search_api(params, None).await.map(|(posts, _)| posts);
+        match (&result, &self.nitter) {
+            (Err(e), Some(nitter)) if is_quota_exhausted(e) => {
+                warn!("Twitter search quota exhausted ({}), falling back to Nitter", e);
+                nitter.search(params, &self.privacy_config).await
+            },
+            _ => result,
+        }
+    }
+
+    async fn search_next_page(
+        &self,
+        params: &SearchParams,
+        page_token: Option<&str>,
+    ) -> Result<(Vec<SocialPost>, Option<String>), ConnectorError> {
+        validate_query(&params.query, self.platform_name())?;
+        self.search_api(params, page_token).await
     }
 
     async fn get_post_by_id(&self, post_id: &str) -> Result<Option<SocialPost>, ConnectorError> {
         info!("Getting Twitter post by ID: {}", post_id);
+
+        // Cache hits don't count against the rate limit
+        if let Some(post) = self.post_cache.get(self.platform_name(), post_id) {
+            debug!("Serving Twitter post {} from cache", post_id);
+            return Ok(Some(post));
+        }
         
-        // Wait for rate limit if necessary
-        self.wait_for_rate_limit().await?;
+        // Wait for a slot in the shared request budget
+        self.rate_limiter.acquire(self.platform_name()).await;
 
         // Build URL for single tweet lookup
         let url = format!(
-            "{}/tweets/{}?tweet.fields=id,text,author_id,created_at,public_metrics,geo,lang,entities,attachments,context_annotations,referenced_tweets&user.fields=id,username,name,verified,public_metrics,created_at&media.fields=media_key,type,url,preview_image_url,alt_text,width,height&expansions=author_id,attachments.media_keys",
-            self.base_url, post_id
+            "{}/tweets/{}?{}",
+            self.base_url, post_id, TWEET_LOOKUP_FIELDS
         );
 
         // Make API request
-        let response = self.client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+        let response = self.get("get_by_id", &url).await?;
 
         // Update rate limit state
         self.update_rate_limit(response.headers()).await;
@@ -828,25 +1402,28 @@ impl ApiConnector for TwitterConnector {
         let tweet_response: serde_json::Value = response
             .json()
             .await
-            .map_err(|e| ConnectorError::ParseError { 
-                source: serde_json::Error::custom(e.to_string()) 
-            })?;
+            .map_err(|e| ConnectorError::DeserializationError { message: e.to_string() })?;
 
         // Extract tweet data
         if let Some(tweet_data) = tweet_response.get("data") {
             let tweet: TwitterTweet = serde_json::from_value(tweet_data.clone())
                 .map_err(|e| ConnectorError::ParseError { source: e })?;
 
-            let includes = tweet_response.get("includes")
+            let includes: Option<TwitterIncludes> = tweet_response.get("includes")
                 .and_then(|i| serde_json::from_value(i.clone()).ok());
+            let users = includes.as_ref().and_then(|i| i.users.as_deref());
+
+            self.prefetch_authors(std::slice::from_ref(&tweet), users).await;
 
             let post = self.convert_tweet_to_post(
                 &tweet,
-                &includes.as_ref().and_then(|i: &TwitterIncludes| i.users.as_ref()),
-                &includes.as_ref().and_then(|i: &TwitterIncludes| i.media.as_ref()),
-                &includes.as_ref().and_then(|i: &TwitterIncludes| i.places.as_ref()),
+                users,
+                includes.as_ref().and_then(|i| i.media.as_deref()),
+                includes.as_ref().and_then(|i| i.places.as_deref()),
+                includes.as_ref().and_then(|i| i.tweets.as_deref()),
             );
 
+            self.post_cache.insert(self.platform_name(), post_id, post.clone());
             Ok(Some(post))
         } else {
             Ok(None)
@@ -861,11 +1438,62 @@ impl ApiConnector for TwitterConnector {
         })
     }
 
-    async fn get_trending_topics(&self, _location: Option<&str>) -> Result<Vec<String>, ConnectorError> {
-        // Trending topics require Twitter API v1.1 or higher tier access
-        Err(ConnectorError::ConfigError {
-            message: "Trending topics require Twitter API v1.1 or higher tier access".to_string(),
-        })
+    async fn get_trending_topics(&self, location: Option<&str>) -> Result<Vec<String>, ConnectorError> {
+        let woeid = self.resolve_woeid(location).await?;
+        info!("Getting Twitter trending topics for WOEID {}", woeid);
+
+        let url = format!("{}/trends/place.json?id={}", self.v1_base_url, woeid);
+        let body = self.get_v1("trending", &url)
+            .await?
+            .text()
+            .await
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+
+        Self::parse_trends(&body)
+    }
+
+    async fn get_user_profile(&self, user_id: &str) -> Result<Option<UserProfile>, ConnectorError> {
+        self.rate_limiter.acquire(self.platform_name()).await;
+
+        // All-digit values are user ids, anything else a username
+        let user_id = user_id.trim_start_matches('@');
+        let path = if !user_id.is_empty() && user_id.chars().all(|c| c.is_ascii_digit()) {
+            format!("users/{}", user_id)
+        } else {
+            format!("users/by/username/{}", user_id)
+        };
+        let url = format!(
+            "{}/{}?user.fields=id,username,name,verified,public_metrics,created_at,description,profile_image_url,location,url",
+            self.base_url, path
+        );
+
+        let response = self.get("user_profile", &url).await?;
+        self.update_rate_limit(response.headers()).await;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+
+            return Err(ConnectorError::ApiError {
+                code: status.as_u16(),
+                message: format!("Twitter API error: {}", error_text),
+            });
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+        let lookup: TwitterUserResponse = serde_json::from_str(&body)
+            .map_err(|e| ConnectorError::ParseError { source: e })?;
+
+        Ok(lookup.data.map(|user| {
+            self.cache_author(&user);
+            user_to_profile(&user, &self.privacy_config)
+        }))
     }
 
     async fn validate_credentials(&self) -> Result<bool, ConnectorError> {
@@ -877,13 +1505,10 @@ impl ApiConnector for TwitterConnector {
             self.base_url
         );
 
-        let response = self.client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| ConnectorError::NetworkError { source: e })?;
+        let response = self.get("validate", &url).await?;
+        let status = response.status().as_u16();
 
-        match response.status().as_u16() {
+        match status {
             200 => {
                 info!("Twitter API credentials are valid");
                 Ok(true)
@@ -895,7 +1520,7 @@ impl ApiConnector for TwitterConnector {
             _ => {
                 let error_text = response.text().await.unwrap_or_default();
                 Err(ConnectorError::ApiError {
-                    code: response.status().as_u16(),
+                    code: status,
                     message: format!("Credential validation failed: {}", error_text),
                 })
             }
@@ -913,6 +1538,400 @@ impl ApiConnector for TwitterConnector {
             },
         ]
     }
+
+    fn max_search_age(&self) -> Option<chrono::Duration> {
+        Some(chrono::Duration::days(RECENT_SEARCH_DAYS))
+    }
+
+    fn estimate_duration(&self, total_requests: u32) -> Duration {
+        self.rate_limiter.estimate(self.platform_name(), total_requests, MIN_REQUEST_SPACING)
+    }
+}
+
+/// Convert a stored single-tweet response (`{"data": ..., "includes": ...}`) again
+///
+/// The author comes from `includes.users`, falling back to anonymous like
+/// a connector with no cached author would.
+pub fn reprocess(raw: &serde_json::Value, privacy: &PrivacyConfig) -> Result<SocialPost, ConnectorError> {
+    let tweet_data = raw.get("data").ok_or_else(|| ConnectorError::DeserializationError {
+        message: "Twitter payload has no data".to_string(),
+    })?;
+    let tweet: TwitterTweet = serde_json::from_value(tweet_data.clone())
+        .map_err(|e| ConnectorError::ParseError { source: e })?;
+    let includes: Option<TwitterIncludes> = raw.get("includes")
+        .map(|i| serde_json::from_value(i.clone()))
+        .transpose()
+        .map_err(|e| ConnectorError::ParseError { source: e })?;
+
+    let author_id = tweet.author_id.as_deref().unwrap_or("unknown");
+    let author = includes.as_ref()
+        .and_then(|i| i.users.as_ref())
+        .and_then(|users| users.iter().find(|u| u.id == author_id))
+        .map(|user| user_to_author(user, privacy))
+        .unwrap_or_else(|| anonymous_author(author_id, privacy));
+
+    Ok(tweet_to_post(
+        &tweet,
+        author,
+        includes.as_ref().and_then(|i| i.media.as_deref()),
+        includes.as_ref().and_then(|i| i.tweets.as_deref()),
+        privacy,
+    ))
+}
+
+/// Convert a tweet to a normalized SocialPost with an already resolved author
+///
+/// Needs no connector state, so stored API payloads can be converted again
+/// (see `reprocess`). `referenced` is includes.tweets, used to expand quotes.
+fn tweet_to_post(
+    tweet: &TwitterTweet,
+    author: AuthorInfo,
+    media: Option<&[TwitterMedia]>,
+    referenced: Option<&[TwitterTweet]>,
+    privacy: &PrivacyConfig,
+) -> SocialPost {
+    // Extract metrics
+    let metrics = if let Some(public_metrics) = &tweet.public_metrics {
+        PostMetrics {
+            likes: public_metrics.like_count.unwrap_or(0),
+            shares: public_metrics.retweet_count.unwrap_or(0) + 
+                   public_metrics.quote_count.unwrap_or(0),
+            comments: public_metrics.reply_count.unwrap_or(0),
+            views: public_metrics.impression_count,
+            engagement_rate: None, // Will be calculated later
+        }
+    } else {
+        PostMetrics {
+            likes: 0,
+            shares: 0,
+            comments: 0,
+            views: None,
+            engagement_rate: None,
+        }
+    };
+
+    // Extract location
+    let location = tweet.geo.as_ref().and_then(|geo| {
+        geo.coordinates.as_ref().map(|coords| {
+            if coords.coordinates.len() >= 2 {
+                GeoLocation {
+                    longitude: coords.coordinates[0],
+                    latitude: coords.coordinates[1],
+                    radius_km: 1.0, // Default radius
+                    name: None,
+                }
+            } else {
+                GeoLocation {
+                    latitude: 0.0,
+                    longitude: 0.0,
+                    radius_km: 1.0,
+                    name: None,
+                }
+            }
+        })
+    });
+
+    // Extract hashtags
+    let hashtags = tweet.entities.as_ref()
+        .and_then(|e| e.hashtags.as_ref())
+        .map(|tags| tags.iter().map(|t| t.tag.clone()).collect())
+        .unwrap_or_default();
+
+    // Extract mentions
+    let mentions = tweet.entities.as_ref()
+        .and_then(|e| e.mentions.as_ref())
+        .map(|mentions| mentions.iter().map(|m| {
+            super::utils::anonymize_user_id(&m.username, privacy)
+        }).collect())
+        .unwrap_or_default();
+
+    // Extract URLs
+    let urls = tweet.entities.as_ref()
+        .and_then(|e| e.urls.as_ref())
+        .map(|urls| urls.iter().map(|u| {
+            super::utils::decode_html_entities(
+                u.expanded_url.as_ref()
+                    .or(u.unwound_url.as_ref())
+                    .unwrap_or(&u.url),
+            )
+        }).collect())
+        .unwrap_or_default();
+
+    // Extract media attachments
+    let media_attachments = if let Some(attachments) = &tweet.attachments {
+        if let Some(media_keys) = &attachments.media_keys {
+            if let Some(media_list) = media {
+                media_keys.iter()
+                    .filter_map(|key| {
+                        media_list.iter().find(|m| m.media_key == *key)
+                    })
+                    .map(media_to_attachment)
+                    .collect()
+            } else {
+                Vec::new()
+            }
+        } else {
+            Vec::new()
+        }
+    } else {
+        Vec::new()
+    };
+
+    // Normalized text (entities decoded, NFKC, invisible characters stripped);
+    // Twitter escapes &, < and > in tweet text
+    let mut content = super::utils::normalize_text(&super::utils::decode_html_entities(&tweet.text));
+
+    // Create metadata
+    let mut metadata = HashMap::new();
+    metadata.insert("tweet_id".to_string(), serde_json::Value::String(tweet.id.clone()));
+
+    // A quote's own text often only makes sense next to the quoted tweet
+    let quoted_id = tweet.referenced_tweets.iter()
+        .flatten()
+        .find(|rt| rt.ref_type == "quoted")
+        .map(|rt| rt.id.as_str());
+    if let Some(quoted_id) = quoted_id {
+        metadata.insert("quoted_tweet_id".to_string(), serde_json::Value::String(quoted_id.to_string()));
+
+        if let Some(quoted) = referenced.into_iter().flatten().find(|t| t.id == quoted_id) {
+            content.push_str(QUOTED_TWEET_DELIMITER);
+            content.push_str(&super::utils::normalize_text(&super::utils::decode_html_entities(&quoted.text)));
+        }
+    }
+
+    // Parse creation date
+    let created_at = super::utils::resolve_created_at(
+        tweet.created_at.as_ref()
+            .and_then(|date_str| DateTime::parse_from_rfc3339(date_str).ok())
+            .map(|dt| dt.with_timezone(&Utc)),
+        &tweet.id,
+        &mut metadata,
+    );
+    
+    if let Some(context_annotations) = &tweet.context_annotations {
+        let contexts: Vec<serde_json::Value> = context_annotations.iter()
+            .map(|ctx| serde_json::json!({
+                "domain": ctx.domain.name,
+                "entity": ctx.entity.name
+            }))
+            .collect();
+        metadata.insert("context_annotations".to_string(), serde_json::Value::Array(contexts));
+    }
+
+    if let Some(referenced_tweets) = &tweet.referenced_tweets {
+        let refs: Vec<serde_json::Value> = referenced_tweets.iter()
+            .map(|rt| serde_json::json!({
+                "type": rt.ref_type,
+                "id": rt.id
+            }))
+            .collect();
+        metadata.insert("referenced_tweets".to_string(), serde_json::Value::Array(refs));
+
+        if let Some(parent) = referenced_tweets.iter().find(|rt| rt.ref_type == "replied_to") {
+            metadata.insert("reply_to".to_string(), serde_json::Value::String(parent.id.clone()));
+        }
+    }
+
+    if let Some(conversation_id) = &tweet.conversation_id {
+        metadata.insert("thread_id".to_string(), serde_json::Value::String(conversation_id.clone()));
+    }
+
+    // Earlier versions of an edited tweet, oldest first (empty if never edited)
+    let edit_history: Vec<serde_json::Value> = tweet.edit_history_tweet_ids.as_ref()
+        .map(|ids| ids.iter()
+            .filter(|id| **id != tweet.id)
+            .map(|id| serde_json::Value::String(id.clone()))
+            .collect())
+        .unwrap_or_default();
+    metadata.insert("edit_count".to_string(), serde_json::Value::from(edit_history.len()));
+    metadata.insert("edit_history".to_string(), serde_json::Value::Array(edit_history));
+
+    if let Some(controls) = &tweet.edit_controls {
+        metadata.insert("edit_controls".to_string(), serde_json::json!({
+            "edits_remaining": controls.edits_remaining,
+            "is_edit_eligible": controls.is_edit_eligible,
+            "editable_until": controls.editable_until
+        }));
+    }
+
+    // Platform label, kept apart from our keyword heuristics (content_warnings)
+    let possibly_sensitive = tweet.possibly_sensitive.unwrap_or(false);
+    metadata.insert("possibly_sensitive".to_string(), serde_json::Value::Bool(possibly_sensitive));
+
+    // Create privacy flags
+    let privacy_flags = PrivacyFlags {
+        anonymized: true,
+        sensitive_content: super::utils::contains_sensitive_content(&content) || possibly_sensitive,
+        location_generalized: false, // Set by apply_privacy_compliance
+        retention_policy: privacy.retention_policy.clone(),
+        consent_status: ConsentStatus::Implied, // Public tweets imply consent
+    };
+
+    let mut post = SocialPost {
+        id: tweet.id.clone(),
+        platform: "twitter".to_string(),
+        content,
+        author,
+        created_at,
+        metrics,
+        location,
+        language: tweet.lang.clone(),
+        media: media_attachments,
+        hashtags,
+        mentions,
+        urls,
+        metadata,
+        privacy_flags,
+    };
+
+    // Apply privacy compliance processing
+    super::utils::assign_canonical_id(&mut post);
+    super::utils::apply_privacy_compliance(&mut post, privacy);
+
+    post
+}
+
+/// Whether a search failed because the API quota is used up
+fn is_quota_exhausted(error: &ConnectorError) -> bool {
+    matches!(error, ConnectorError::RateLimitExceeded { .. } | ConnectorError::ApiError { code: 429, .. })
+}
+
+/// Convert Twitter user to AuthorInfo
+fn user_to_author(user: &TwitterUser, privacy: &PrivacyConfig) -> AuthorInfo {
+    let follower_count = user.public_metrics.as_ref()
+        .and_then(|m| m.followers_count);
+
+    let account_created = user.created_at.as_ref()
+        .and_then(|date_str| DateTime::parse_from_rfc3339(date_str).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+
+    AuthorInfo {
+        id_hash: super::utils::anonymize_user_id(&user.id, privacy),
+        username: user.username.clone(),
+        verified: user.verified.unwrap_or(false),
+        follower_count,
+        account_created,
+        account_type: Some("twitter".to_string()),
+    }
+}
+
+/// Convert Twitter user to UserProfile
+fn user_to_profile(user: &TwitterUser, privacy: &PrivacyConfig) -> UserProfile {
+    let metrics = user.public_metrics.as_ref();
+
+    UserProfile {
+        id: super::utils::anonymize_user_id(&user.id, privacy),
+        platform: Platform::Twitter,
+        username: user.username.clone(),
+        display_name: Some(user.name.clone()),
+        bio: user.description.clone().filter(|bio| !bio.is_empty()),
+        follower_count: metrics.and_then(|m| m.followers_count).unwrap_or(0),
+        following_count: metrics.and_then(|m| m.following_count).unwrap_or(0),
+        post_count: metrics.and_then(|m| m.tweet_count).unwrap_or(0),
+        verified: user.verified.unwrap_or(false),
+        account_created: user.created_at.as_ref()
+            .and_then(|date_str| DateTime::parse_from_rfc3339(date_str).ok())
+            .map(|dt| dt.with_timezone(&Utc)),
+        profile_image_url: user.profile_image_url.clone(),
+        location: user.location.clone().filter(|location| !location.is_empty()),
+        website: user.url.clone().filter(|url| !url.is_empty()),
+        last_updated: Utc::now(),
+    }
+}
+
+/// Create anonymous author info
+fn anonymous_author(user_id: &str, privacy: &PrivacyConfig) -> AuthorInfo {
+    AuthorInfo {
+        id_hash: super::utils::anonymize_user_id(user_id, privacy),
+        username: "anonymous".to_string(),
+        verified: false,
+        follower_count: None,
+        account_created: None,
+        account_type: Some("twitter".to_string()),
+    }
+}
+
+/// Convert Twitter media to MediaAttachment
+fn media_to_attachment(media: &TwitterMedia) -> MediaAttachment {
+    let dimensions = if let (Some(width), Some(height)) = (media.width, media.height) {
+        Some(MediaDimensions { width, height })
+    } else {
+        None
+    };
+
+    MediaAttachment {
+        media_type: media.media_type.clone(),
+        url: media.url.as_deref()
+            .or(media.preview_image_url.as_deref())
+            .map(super::utils::decode_html_entities)
+            .unwrap_or_default(),
+        alt_text: media.alt_text.clone(),
+        dimensions,
+        file_size: None, // Not provided by Twitter API
+    }
+}
+
+/// `bounding_box:` operator for a box, if Twitter accepts it
+///
+/// Twitter limits each side to 25 miles and can't express boxes crossing
+/// the antimeridian; those rely on the post-fetch filter alone.
+fn bounding_box_operator(bounds: &GeoBounds) -> Option<String> {
+    const MAX_SIDE_KM: f64 = 40.2;
+    const KM_PER_DEGREE: f64 = 111.32;
+
+    if bounds.crosses_antimeridian() {
+        return None;
+    }
+
+    let mid_latitude = ((bounds.north + bounds.south) / 2.0).to_radians();
+    let height_km = (bounds.north - bounds.south) * KM_PER_DEGREE;
+    let width_km = (bounds.east - bounds.west) * KM_PER_DEGREE * mid_latitude.cos();
+    if height_km > MAX_SIDE_KM || width_km > MAX_SIDE_KM {
+        return None;
+    }
+
+    Some(format!("bounding_box:[{} {} {} {}]", bounds.west, bounds.south, bounds.east, bounds.north))
+}
+
+/// Order a conversation parents-first (depth-first, siblings by time)
+///
+/// Parents are linked by native id, which is what `reply_to` holds.
+fn order_thread(mut posts: Vec<SocialPost>, root_id: &str) -> Vec<SocialPost> {
+    posts.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+
+    let parent_of = |post: &SocialPost| post.metadata.get("reply_to").and_then(|v| v.as_str()).map(str::to_string);
+    let ids: std::collections::HashSet<&str> = posts.iter().map(SocialPost::native_id).collect();
+
+    // Start from the root, then from any reply whose parent is missing
+    let mut starts: Vec<usize> = posts.iter().position(|post| post.native_id() == root_id).into_iter().collect();
+    starts.extend(posts.iter().enumerate()
+        .filter(|(_, post)| post.native_id() != root_id && !parent_of(post).is_some_and(|parent| ids.contains(parent.as_str())))
+        .map(|(index, _)| index));
+
+    let mut children: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, post) in posts.iter().enumerate() {
+        if let Some(parent) = parent_of(post) {
+            children.entry(parent).or_default().push(index);
+        }
+    }
+
+    let mut order = Vec::with_capacity(posts.len());
+    let mut visited = vec![false; posts.len()];
+    for start in starts {
+        let mut stack = vec![start];
+        while let Some(index) = stack.pop() {
+            if std::mem::replace(&mut visited[index], true) {
+                continue;
+            }
+            order.push(index);
+            if let Some(replies) = children.get(posts[index].native_id()) {
+                stack.extend(replies.iter().rev());
+            }
+        }
+    }
+
+    let mut slots: Vec<Option<SocialPost>> = posts.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|index| slots[index].take()).collect()
 }
 
 #[cfg(test)]
@@ -928,6 +1947,437 @@ mod tests {
         
         assert_eq!(connector.platform_name(), "twitter");
         assert!(connector.is_configured());
+
+        // User timelines aren't available on the free tier
+        let capabilities = connector.capabilities();
+        assert!(capabilities.supports_search && capabilities.supports_streaming);
+        assert!(!capabilities.supports_user_posts);
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_surfaces_network_error() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tweets/search/recent"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "data": [] }))
+                .set_delay(Duration::from_secs(5)))
+            .mount(&server)
+            .await;
+
+        let http = HttpConfig { request_timeout_secs: 1, connect_timeout_secs: Some(1), ..HttpConfig::default() };
+        let mut connector = TwitterConnector::with_http_config("test_token".to_string(), PrivacyConfig::default(), &http).unwrap();
+        connector.base_url = server.uri();
+
+        let params = SearchParams {
+            query: "slow".to_string(),
+            max_results: Some(10),
+            ..Default::default()
+        };
+
+        let started = std::time::Instant::now();
+        let result = connector.search_posts(&params).await;
+
+        assert!(matches!(result, Err(ConnectorError::NetworkError { .. })));
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+
+    #[tokio::test]
+    async fn test_reprocess_matches_connector_conversion() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let raw = serde_json::json!({
+            "data": {
+                "id": "1700000000000000030",
+                "text": "Water rising near #Andheri station, thanks @bmc",
+                "author_id": "42",
+                "created_at": "2024-07-08T06:30:00Z",
+                "lang": "en",
+                "conversation_id": "1700000000000000030",
+                "public_metrics": { "like_count": 7, "retweet_count": 2, "reply_count": 1, "quote_count": 0 },
+                "entities": {
+                    "hashtags": [{ "start": 20, "end": 28, "tag": "Andheri" }],
+                    "mentions": [{ "start": 44, "end": 48, "username": "bmc", "id": "7" }]
+                },
+                "attachments": { "media_keys": ["3_1"] }
+            },
+            "includes": {
+                "users": [{ "id": "42", "username": "reporter", "name": "Reporter", "verified": true,
+                            "public_metrics": { "followers_count": 1200 } }],
+                "media": [{ "media_key": "3_1", "type": "photo", "url": "https://pbs.twimg.com/media/a.jpg",
+                            "width": 640, "height": 480 }]
+            }
+        });
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tweets/1700000000000000030"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&raw))
+            .mount(&server)
+            .await;
+
+        let privacy = PrivacyConfig::default();
+        let mut connector = TwitterConnector::new("test_token".to_string(), privacy.clone());
+        connector.base_url = server.uri();
+
+        let original = connector.get_post_by_id("1700000000000000030").await.unwrap().unwrap();
+        let reprocessed = reprocess(&raw, &privacy).unwrap();
+
+        assert_eq!(reprocessed.author.username, "reporter");
+        assert_eq!(serde_json::to_value(&reprocessed).unwrap(), serde_json::to_value(&original).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_estimate_duration_under_search_window() {
+        let connector = TwitterConnector::new("test_token".to_string(), PrivacyConfig::default());
+
+        // Within the 300-request burst only the 1s spacing applies
+        let estimate = connector.estimate_duration(10);
+        assert!(estimate >= Duration::from_secs(9) && estimate < Duration::from_secs(10), "{:?}", estimate);
+
+        // 600 requests: 300 more tokens at one per 3s
+        let estimate = connector.estimate_duration(600);
+        assert!(estimate >= Duration::from_secs(899) && estimate <= Duration::from_secs(900), "{:?}", estimate);
+
+        // Remaining reported as 0 until the window resets in 10 minutes
+        connector.rate_limiter.update("twitter", 0, Utc::now() + chrono::Duration::minutes(10));
+        assert!(connector.estimate_duration(1) >= Duration::from_secs(599));
+    }
+
+    #[test]
+    fn test_quote_tweet_content_includes_quoted_text() {
+        let raw = serde_json::json!({
+            "data": {
+                "id": "1700000000000000040",
+                "text": "This is exactly what we warned about",
+                "author_id": "42",
+                "created_at": "2024-07-08T07:00:00Z",
+                "public_metrics": { "like_count": 3, "retweet_count": 1, "reply_count": 0, "quote_count": 0 },
+                "referenced_tweets": [{ "type": "quoted", "id": "1700000000000000030" }]
+            },
+            "includes": {
+                "tweets": [{
+                    "id": "1700000000000000030",
+                    "text": "Water rising near Andheri station",
+                    "author_id": "7",
+                    "public_metrics": { "like_count": 900, "retweet_count": 250, "reply_count": 40, "quote_count": 12 }
+                }]
+            }
+        });
+
+        let post = reprocess(&raw, &PrivacyConfig::default()).unwrap();
+        assert_eq!(post.content, "This is exactly what we warned about\n\n[Quoted tweet] Water rising near Andheri station");
+        assert_eq!(post.metadata["quoted_tweet_id"], serde_json::json!("1700000000000000030"));
+        assert_eq!((post.metrics.likes, post.metrics.shares, post.metrics.comments), (3, 1, 0));
+    }
+
+    #[tokio::test]
+    async fn test_get_post_by_id_served_from_cache() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tweets/1700000000000000020"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "id": "1700000000000000020", "text": "Viral post", "author_id": "42" }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut connector = TwitterConnector::new("test_token".to_string(), PrivacyConfig::default());
+        connector.base_url = server.uri();
+
+        let first = connector.get_post_by_id("1700000000000000020").await.unwrap().unwrap();
+
+        // Exhaust the rate limit: a lookup that waited on it would time out
+        connector.rate_limiter.update("twitter", 0, Utc::now() + chrono::Duration::hours(1));
+        let second = tokio::time::timeout(Duration::from_secs(1), connector.get_post_by_id("1700000000000000020"))
+            .await
+            .expect("cache hit must not wait for the rate limit")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(second.content, first.content);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_json_is_deserialization_error() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tweets/1700000000000000030"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(r#"{"data": {"id": "#, "application/json"))
+            .mount(&server)
+            .await;
+
+        let mut connector = TwitterConnector::new("test_token".to_string(), PrivacyConfig::default());
+        connector.base_url = server.uri();
+
+        match connector.get_post_by_id("1700000000000000030").await {
+            Err(ConnectorError::DeserializationError { message }) => assert!(message.contains("decoding response body")),
+            other => panic!("expected DeserializationError, got {:?}", other.map(|p| p.map(|p| p.id))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quota_exhaustion_falls_back_to_nitter() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tweets/search/recent"))
+            .respond_with(ResponseTemplate::new(429).set_body_string(r#"{"title":"UsageCapExceeded"}"#))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .and(query_param("q", "mumbai flood"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/nitter/search_flood.html"
+            ))))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut connector = TwitterConnector::new("test_token".to_string(), PrivacyConfig::default());
+        connector.base_url = server.uri();
+        let params = test_utils::search_params("mumbai flood");
+
+        // Without the fallback the quota error surfaces as before
+        assert!(matches!(connector.search_posts(&params).await, Err(ConnectorError::ApiError { code: 429, .. })));
+
+        connector.set_nitter_fallback(NitterClient::new(&server.uri(), &HttpConfig::default()).unwrap());
+        let posts = connector.search_posts(&params).await.unwrap();
+        assert_eq!(posts.len(), 2);
+        assert!(posts.iter().all(|post| post.metadata["source"] == serde_json::json!("nitter")));
+        // Only the photo counts; the scraped metrics are approximate
+        assert_eq!(posts[0].completeness_score(), 0.2);
+    }
+
+    #[tokio::test]
+    async fn test_get_posts_by_ids_batches_by_100() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+        /// Echo requested ids as tweets, reporting ids ending in 7 as missing
+        struct Lookup;
+
+        impl Respond for Lookup {
+            fn respond(&self, request: &Request) -> ResponseTemplate {
+                let ids = request.url.query_pairs()
+                    .find(|(key, _)| key == "ids")
+                    .map(|(_, value)| value.into_owned())
+                    .unwrap_or_default();
+                let (missing, found): (Vec<&str>, Vec<&str>) = ids.split(',').partition(|id| id.ends_with('7'));
+
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "data": found.iter().map(|id| serde_json::json!({ "id": id, "text": "hydrated", "author_id": "42" })).collect::<Vec<_>>(),
+                    "errors": missing.iter().map(|id| serde_json::json!({
+                        "value": id, "detail": "Could not find tweet", "title": "Not Found Error",
+                        "resource_type": "tweet", "parameter": "ids"
+                    })).collect::<Vec<_>>()
+                }))
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tweets"))
+            .respond_with(Lookup)
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let mut connector = TwitterConnector::new("test_token".to_string(), PrivacyConfig::default());
+        connector.base_url = server.uri();
+
+        let ids: Vec<String> = (0..150).map(|i| (1_700_000_000_000_000_000u64 + i).to_string()).collect();
+        let posts = connector.get_posts_by_ids(&ids).await.unwrap();
+
+        assert_eq!(posts.len(), 135);
+        assert!(posts.iter().all(|post| !post.id.ends_with('7')));
+    }
+
+    #[tokio::test]
+    async fn test_search_stream_pages_only_as_consumed() {
+        use futures::TryStreamExt;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+        /// Ten tweets per page, pointing at the next page forever
+        struct Pages;
+
+        impl Respond for Pages {
+            fn respond(&self, request: &Request) -> ResponseTemplate {
+                let page: u64 = request.url.query_pairs()
+                    .find(|(key, _)| key == "next_token")
+                    .and_then(|(_, value)| value.parse().ok())
+                    .unwrap_or(0);
+
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "data": (0..10).map(|i| serde_json::json!({
+                        "id": (1_700_000_000_000_000_000u64 + page * 10 + i).to_string(),
+                        "text": format!("flood update {}", page * 10 + i)
+                    })).collect::<Vec<_>>(),
+                    "meta": { "result_count": 10, "next_token": (page + 1).to_string() }
+                }))
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tweets/search/recent"))
+            .respond_with(Pages)
+            .mount(&server)
+            .await;
+
+        let mut connector = TwitterConnector::new("test_token".to_string(), PrivacyConfig::default());
+        connector.base_url = server.uri();
+        let params = SearchParams { max_results: None, ..super::super::test_utils::search_params("flood") };
+
+        let first: Vec<SocialPost> = connector.search_stream(&params).take(5).try_collect().await.unwrap();
+        assert_eq!(first.len(), 5);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        // Reading past the first page follows next_token
+        let posts: Vec<SocialPost> = connector.search_stream(&params).take(15).try_collect().await.unwrap();
+        assert_eq!(posts[14].content, "flood update 14");
+        assert_eq!(server.received_requests().await.unwrap().len(), 1 + 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_conversation_orders_thread_root_first() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let tweet = |id: &str, text: &str, created_at: &str, parent: Option<&str>| {
+            let mut tweet = serde_json::json!({
+                "id": id, "text": text, "author_id": "42",
+                "created_at": created_at, "conversation_id": "100"
+            });
+            if let Some(parent) = parent {
+                tweet["referenced_tweets"] = serde_json::json!([{ "type": "replied_to", "id": parent }]);
+            }
+            tweet
+        };
+
+        let server = MockServer::start().await;
+        // Search returns the replies newest first and never the root itself
+        Mock::given(method("GET"))
+            .and(path("/tweets/search/recent"))
+            .and(query_param("query", "conversation_id:100"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    tweet("102", "Reply to reply", "2024-03-01T10:10:00Z", Some("101")),
+                    tweet("101", "Reply to root", "2024-03-01T10:05:00Z", Some("100")),
+                ],
+                "meta": { "result_count": 2 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/tweets/100"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": tweet("100", "Root", "2024-03-01T10:00:00Z", None)
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut connector = TwitterConnector::new("test_token".to_string(), PrivacyConfig::default());
+        connector.base_url = server.uri();
+
+        let thread = connector.get_conversation("100").await.unwrap();
+
+        let ids: Vec<&str> = thread.iter().map(SocialPost::native_id).collect();
+        assert_eq!(ids, vec!["100", "101", "102"]);
+        assert!(!thread[0].metadata.contains_key("reply_to"));
+        assert_eq!(thread[1].metadata["reply_to"], "100");
+        assert_eq!(thread[2].metadata["reply_to"], "101");
+        assert!(thread.iter().all(|post| post.metadata["thread_id"] == "100"));
+    }
+
+    #[test]
+    fn test_parse_trends_response() {
+        let body = r##"[{
+            "trends": [
+                {"name": "#WorldCup", "url": "http://twitter.com/search?q=%23WorldCup", "promoted_content": null, "query": "%23WorldCup", "tweet_volume": 325000},
+                {"name": "Monsoon", "url": "http://twitter.com/search?q=Monsoon", "promoted_content": null, "query": "Monsoon", "tweet_volume": null}
+            ],
+            "as_of": "2024-06-01T12:00:00Z",
+            "created_at": "2024-06-01T11:52:41Z",
+            "locations": [{"name": "Worldwide", "woeid": 1}]
+        }]"##;
+
+        let trends = TwitterConnector::parse_trends(body).unwrap();
+        assert_eq!(trends, vec!["#WorldCup", "Monsoon"]);
+    }
+
+    #[tokio::test]
+    async fn test_trending_topics_resolves_woeid_once() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/trends/available.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "name": "Worldwide", "woeid": 1, "country": "" },
+                { "name": "Mumbai", "woeid": 2295411, "country": "India" },
+                { "name": "India", "woeid": 23424848, "country": "India" }
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/trends/place.json"))
+            .and(query_param("id", "2295411"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "trends": [{ "name": "#MumbaiRains" }] }
+            ])))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let mut connector = TwitterConnector::new("test_token".to_string(), PrivacyConfig::default());
+        connector.v1_base_url = server.uri();
+
+        assert_eq!(connector.get_trending_topics(Some("mumbai")).await.unwrap(), vec!["#MumbaiRains"]);
+        assert_eq!(connector.get_trending_topics(Some("Mumbai")).await.unwrap(), vec!["#MumbaiRains"]);
+        assert_eq!(connector.resolve_woeid(Some("India")).await.unwrap(), 23424848);
+        assert_eq!(connector.resolve_woeid(None).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_trending_topics_forbidden_is_config_error() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/trends/place.json"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let mut connector = TwitterConnector::new("test_token".to_string(), PrivacyConfig::default());
+        connector.v1_base_url = server.uri();
+
+        match connector.get_trending_topics(None).await {
+            Err(ConnectorError::ConfigError { message }) => assert!(message.contains("v1.1 access")),
+            other => panic!("expected ConfigError, got {:?}", other),
+        }
     }
 
     #[test]
@@ -940,17 +2390,127 @@ mod tests {
         let params = SearchParams {
             query: "climate change".to_string(),
             max_results: Some(50),
-            start_date: None,
-            end_date: None,
-            language: None,
-            location: None,
-            content_type: None,
-            extra_params: HashMap::new(),
+            ..Default::default()
         };
         
         let url = connector.build_search_url(&params);
         assert!(url.contains("climate%20change"));
         assert!(url.contains("max_results=50"));
+        assert!(url.contains("possibly_sensitive"));
+
+        let images = SearchParams { content_type: Some(ContentType::Image), ..params.clone() };
+        assert_eq!(TwitterConnector::build_query(&images), "(climate change) has:media");
+
+        let text = SearchParams { content_type: Some(ContentType::Text), ..params };
+        assert_eq!(TwitterConnector::build_query(&text), "(climate change) -has:media -has:links");
+
+        let bounds = |west: f64, east: f64| Some(GeoBounds { north: 19.3, south: 19.0, east, west });
+        let mumbai = SearchParams { bounds: bounds(72.75, 73.0), ..text.clone() };
+        assert_eq!(
+            TwitterConnector::build_query(&mumbai),
+            "(climate change) -has:media -has:links bounding_box:[72.75 19 73 19.3]"
+        );
+        let wide = SearchParams { bounds: bounds(72.0, 74.0), content_type: None, ..text.clone() };
+        assert_eq!(TwitterConnector::build_query(&wide), "climate change");
+        let wrapped = SearchParams { bounds: bounds(179.9, -179.9), ..text };
+        assert_eq!(TwitterConnector::build_query(&wrapped), "(climate change) -has:media -has:links");
+    }
+
+    #[test]
+    fn test_parse_stream_line() {
+        let connector = TwitterConnector::new(
+            "test_token".to_string(),
+            PrivacyConfig::default()
+        );
+
+        let line = r#"{"data":{"id":"1700000000000000001","text":"Live update #event","author_id":"42","created_at":"2024-03-01T12:00:00.000Z","lang":"en","public_metrics":{"retweet_count":3,"reply_count":1,"like_count":10,"quote_count":2}},"includes":{"users":[{"id":"42","username":"reporter","name":"Reporter"}]},"matching_rules":[{"id":"1","tag":null}]}"#;
+
+        let post = connector.parse_stream_line(line)
+            .expect("tweet line should yield a post")
+            .expect("tweet line should parse");
+
+        assert_eq!(post.id, "twitter:1700000000000000001");
+        assert_eq!(post.content, "Live update #event");
+        assert_eq!(post.author.username, "reporter");
+        assert_eq!(post.metrics.likes, 10);
+        assert_eq!(post.metrics.shares, 5);
+        assert_eq!(post.language.as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn test_edit_history_populated_for_edited_tweets() {
+        let connector = TwitterConnector::new(
+            "test_token".to_string(),
+            PrivacyConfig::default()
+        );
+
+        let edited = r#"{"data":{"id":"1700000000000000003","text":"Corrected figures","edit_history_tweet_ids":["1700000000000000001","1700000000000000002","1700000000000000003"],"edit_controls":{"edits_remaining":3,"is_edit_eligible":true,"editable_until":"2024-03-01T12:30:00.000Z"}}}"#;
+        let post = connector.parse_stream_line(edited).unwrap().unwrap();
+
+        assert_eq!(post.metadata["edit_count"], serde_json::json!(2));
+        assert_eq!(
+            post.metadata["edit_history"],
+            serde_json::json!(["1700000000000000001", "1700000000000000002"])
+        );
+        assert_eq!(post.metadata["edit_controls"]["edits_remaining"], serde_json::json!(3));
+
+        let unedited = r#"{"data":{"id":"1700000000000000004","text":"Original","edit_history_tweet_ids":["1700000000000000004"]}}"#;
+        let post = connector.parse_stream_line(unedited).unwrap().unwrap();
+
+        assert_eq!(post.metadata["edit_count"], serde_json::json!(0));
+        assert_eq!(post.metadata["edit_history"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_possibly_sensitive_tweet_flagged_despite_benign_text() {
+        let connector = TwitterConnector::new(
+            "test_token".to_string(),
+            PrivacyConfig::default()
+        );
+
+        let labeled = r#"{"data":{"id":"1700000000000000050","text":"Sunset over the bay tonight","possibly_sensitive":true}}"#;
+        let post = connector.parse_stream_line(labeled).unwrap().unwrap();
+
+        assert!(post.privacy_flags.sensitive_content);
+        assert_eq!(post.metadata["possibly_sensitive"], serde_json::json!(true));
+        assert!(!post.metadata.contains_key("content_warnings"));
+
+        let unlabeled = r#"{"data":{"id":"1700000000000000051","text":"Sunset over the bay tonight","possibly_sensitive":false}}"#;
+        let post = connector.parse_stream_line(unlabeled).unwrap().unwrap();
+
+        assert!(!post.privacy_flags.sensitive_content);
+        assert_eq!(post.metadata["possibly_sensitive"], serde_json::json!(false));
+    }
+
+    #[test]
+    fn test_missing_includes_author_resolves_from_cache() {
+        let mut connector = TwitterConnector::new(
+            "test_token".to_string(),
+            PrivacyConfig::default()
+        );
+
+        let with_includes = r#"{"data":{"id":"1700000000000000010","text":"First","author_id":"42"},"includes":{"users":[{"id":"42","username":"reporter","name":"Reporter"}]}}"#;
+        let without_includes = r#"{"data":{"id":"1700000000000000011","text":"Second","author_id":"42"}}"#;
+
+        connector.parse_stream_line(with_includes).unwrap().unwrap();
+        let post = connector.parse_stream_line(without_includes).unwrap().unwrap();
+        assert_eq!(post.author.username, "reporter");
+
+        // Without the cache step the same author goes anonymous
+        connector.set_author_resolution(vec![AuthorResolutionStep::Includes]);
+        let post = connector.parse_stream_line(without_includes).unwrap().unwrap();
+        assert_eq!(post.author.username, "anonymous");
+    }
+
+    #[test]
+    fn test_parse_stream_keep_alive() {
+        let connector = TwitterConnector::new(
+            "test_token".to_string(),
+            PrivacyConfig::default()
+        );
+
+        assert!(connector.parse_stream_line("\r\n").is_none());
+        assert!(connector.parse_stream_line("").is_none());
     }
 
     #[test]
@@ -965,4 +2525,169 @@ mod tests {
         assert_eq!(requirements[0].key, "TWITTER_BEARER_TOKEN");
         assert!(requirements[0].required);
     }
+
+    #[tokio::test]
+    async fn test_get_user_profile_by_username_and_id() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users/by/username/reporter"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "id": "2244994945",
+                    "username": "reporter",
+                    "name": "Field Reporter",
+                    "verified": true,
+                    "created_at": "2013-12-14T04:35:55.000Z",
+                    "description": "Covering floods",
+                    "location": "Chennai",
+                    "url": "",
+                    "public_metrics": {
+                        "followers_count": 5120, "following_count": 310, "tweet_count": 8842, "listed_count": 12
+                    }
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/404404"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "errors": [{ "value": "404404", "detail": "Could not find user with id: [404404].", "title": "Not Found Error" }]
+            })))
+            .mount(&server)
+            .await;
+
+        let privacy = PrivacyConfig::default();
+        let mut connector = TwitterConnector::new("test_token".to_string(), privacy.clone());
+        connector.base_url = server.uri();
+
+        let profile = connector.get_user_profile("@reporter").await.unwrap().unwrap();
+        assert_eq!(profile.id, super::super::utils::anonymize_user_id("2244994945", &privacy));
+        assert_eq!(profile.platform, Platform::Twitter);
+        assert_eq!(profile.display_name.as_deref(), Some("Field Reporter"));
+        assert_eq!((profile.follower_count, profile.following_count, profile.post_count), (5120, 310, 8842));
+        assert_eq!(profile.account_created, Some(Utc.with_ymd_and_hms(2013, 12, 14, 4, 35, 55).unwrap()));
+        assert_eq!(profile.location.as_deref(), Some("Chennai"));
+        assert_eq!(profile.website, None);
+        assert!(profile.verified);
+
+        // All-digit values are looked up by id
+        assert!(connector.get_user_profile("404404").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_extra_headers_are_sent_without_replacing_connector_headers() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tweets/search/recent"))
+            .and(header("x-api-gateway-key", "gw-secret"))
+            .and(header("traceparent", "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"))
+            .and(header("authorization", "Bearer test_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "meta": { "result_count": 0 } })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let http = HttpConfig {
+            extra_headers: std::collections::HashMap::from([
+                ("X-Api-Gateway-Key".to_string(), "gw-secret".to_string()),
+                ("traceparent".to_string(), "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string()),
+                ("Authorization".to_string(), "Basic gateway".to_string()),
+            ]),
+            ..HttpConfig::default()
+        };
+        let mut connector = TwitterConnector::with_http_config("test_token".to_string(), PrivacyConfig::default(), &http).unwrap();
+        connector.base_url = server.uri();
+
+        connector.search_posts(&super::super::test_utils::search_params("flood")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_search_page_resumes_from_a_saved_cursor() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let page = |ids: [&str; 2], next_token: Option<&str>| {
+            let mut body = serde_json::json!({
+                "data": ids.iter().map(|id| serde_json::json!({ "id": id, "text": format!("flood update {}", id) })).collect::<Vec<_>>(),
+                "meta": { "result_count": 2 }
+            });
+            if let Some(token) = next_token {
+                body["meta"]["next_token"] = serde_json::json!(token);
+            }
+            ResponseTemplate::new(200).set_body_json(body)
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tweets/search/recent"))
+            .and(query_param("next_token", "page2"))
+            .respond_with(page(["1700000000000000003", "1700000000000000004"], None))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/tweets/search/recent"))
+            .respond_with(page(["1700000000000000001", "1700000000000000002"], Some("page2")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let connector = || {
+            let mut connector = TwitterConnector::new("test_token".to_string(), PrivacyConfig::default());
+            connector.base_url = server.uri();
+            connector
+        };
+        let params = super::super::test_utils::search_params("flood");
+
+        let (first, cursor) = connector().search_page(&params, None).await.unwrap();
+        assert_eq!(first.len(), 2);
+        let saved = serde_json::to_string(&cursor.unwrap()).unwrap();
+
+        // A fresh connector, as after a restart, picks up at page two
+        let cursor: SearchCursor = serde_json::from_str(&saved).unwrap();
+        assert_eq!(cursor.next_token, "page2");
+        let other_query = super::super::test_utils::search_params("wildfire");
+        assert!(matches!(
+            connector().search_page(&other_query, Some(cursor.clone())).await,
+            Err(ConnectorError::InvalidCursor { .. })
+        ));
+
+        let (second, cursor) = connector().search_page(&params, Some(cursor)).await.unwrap();
+        assert_eq!(second[0].content, "flood update 1700000000000000003");
+        assert_eq!(second.len(), 2);
+        assert!(cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_request_latency_is_recorded_by_platform_operation_and_status() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tweets/search/recent"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut connector = TwitterConnector::new("test_token".to_string(), PrivacyConfig::default());
+        connector.base_url = server.uri();
+
+        // Failed requests are timed too; no other test answers a search with 503
+        assert!(connector.search_posts(&super::super::test_utils::search_params("flood")).await.is_err());
+
+        let latency = crate::metrics::request_durations().snapshot("twitter", "search", "503").unwrap();
+        assert_eq!(latency.count, 1);
+        assert!(crate::metrics::request_durations().snapshot("twitter", "get_by_id", "503").is_none());
+
+        let body = crate::metrics::Metrics::new().render();
+        assert!(body.contains("connector_request_duration_seconds_count{platform=\"twitter\",operation=\"search\",status=\"503\"} 1"));
+    }
 }
\ No newline at end of file
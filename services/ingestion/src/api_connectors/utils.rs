@@ -0,0 +1,1573 @@
+/*
+ * =============================================================================
+ * Shared Connector Utilities for SentinelBERT
+ * =============================================================================
+ *
+ * Helpers used by every connector while normalizing platform responses:
+ * - Identifier anonymization (salted SHA-256, keyed BLAKE3 or HMAC-SHA256)
+ * - Text normalization (NFKC, invisible character stripping, whitespace)
+ * - Hashtag, mention and URL extraction from free text
+ * - URL canonicalization (tracking parameters, scheme/host case, default ports)
+ * - Query term highlighting (match spans recorded in metadata["matches"])
+ * - Sensitive content classification (weighted, obfuscation-tolerant lexicon)
+ * - Privacy compliance, including location generalization
+ * - UTF-8 safe truncation and slicing
+ * - Content length limits (word-boundary truncation with an ellipsis)
+ * - Content type filtering (SearchParams::content_type)
+ * - Regex allow/deny content filtering (SearchParams::content_allow/deny)
+ * - Honouring Retry-After / x-rate-limit-reset on 429 responses
+ * - Request latency recording (`send_timed`, connector_request_duration_seconds)
+ *
+ * Location Generalization:
+ * Coordinates are snapped to a grid whose cells are roughly
+ * `location_precision_km` on each side. A degree of latitude is ~110.574 km
+ * everywhere, but a degree of longitude shrinks with cos(latitude), so the
+ * longitude step widens towards the poles to keep cells square in km.
+ *
+ * Sensitivity Scoring:
+ * Each lexicon term compiles to a regex tolerating common obfuscation
+ * (leetspeak like "su1c1d3", separators like "s.u.i.c.i.d.e"). Matched
+ * weights combine as independent probabilities, 1 - Π(1 - w), so several
+ * weak signals add up without ever exceeding 1. Matched categories are
+ * recorded on the post under metadata["content_warnings"].
+ *
+ * The lexicon is built in unless `processing.sensitivity_lexicon` names a
+ * file: TOML (`[[terms]]` tables of term, category, weight and optional
+ * pattern) or a plain list with one `term | category | weight` per line,
+ * where category and weight may be left off. The service reloads the file
+ * on SIGHUP via `set_default_classifier`.
+ *
+ * Text Normalization:
+ * `normalize_text` runs before entity extraction so that "ｆｌｏｏｄ",
+ * "fl\u{200B}ood" and "flood" all match and hash the same. Zero-width and
+ * bidi control characters are dropped, except a zero-width joiner between
+ * two pictographs (it builds emoji like 👨‍👩‍👧). Variation selectors are
+ * kept only after a pictograph, where they pick emoji presentation.
+ *
+ * URL Canonicalization:
+ * `canonicalize_url` drops tracking query parameters (utm_*, fbclid, gclid,
+ * ...) so the same link shared from different campaigns clusters as one.
+ * Entries ending in `*` match by prefix. The remaining parameters keep
+ * their order and original encoding. Unparseable URLs are left as they are.
+ *
+ * Query Highlighting:
+ * `query_terms` splits a search query into plain terms and quoted phrases,
+ * skipping boolean operators, exclusions (-term) and platform operators
+ * (lang:en). `match_spans` finds each term case-insensitively on word
+ * boundaries; overlapping spans merge into one whose term lists every
+ * contributing term joined by "|". Offsets are character (not byte)
+ * offsets with an exclusive end, so they're stable for any consumer.
+ *
+ * Rate-Limited Responses:
+ * `send_with_retry_after` sleeps until the time a 429 response names and
+ * retries once. `Retry-After` may be delay-seconds or an HTTP-date;
+ * `x-rate-limit-reset` (Twitter) is a Unix timestamp. The sleep is capped
+ * at the connector's `max_retry_wait_secs`, and a 429 naming no time is
+ * returned as is. Inside a job, the retry is charged to the job's
+ * RetryBudget and skipped (RetryBudgetExhausted) once that is spent.
+ *
+ * Timestamps:
+ * Converters never invent a creation time silently. `resolve_created_at`
+ * records metadata `created_at_source` ("api" or "defaulted"); a missing,
+ * unparseable or zero timestamp falls back to the collection time and adds
+ * `timestamp_unreliable: true` so temporal analysis can leave the post out.
+ *
+ * Cutting Text:
+ * Never index post content with raw byte ranges; a cut inside a multibyte
+ * character panics. Use `safe_truncate` (character count) or `safe_slice`
+ * (byte range snapped to character boundaries) instead.
+ *
+ * =============================================================================
+ */
+
+use super::*;
+use hmac::{Hmac, Mac};
+use regex::Regex;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::{Arc, OnceLock, RwLock};
+use crate::retry_budget::RetryBudget;
+use unicode_normalization::UnicodeNormalization;
+
+/// Kilometres per degree of latitude (WGS84 mean)
+const KM_PER_DEGREE_LAT: f64 = 110.574;
+
+/// Kilometres per degree of longitude at the equator (WGS84)
+const KM_PER_DEGREE_LON_EQUATOR: f64 = 111.320;
+
+/// BLAKE3 key derivation context for author id hashing
+const BLAKE3_KEY_CONTEXT: &str = "SentinelBERT 2024 author id anonymization";
+
+/// Built-in lexicon: (term, category, weight)
+const DEFAULT_LEXICON: &[(&str, &str, f64)] = &[
+    ("suicide", "self_harm", 0.9),
+    ("self-harm", "self_harm", 0.9),
+    ("kill myself", "self_harm", 0.9),
+    ("overdose", "self_harm", 0.6),
+    ("nsfw", "adult", 0.8),
+    ("explicit", "adult", 0.4),
+    ("gore", "violence", 0.8),
+    ("graphic violence", "violence", 0.9),
+];
+
+/// Query parameters `canonicalize_url` removes by default; `*` matches a prefix
+pub const DEFAULT_TRACKING_PARAMS: &[&str] = &[
+    "utm_*",
+    "fbclid",
+    "gclid",
+    "dclid",
+    "gbraid",
+    "wbraid",
+    "msclkid",
+    "yclid",
+    "mc_cid",
+    "mc_eid",
+    "igshid",
+    "_ga",
+    "_gl",
+];
+
+/// Category and weight of plain-list lexicon lines that leave them off
+const DEFAULT_CUSTOM_CATEGORY: &str = "custom";
+const DEFAULT_CUSTOM_WEIGHT: f64 = 1.0;
+
+/// Score at or above which content counts as sensitive
+pub const SENSITIVITY_THRESHOLD: f64 = 0.5;
+
+/// Named character references: the HTML 4 set plus `&apos;`, sorted by name
+const HTML_ENTITIES: &[(&str, char)] = &[
+    ("AElig", '\u{c6}'), ("Aacute", '\u{c1}'), ("Acirc", '\u{c2}'), ("Agrave", '\u{c0}'),
+    ("Alpha", '\u{391}'), ("Aring", '\u{c5}'), ("Atilde", '\u{c3}'), ("Auml", '\u{c4}'),
+    ("Beta", '\u{392}'), ("Ccedil", '\u{c7}'), ("Chi", '\u{3a7}'), ("Dagger", '\u{2021}'),
+    ("Delta", '\u{394}'), ("ETH", '\u{d0}'), ("Eacute", '\u{c9}'), ("Ecirc", '\u{ca}'),
+    ("Egrave", '\u{c8}'), ("Epsilon", '\u{395}'), ("Eta", '\u{397}'), ("Euml", '\u{cb}'),
+    ("Gamma", '\u{393}'), ("Iacute", '\u{cd}'), ("Icirc", '\u{ce}'), ("Igrave", '\u{cc}'),
+    ("Iota", '\u{399}'), ("Iuml", '\u{cf}'), ("Kappa", '\u{39a}'), ("Lambda", '\u{39b}'),
+    ("Mu", '\u{39c}'), ("Ntilde", '\u{d1}'), ("Nu", '\u{39d}'), ("OElig", '\u{152}'),
+    ("Oacute", '\u{d3}'), ("Ocirc", '\u{d4}'), ("Ograve", '\u{d2}'), ("Omega", '\u{3a9}'),
+    ("Omicron", '\u{39f}'), ("Oslash", '\u{d8}'), ("Otilde", '\u{d5}'), ("Ouml", '\u{d6}'),
+    ("Phi", '\u{3a6}'), ("Pi", '\u{3a0}'), ("Prime", '\u{2033}'), ("Psi", '\u{3a8}'),
+    ("Rho", '\u{3a1}'), ("Scaron", '\u{160}'), ("Sigma", '\u{3a3}'), ("THORN", '\u{de}'),
+    ("Tau", '\u{3a4}'), ("Theta", '\u{398}'), ("Uacute", '\u{da}'), ("Ucirc", '\u{db}'),
+    ("Ugrave", '\u{d9}'), ("Upsilon", '\u{3a5}'), ("Uuml", '\u{dc}'), ("Xi", '\u{39e}'),
+    ("Yacute", '\u{dd}'), ("Yuml", '\u{178}'), ("Zeta", '\u{396}'), ("aacute", '\u{e1}'),
+    ("acirc", '\u{e2}'), ("acute", '\u{b4}'), ("aelig", '\u{e6}'), ("agrave", '\u{e0}'),
+    ("alefsym", '\u{2135}'), ("alpha", '\u{3b1}'), ("amp", '\u{26}'), ("and", '\u{2227}'),
+    ("ang", '\u{2220}'), ("apos", '\u{27}'), ("aring", '\u{e5}'), ("asymp", '\u{2248}'),
+    ("atilde", '\u{e3}'), ("auml", '\u{e4}'), ("bdquo", '\u{201e}'), ("beta", '\u{3b2}'),
+    ("brvbar", '\u{a6}'), ("bull", '\u{2022}'), ("cap", '\u{2229}'), ("ccedil", '\u{e7}'),
+    ("cedil", '\u{b8}'), ("cent", '\u{a2}'), ("chi", '\u{3c7}'), ("circ", '\u{2c6}'),
+    ("clubs", '\u{2663}'), ("cong", '\u{2245}'), ("copy", '\u{a9}'), ("crarr", '\u{21b5}'),
+    ("cup", '\u{222a}'), ("curren", '\u{a4}'), ("dArr", '\u{21d3}'), ("dagger", '\u{2020}'),
+    ("darr", '\u{2193}'), ("deg", '\u{b0}'), ("delta", '\u{3b4}'), ("diams", '\u{2666}'),
+    ("divide", '\u{f7}'), ("eacute", '\u{e9}'), ("ecirc", '\u{ea}'), ("egrave", '\u{e8}'),
+    ("empty", '\u{2205}'), ("emsp", '\u{2003}'), ("ensp", '\u{2002}'), ("epsilon", '\u{3b5}'),
+    ("equiv", '\u{2261}'), ("eta", '\u{3b7}'), ("eth", '\u{f0}'), ("euml", '\u{eb}'),
+    ("euro", '\u{20ac}'), ("exist", '\u{2203}'), ("fnof", '\u{192}'), ("forall", '\u{2200}'),
+    ("frac12", '\u{bd}'), ("frac14", '\u{bc}'), ("frac34", '\u{be}'), ("frasl", '\u{2044}'),
+    ("gamma", '\u{3b3}'), ("ge", '\u{2265}'), ("gt", '\u{3e}'), ("hArr", '\u{21d4}'),
+    ("harr", '\u{2194}'), ("hearts", '\u{2665}'), ("hellip", '\u{2026}'), ("iacute", '\u{ed}'),
+    ("icirc", '\u{ee}'), ("iexcl", '\u{a1}'), ("igrave", '\u{ec}'), ("image", '\u{2111}'),
+    ("infin", '\u{221e}'), ("int", '\u{222b}'), ("iota", '\u{3b9}'), ("iquest", '\u{bf}'),
+    ("isin", '\u{2208}'), ("iuml", '\u{ef}'), ("kappa", '\u{3ba}'), ("lArr", '\u{21d0}'),
+    ("lambda", '\u{3bb}'), ("lang", '\u{2329}'), ("laquo", '\u{ab}'), ("larr", '\u{2190}'),
+    ("lceil", '\u{2308}'), ("ldquo", '\u{201c}'), ("le", '\u{2264}'), ("lfloor", '\u{230a}'),
+    ("lowast", '\u{2217}'), ("loz", '\u{25ca}'), ("lrm", '\u{200e}'), ("lsaquo", '\u{2039}'),
+    ("lsquo", '\u{2018}'), ("lt", '\u{3c}'), ("macr", '\u{af}'), ("mdash", '\u{2014}'),
+    ("micro", '\u{b5}'), ("middot", '\u{b7}'), ("minus", '\u{2212}'), ("mu", '\u{3bc}'),
+    ("nabla", '\u{2207}'), ("nbsp", '\u{a0}'), ("ndash", '\u{2013}'), ("ne", '\u{2260}'),
+    ("ni", '\u{220b}'), ("not", '\u{ac}'), ("notin", '\u{2209}'), ("nsub", '\u{2284}'),
+    ("ntilde", '\u{f1}'), ("nu", '\u{3bd}'), ("oacute", '\u{f3}'), ("ocirc", '\u{f4}'),
+    ("oelig", '\u{153}'), ("ograve", '\u{f2}'), ("oline", '\u{203e}'), ("omega", '\u{3c9}'),
+    ("omicron", '\u{3bf}'), ("oplus", '\u{2295}'), ("or", '\u{2228}'), ("ordf", '\u{aa}'),
+    ("ordm", '\u{ba}'), ("oslash", '\u{f8}'), ("otilde", '\u{f5}'), ("otimes", '\u{2297}'),
+    ("ouml", '\u{f6}'), ("para", '\u{b6}'), ("part", '\u{2202}'), ("permil", '\u{2030}'),
+    ("perp", '\u{22a5}'), ("phi", '\u{3c6}'), ("pi", '\u{3c0}'), ("piv", '\u{3d6}'),
+    ("plusmn", '\u{b1}'), ("pound", '\u{a3}'), ("prime", '\u{2032}'), ("prod", '\u{220f}'),
+    ("prop", '\u{221d}'), ("psi", '\u{3c8}'), ("quot", '\u{22}'), ("rArr", '\u{21d2}'),
+    ("radic", '\u{221a}'), ("rang", '\u{232a}'), ("raquo", '\u{bb}'), ("rarr", '\u{2192}'),
+    ("rceil", '\u{2309}'), ("rdquo", '\u{201d}'), ("real", '\u{211c}'), ("reg", '\u{ae}'),
+    ("rfloor", '\u{230b}'), ("rho", '\u{3c1}'), ("rlm", '\u{200f}'), ("rsaquo", '\u{203a}'),
+    ("rsquo", '\u{2019}'), ("sbquo", '\u{201a}'), ("scaron", '\u{161}'), ("sdot", '\u{22c5}'),
+    ("sect", '\u{a7}'), ("shy", '\u{ad}'), ("sigma", '\u{3c3}'), ("sigmaf", '\u{3c2}'),
+    ("sim", '\u{223c}'), ("spades", '\u{2660}'), ("sub", '\u{2282}'), ("sube", '\u{2286}'),
+    ("sum", '\u{2211}'), ("sup", '\u{2283}'), ("sup1", '\u{b9}'), ("sup2", '\u{b2}'),
+    ("sup3", '\u{b3}'), ("supe", '\u{2287}'), ("szlig", '\u{df}'), ("tau", '\u{3c4}'),
+    ("there4", '\u{2234}'), ("theta", '\u{3b8}'), ("thetasym", '\u{3d1}'), ("thinsp", '\u{2009}'),
+    ("thorn", '\u{fe}'), ("tilde", '\u{2dc}'), ("times", '\u{d7}'), ("trade", '\u{2122}'),
+    ("uArr", '\u{21d1}'), ("uacute", '\u{fa}'), ("uarr", '\u{2191}'), ("ucirc", '\u{fb}'),
+    ("ugrave", '\u{f9}'), ("uml", '\u{a8}'), ("upsih", '\u{3d2}'), ("upsilon", '\u{3c5}'),
+    ("uuml", '\u{fc}'), ("weierp", '\u{2118}'), ("xi", '\u{3be}'), ("yacute", '\u{fd}'),
+    ("yen", '\u{a5}'), ("yuml", '\u{ff}'), ("zeta", '\u{3b6}'), ("zwj", '\u{200d}'),
+    ("zwnj", '\u{200c}'),
+];
+
+/// Characters commonly substituted for each letter
+const OBFUSCATIONS: &[(char, &str)] = &[
+    ('a', "a@4"), ('e', "e3"), ('i', "i1!|"), ('l', "l1|"),
+    ('o', "o0"), ('s', "s5$"), ('t', "t7+"), ('g', "g9"), ('b', "b8"),
+];
+
+/// Hash a platform user identifier using the configured algorithm and salt
+///
+/// The keyed algorithms use the salt as secret key material, so the mapping
+/// can't be rebuilt by hashing candidate usernames without the key.
+pub fn anonymize_user_id(user_id: &str, config: &PrivacyConfig) -> String {
+    match config.hash_algorithm {
+        HashAlgorithm::Sha256 => {
+            let digest = Sha256::digest(format!("{}{}", config.salt, user_id).as_bytes());
+            hex::encode(digest)
+        },
+        HashAlgorithm::Blake3 => {
+            let key = blake3::derive_key(BLAKE3_KEY_CONTEXT, config.salt.as_bytes());
+            blake3::keyed_hash(&key, user_id.as_bytes()).to_hex().to_string()
+        },
+        HashAlgorithm::HmacSha256 => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(config.salt.as_bytes())
+                .expect("HMAC accepts keys of any length");
+            mac.update(user_id.as_bytes());
+            hex::encode(mac.finalize().into_bytes())
+        },
+    }
+}
+
+/// One weighted lexicon entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LexiconEntry {
+    pub term: String,
+    pub category: String,
+
+    /// Contribution to the score when matched, in [0, 1]
+    pub weight: f64,
+
+    /// Regex used instead of the term's generated obfuscation pattern
+    #[serde(default)]
+    pub pattern: Option<String>,
+}
+
+/// Result of classifying a piece of text
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SensitivityScore {
+    /// Combined score in [0, 1]
+    pub score: f64,
+
+    /// Categories of matched terms, sorted and deduplicated
+    pub categories: Vec<String>,
+}
+
+impl SensitivityScore {
+    pub fn is_sensitive(&self) -> bool {
+        self.score >= SENSITIVITY_THRESHOLD
+    }
+}
+
+/// Weighted lexicon classifier for sensitive content
+#[derive(Debug, Clone)]
+pub struct SensitivityClassifier {
+    rules: Vec<(Regex, String, f64)>,
+}
+
+impl SensitivityClassifier {
+    /// Compile a lexicon; fails on an invalid custom pattern
+    pub fn new(lexicon: &[LexiconEntry]) -> Result<Self, ConnectorError> {
+        let rules = lexicon
+            .iter()
+            .map(|entry| {
+                let pattern = match &entry.pattern {
+                    Some(pattern) => format!("(?i){}", pattern),
+                    None => obfuscation_pattern(&entry.term),
+                };
+                let regex = Regex::new(&pattern).map_err(|e| ConnectorError::ConfigError {
+                    message: format!("Invalid sensitivity pattern for '{}': {}", entry.term, e),
+                })?;
+                Ok((regex, entry.category.clone(), entry.weight.clamp(0.0, 1.0)))
+            })
+            .collect::<Result<_, ConnectorError>>()?;
+
+        Ok(Self { rules })
+    }
+
+    /// Load a lexicon file: TOML when the extension is `.toml`, otherwise a
+    /// plain list. Malformed entries fail with their location in the file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConnectorError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| ConnectorError::ConfigError {
+            message: format!("Failed to read sensitivity lexicon {}: {}", path.display(), e),
+        })?;
+
+        let lexicon = if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml")) {
+            parse_toml_lexicon(&text, path)?
+        } else {
+            parse_plain_lexicon(&text, path)?
+        };
+
+        Self::new(&lexicon).map_err(|e| ConnectorError::ConfigError {
+            message: format!("{}: {}", path.display(), e),
+        })
+    }
+
+    pub fn classify(&self, content: &str) -> SensitivityScore {
+        let mut unmatched = 1.0;
+        let mut categories = Vec::new();
+
+        for (regex, category, weight) in &self.rules {
+            if regex.is_match(content) {
+                unmatched *= 1.0 - weight;
+                categories.push(category.clone());
+            }
+        }
+
+        categories.sort();
+        categories.dedup();
+
+        SensitivityScore { score: 1.0 - unmatched, categories }
+    }
+}
+
+impl Default for SensitivityClassifier {
+    fn default() -> Self {
+        let lexicon: Vec<LexiconEntry> = DEFAULT_LEXICON
+            .iter()
+            .map(|(term, category, weight)| LexiconEntry {
+                term: term.to_string(),
+                category: category.to_string(),
+                weight: *weight,
+                pattern: None,
+            })
+            .collect();
+
+        Self::new(&lexicon).expect("built-in lexicon compiles")
+    }
+}
+
+/// Case-insensitive regex for a term, tolerating letter substitutions and
+/// separators (spaces, dots, dashes, ...) between letters
+fn obfuscation_pattern(term: &str) -> String {
+    let letters: Vec<String> = term
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .map(|c| {
+            let c = c.to_ascii_lowercase();
+            match OBFUSCATIONS.iter().find(|(letter, _)| *letter == c) {
+                Some((_, alternatives)) => format!("[{}]", regex::escape(alternatives)),
+                None => regex::escape(&c.to_string()),
+            }
+        })
+        .collect();
+
+    format!(r"(?i)(?:^|[^\p{{L}}\d])(?:{})(?:$|[^\p{{L}}\d])", letters.join(r"[\W_]*"))
+}
+
+/// Lexicon file in TOML form
+#[derive(Deserialize)]
+struct LexiconFile {
+    terms: Vec<LexiconEntry>,
+}
+
+fn parse_toml_lexicon(text: &str, path: &Path) -> Result<Vec<LexiconEntry>, ConnectorError> {
+    let file: LexiconFile = ::config::Config::builder()
+        .add_source(::config::File::from_str(text, ::config::FileFormat::Toml))
+        .build()
+        .and_then(|settings| settings.try_deserialize())
+        .map_err(|e| ConnectorError::ConfigError {
+            message: format!("Malformed sensitivity lexicon {}: {}", path.display(), e),
+        })?;
+
+    for (index, entry) in file.terms.iter().enumerate() {
+        validate_lexicon_entry(entry).map_err(|reason| ConnectorError::ConfigError {
+            message: format!("{}: terms[{}]: {}", path.display(), index, reason),
+        })?;
+    }
+    Ok(file.terms)
+}
+
+/// One `term | category | weight` per line; blank lines and `#` comments skipped
+fn parse_plain_lexicon(text: &str, path: &Path) -> Result<Vec<LexiconEntry>, ConnectorError> {
+    let mut lexicon = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let malformed = |reason: String| ConnectorError::ConfigError {
+            message: format!("{}:{}: {}", path.display(), index + 1, reason),
+        };
+
+        let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+        if fields.len() > 3 {
+            return Err(malformed(format!("expected 'term | category | weight', got '{}'", line)));
+        }
+        let category = fields.get(1).copied().filter(|c| !c.is_empty()).unwrap_or(DEFAULT_CUSTOM_CATEGORY);
+        let weight = match fields.get(2) {
+            Some(weight) => weight.parse::<f64>().map_err(|_| malformed(format!("invalid weight '{}'", weight)))?,
+            None => DEFAULT_CUSTOM_WEIGHT,
+        };
+
+        let entry = LexiconEntry {
+            term: fields[0].to_string(),
+            category: category.to_string(),
+            weight,
+            pattern: None,
+        };
+        validate_lexicon_entry(&entry).map_err(malformed)?;
+        lexicon.push(entry);
+    }
+
+    Ok(lexicon)
+}
+
+fn validate_lexicon_entry(entry: &LexiconEntry) -> Result<(), String> {
+    if entry.term.trim().is_empty() {
+        return Err("empty term".to_string());
+    }
+    if entry.pattern.is_none() && !entry.term.chars().any(char::is_alphanumeric) {
+        return Err(format!("term '{}' has no letters or digits to match", entry.term));
+    }
+    if entry.category.trim().is_empty() {
+        return Err(format!("empty category for '{}'", entry.term));
+    }
+    if !(0.0..=1.0).contains(&entry.weight) {
+        return Err(format!("weight {} for '{}' is outside [0, 1]", entry.weight, entry.term));
+    }
+    Ok(())
+}
+
+fn classifier_slot() -> &'static RwLock<Arc<SensitivityClassifier>> {
+    static CLASSIFIER: OnceLock<RwLock<Arc<SensitivityClassifier>>> = OnceLock::new();
+    CLASSIFIER.get_or_init(|| RwLock::new(Arc::new(SensitivityClassifier::default())))
+}
+
+/// The classifier used while converting posts
+pub(crate) fn default_classifier() -> Arc<SensitivityClassifier> {
+    classifier_slot().read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Replace the classifier used while converting posts (e.g. a reloaded lexicon)
+pub fn set_default_classifier(classifier: SensitivityClassifier) {
+    *classifier_slot().write().unwrap_or_else(|e| e.into_inner()) = Arc::new(classifier);
+}
+
+/// Whether text scores as sensitive with the current lexicon
+pub fn contains_sensitive_content(content: &str) -> bool {
+    default_classifier().classify(content).is_sensitive()
+}
+
+/// Zero-width characters and bidi controls with no visible effect on text
+fn is_invisible(c: char) -> bool {
+    matches!(c,
+        '\u{200B}'..='\u{200F}' // zero-width space/non-joiner/joiner, LRM, RLM
+        | '\u{202A}'..='\u{202E}' // bidi embeddings and overrides
+        | '\u{2060}'..='\u{2064}' // word joiner, invisible operators
+        | '\u{2066}'..='\u{2069}' // bidi isolates
+        | '\u{061C}' // Arabic letter mark
+        | '\u{FEFF}' // zero-width no-break space (BOM)
+    )
+}
+
+fn is_variation_selector(c: char) -> bool {
+    matches!(c, '\u{FE00}'..='\u{FE0F}' | '\u{E0100}'..='\u{E01EF}')
+}
+
+/// Emoji and pictographic symbols (approximates Extended_Pictographic)
+fn is_pictograph(c: char) -> bool {
+    matches!(c,
+        '\u{00A9}' | '\u{00AE}' | '\u{203C}' | '\u{2049}' | '\u{2122}' | '\u{2139}'
+        | '\u{2194}'..='\u{21AA}'
+        | '\u{231A}'..='\u{23FF}'
+        | '\u{2600}'..='\u{27BF}'
+        | '\u{2934}'..='\u{2935}'
+        | '\u{2B05}'..='\u{2B55}'
+        | '\u{3030}' | '\u{303D}' | '\u{3297}' | '\u{3299}'
+        | '\u{1F000}'..='\u{1FAFF}'
+    )
+}
+
+/// Normalize post text before matching, hashing and entity extraction
+///
+/// Applies NFKC, drops invisible characters (keeping emoji ZWJ sequences
+/// and emoji variation selectors), collapses runs of spaces within a line
+/// and allows at most one blank line between paragraphs.
+pub fn normalize_text(content: &str) -> String {
+    let chars: Vec<char> = content.nfkc().collect();
+    let mut visible = String::with_capacity(content.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|p| chars[p]);
+        let keep = if c == '\u{200D}' {
+            // Joiner inside an emoji sequence (a variation selector may sit before it)
+            let before = chars[..i].iter().rev().find(|c| !is_variation_selector(**c));
+            before.is_some_and(|c| is_pictograph(*c)) && chars.get(i + 1).is_some_and(|c| is_pictograph(*c))
+        } else if is_variation_selector(c) {
+            previous.is_some_and(is_pictograph)
+        } else {
+            !is_invisible(c)
+        };
+
+        if keep {
+            visible.push(c);
+        }
+    }
+
+    let mut normalized = String::with_capacity(visible.len());
+    let mut blank_lines = 0;
+    for line in visible.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+        if !normalized.is_empty() {
+            normalized.push_str(if blank_lines > 0 { "\n\n" } else { "\n" });
+        }
+        normalized.push_str(&line);
+        blank_lines = 0;
+    }
+
+    normalized
+}
+
+/// Decode HTML character references (`&amp;`, `&#39;`, `&#x27;`, ...)
+///
+/// Platforms HTML-escape text and URLs in their JSON. Decoding is a single
+/// pass, so `&amp;lt;` becomes `&lt;`. Unknown names, unterminated references
+/// and numeric references to invalid code points are left as they are.
+pub fn decode_html_entities(s: &str) -> String {
+    let mut decoded = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        // Longest names are 8 characters ("thetasym"), numbers at most 8 hex digits
+        let reference = rest[1..]
+            .char_indices()
+            .take(11)
+            .find(|(_, c)| *c == ';')
+            .map(|(end, _)| &rest[1..1 + end]);
+        let character = reference.and_then(|name| match name.strip_prefix('#') {
+            Some(number) => match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => number.parse().ok(),
+            }
+            .and_then(char::from_u32)
+            .filter(|c| *c != '\0'),
+            None => HTML_ENTITIES
+                .binary_search_by(|(entity, _)| (*entity).cmp(name))
+                .ok()
+                .map(|i| HTML_ENTITIES[i].1),
+        });
+
+        match (reference, character) {
+            (Some(name), Some(c)) => {
+                decoded.push(c);
+                rest = &rest[name.len() + 2..];
+            },
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            },
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Extract hashtags (without the leading `#`)
+pub fn extract_hashtags(content: &str) -> Vec<String> {
+    static HASHTAG: OnceLock<Regex> = OnceLock::new();
+    let regex = HASHTAG.get_or_init(|| Regex::new(r"(?:^|[^\w&])#(\w+)").unwrap());
+
+    regex.captures_iter(content)
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+
+/// Extract mentioned usernames (without the leading `@`)
+pub fn extract_mentions(content: &str) -> Vec<String> {
+    static MENTION: OnceLock<Regex> = OnceLock::new();
+    let regex = MENTION.get_or_init(|| Regex::new(r"(?:^|[^\w])@(\w+)").unwrap());
+
+    regex.captures_iter(content)
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+
+/// Canonical form of a URL with the default tracking parameters removed
+pub fn canonicalize_url(url: &str) -> String {
+    canonicalize_url_with(url, DEFAULT_TRACKING_PARAMS)
+}
+
+/// Canonical form of a URL: lowercase scheme and host, no default port and
+/// none of the given tracking parameters
+pub fn canonicalize_url_with<S: AsRef<str>>(url: &str, tracking_params: &[S]) -> String {
+    let Ok(mut parsed) = reqwest::Url::parse(url) else {
+        return url.to_string();
+    };
+
+    let is_tracking = |pair: &str| {
+        let key = pair.split('=').next().unwrap_or_default().to_ascii_lowercase();
+        tracking_params.iter().any(|param| match param.as_ref().strip_suffix('*') {
+            Some(prefix) => key.starts_with(prefix),
+            None => key == param.as_ref(),
+        })
+    };
+
+    // Filtered on the raw query so kept parameters aren't re-encoded
+    if let Some(query) = parsed.query() {
+        let kept: Vec<&str> = query.split('&').filter(|pair| !pair.is_empty() && !is_tracking(pair)).collect();
+        let kept = kept.join("&");
+        parsed.set_query((!kept.is_empty()).then_some(kept.as_str()));
+    }
+
+    parsed.to_string()
+}
+
+/// Extract http(s) URLs, dropping trailing punctuation
+pub fn extract_urls(content: &str) -> Vec<String> {
+    static URL: OnceLock<Regex> = OnceLock::new();
+    let regex = URL.get_or_init(|| Regex::new(r#"https?://[^\s<>"]+"#).unwrap());
+
+    regex.find_iter(content)
+        .map(|m| m.as_str().trim_end_matches(|c: char| ".,;:!?)]}'".contains(c)).to_string())
+        .collect()
+}
+
+/// UTC time for Unix seconds; zero, negative and non-finite values are None
+pub fn unix_timestamp(seconds: f64) -> Option<DateTime<Utc>> {
+    if !seconds.is_finite() || seconds <= 0.0 {
+        return None;
+    }
+    Utc.timestamp_opt(seconds as i64, 0).single()
+}
+
+/// Creation time for a converted post, recording where it came from
+///
+/// A post without a usable `parsed` time is stamped with the current time
+/// and flagged `timestamp_unreliable`, with a warning.
+pub fn resolve_created_at(
+    parsed: Option<DateTime<Utc>>,
+    post_id: &str,
+    metadata: &mut HashMap<String, serde_json::Value>,
+) -> DateTime<Utc> {
+    match parsed {
+        Some(created_at) => {
+            metadata.insert("created_at_source".to_string(), serde_json::json!("api"));
+            created_at
+        },
+        None => {
+            tracing::warn!("Post {} has no usable creation time; using the collection time", post_id);
+            metadata.insert("created_at_source".to_string(), serde_json::json!("defaulted"));
+            metadata.insert("timestamp_unreliable".to_string(), serde_json::Value::Bool(true));
+            Utc::now()
+        },
+    }
+}
+
+/// Where a query term matched in post content
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchSpan {
+    pub term: String,
+
+    /// Character offset of the first matched character
+    pub start: usize,
+
+    /// Character offset just past the match
+    pub end: usize,
+}
+
+/// Plain terms and quoted phrases of a search query, lowercased and deduplicated
+pub fn query_terms(query: &str) -> Vec<String> {
+    static TOKEN: OnceLock<Regex> = OnceLock::new();
+    let regex = TOKEN.get_or_init(|| Regex::new(r#""([^"]+)"|(\S+)"#).unwrap());
+
+    let mut terms: Vec<String> = Vec::new();
+    for caps in regex.captures_iter(query) {
+        let term = match (caps.get(1), caps.get(2)) {
+            (Some(phrase), _) => phrase.as_str().trim().to_string(),
+            (None, Some(token)) => {
+                let token = token.as_str();
+                if matches!(token, "OR" | "AND" | "NOT") || token.starts_with('-') || token.contains(':') {
+                    continue;
+                }
+                token.trim_matches(|c: char| "()".contains(c)).trim_start_matches(['#', '@']).to_string()
+            },
+            (None, None) => continue,
+        };
+
+        let term = term.to_lowercase();
+        if !term.is_empty() && !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+/// Case-insensitive, word-bounded matches of `terms` in `content`, in order
+/// of position with overlapping matches merged
+pub fn match_spans(content: &str, terms: &[String]) -> Vec<MatchSpan> {
+    let char_offset = |byte: usize| content[..byte].chars().count();
+
+    let mut spans: Vec<MatchSpan> = Vec::new();
+    for term in terms {
+        let Ok(regex) = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(term))) else {
+            continue;
+        };
+        spans.extend(regex.find_iter(content).map(|m| MatchSpan {
+            term: term.clone(),
+            start: char_offset(m.start()),
+            end: char_offset(m.end()),
+        }));
+    }
+    spans.sort_by_key(|span| (span.start, span.end));
+
+    let mut merged: Vec<MatchSpan> = Vec::new();
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if span.start < last.end => {
+                last.end = last.end.max(span.end);
+                if !last.term.split('|').any(|term| term == span.term) {
+                    last.term = format!("{}|{}", last.term, span.term);
+                }
+            },
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+/// Record where the query terms matched a post under metadata["matches"]
+///
+/// Posts without a match get no entry.
+pub fn highlight_matches(post: &mut SocialPost, terms: &[String]) {
+    let spans = match_spans(&post.content, terms);
+    if !spans.is_empty() {
+        post.metadata.insert("matches".to_string(), serde_json::json!(spans));
+    }
+}
+
+/// Deterministic cross-platform id, e.g. "reddit:abc123"
+///
+/// Storage, sinks and the models layer key posts on this rather than the
+/// native id alone, so the same post always gets the same id.
+pub fn canonical_id(platform: &str, native_id: &str) -> String {
+    format!("{}:{}", platform, native_id)
+}
+
+/// Give a freshly converted post its canonical id, moving the native id
+/// into `metadata["native_id"]`; every connector's converter calls this
+pub fn assign_canonical_id(post: &mut SocialPost) {
+    let native_id = post.native_id().to_string();
+    post.id = canonical_id(&post.platform, &native_id);
+    post.metadata.insert("native_id".to_string(), serde_json::Value::String(native_id));
+}
+
+/// Truncate to at most `max_chars` characters without splitting a character
+pub fn safe_truncate(s: &str, max_chars: usize) -> &str {
+    match s.char_indices().nth(max_chars) {
+        Some((byte_index, _)) => &s[..byte_index],
+        None => s,
+    }
+}
+
+/// Shorten text to at most `max_chars` characters, ending on a word boundary
+///
+/// The cut text ends with an ellipsis (counted towards `max_chars`). A
+/// single word longer than the limit is cut mid-word. Returns `None` when
+/// the text already fits.
+pub fn truncate_on_word_boundary(text: &str, max_chars: usize) -> Option<String> {
+    if text.chars().count() <= max_chars {
+        return None;
+    }
+
+    let kept = safe_truncate(text, max_chars.saturating_sub(1));
+    let next_is_break = text[kept.len()..].starts_with(char::is_whitespace);
+    let kept = match kept.rfind(char::is_whitespace) {
+        Some(last_space) if !next_is_break => &kept[..last_space],
+        _ => kept,
+    };
+
+    Some(format!("{}…", kept.trim_end()))
+}
+
+/// Slice by byte range, shrinking the range inwards to character boundaries
+///
+/// Out-of-bounds ranges are clamped to the string, and an empty string is
+/// returned if no whole character lies inside the range.
+pub fn safe_slice(s: &str, byte_range: Range<usize>) -> &str {
+    let mut start = byte_range.start.min(s.len());
+    let mut end = byte_range.end.min(s.len());
+
+    while !s.is_char_boundary(start) {
+        start += 1;
+    }
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    if start >= end {
+        ""
+    } else {
+        &s[start..end]
+    }
+}
+
+/// Snap a location to a grid with cells of roughly `precision_km` per side
+///
+/// Returns `None` when the precision is not positive or the coordinates are
+/// not finite, in which case no generalization can be applied.
+pub fn generalize_location(location: &GeoLocation, precision_km: f64) -> Option<GeoLocation> {
+    let valid_precision = precision_km.is_finite() && precision_km > 0.0;
+    if !valid_precision || !location.latitude.is_finite() || !location.longitude.is_finite() {
+        return None;
+    }
+
+    let lat_step = precision_km / KM_PER_DEGREE_LAT;
+    let latitude = ((location.latitude / lat_step).round() * lat_step).clamp(-90.0, 90.0);
+
+    // Longitude degrees shrink with latitude; cap the step at a full turn near the poles
+    let km_per_degree_lon = KM_PER_DEGREE_LON_EQUATOR * latitude.to_radians().cos();
+    let lon_step = if km_per_degree_lon > 0.0 {
+        (precision_km / km_per_degree_lon).min(360.0)
+    } else {
+        360.0
+    };
+
+    let mut longitude = (location.longitude / lon_step).round() * lon_step;
+    if longitude > 180.0 {
+        longitude -= 360.0;
+    } else if longitude < -180.0 {
+        longitude += 360.0;
+    }
+
+    Some(GeoLocation {
+        latitude,
+        longitude,
+        radius_km: precision_km,
+        name: location.name.clone(),
+    })
+}
+
+/// Apply the configured privacy rules to a normalized post
+///
+/// Runs `config.pipeline` (classification, truncation, location
+/// generalization and required field checks by default); the
+/// `location_generalized` flag reflects whether generalization happened.
+pub fn apply_privacy_compliance(post: &mut SocialPost, config: &PrivacyConfig) {
+    post.privacy_flags.location_generalized = false;
+    config.pipeline.run(post, config);
+}
+
+/// Whether a post matches the requested content type
+///
+/// Media types are compared across platforms: Twitter's "photo" counts as an
+/// image and its "animated_gif" (an MP4 clip) as a video.
+pub fn matches_content_type(post: &SocialPost, content_type: &ContentType) -> bool {
+    let has_media = |kinds: &[&str]| post.media.iter().any(|m| kinds.contains(&m.media_type.as_str()));
+
+    match content_type {
+        ContentType::All => true,
+        ContentType::Image => has_media(&["image", "photo"]),
+        ContentType::Video => has_media(&["video", "animated_gif"]),
+        ContentType::Link => !post.urls.is_empty() && post.media.is_empty(),
+        ContentType::Text => post.urls.is_empty() && post.media.is_empty(),
+    }
+}
+
+/// Keep only posts matching `content_type` (all posts when unset)
+pub fn filter_by_content_type(posts: Vec<SocialPost>, content_type: Option<&ContentType>) -> Vec<SocialPost> {
+    match content_type {
+        Some(content_type) => posts
+            .into_iter()
+            .filter(|post| matches_content_type(post, content_type))
+            .collect(),
+        None => posts,
+    }
+}
+
+/// Keep only posts located inside `bounds`
+///
+/// Posts without a location pass unless `require_location` is set, which
+/// drops them even when no bounds are given.
+pub fn filter_by_bounds(posts: Vec<SocialPost>, bounds: Option<&GeoBounds>, require_location: bool) -> Vec<SocialPost> {
+    if bounds.is_none() && !require_location {
+        return posts;
+    }
+
+    posts
+        .into_iter()
+        .filter(|post| match (&post.location, bounds) {
+            (None, _) => !require_location,
+            (Some(_), None) => true,
+            (Some(location), Some(bounds)) => bounds.contains(location.latitude, location.longitude),
+        })
+        .collect()
+}
+
+/// Allow and deny regexes over post content, compiled once per search
+#[derive(Debug, Clone, Default)]
+pub struct ContentFilter {
+    allow: Vec<Regex>,
+    deny: Vec<Regex>,
+}
+
+impl ContentFilter {
+    /// Compile the allow and deny patterns, a ConfigError naming the first invalid one
+    pub fn new(allow: &[String], deny: &[String]) -> Result<Self, ConnectorError> {
+        let compile = |patterns: &[String], list: &str| -> Result<Vec<Regex>, ConnectorError> {
+            patterns
+                .iter()
+                .map(|pattern| Regex::new(pattern).map_err(|e| ConnectorError::ConfigError {
+                    message: format!("Invalid content_{} pattern {:?}: {}", list, pattern, e),
+                }))
+                .collect()
+        };
+
+        Ok(Self {
+            allow: compile(allow, "allow")?,
+            deny: compile(deny, "deny")?,
+        })
+    }
+
+    /// Filter for a search's `content_allow` and `content_deny`
+    pub fn from_params(params: &SearchParams) -> Result<Self, ConnectorError> {
+        Self::new(&params.content_allow, &params.content_deny)
+    }
+
+    /// Whether the filter keeps every post
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// Whether a post matches an allow pattern (if there are any) and no deny pattern
+    pub fn keeps(&self, post: &SocialPost) -> bool {
+        (self.allow.is_empty() || self.allow.iter().any(|re| re.is_match(&post.content)))
+            && !self.deny.iter().any(|re| re.is_match(&post.content))
+    }
+
+    /// Keep only the posts this filter keeps
+    pub fn apply(&self, posts: Vec<SocialPost>) -> Vec<SocialPost> {
+        if self.is_empty() {
+            return posts;
+        }
+
+        posts.into_iter().filter(|post| self.keeps(post)).collect()
+    }
+}
+
+/// Order merged results; ties keep their existing (API) order
+pub fn sort_posts(posts: &mut [SocialPost], order: SortOrder) {
+    match order {
+        SortOrder::Relevance => {},
+        SortOrder::NewestFirst => posts.sort_by_key(|post| std::cmp::Reverse(post.created_at)),
+        SortOrder::OldestFirst => posts.sort_by_key(|post| post.created_at),
+        SortOrder::MostEngaged => posts.sort_by(|a, b| {
+            b.calculate_engagement_score().total_cmp(&a.calculate_engagement_score())
+        }),
+    }
+}
+
+/// How long a 429 response asks us to wait, from `Retry-After` or `x-rate-limit-reset`
+///
+/// Times in the past give a zero wait; unparseable headers give None.
+pub fn retry_after(headers: &reqwest::header::HeaderMap, now: DateTime<Utc>) -> Option<std::time::Duration> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok()).map(str::trim);
+
+    let until = if let Some(value) = header("retry-after") {
+        match value.parse::<u64>() {
+            Ok(secs) => return Some(std::time::Duration::from_secs(secs)),
+            Err(_) => DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc),
+        }
+    } else {
+        Utc.timestamp_opt(header("x-rate-limit-reset")?.parse().ok()?, 0).single()?
+    };
+
+    Some((until - now).to_std().unwrap_or_default())
+}
+
+/// Record a connector request's latency, labeled with its HTTP status or
+/// "error" when no response arrived
+pub fn record_request_duration(
+    platform: &str,
+    operation: &str,
+    status: Option<reqwest::StatusCode>,
+    elapsed: std::time::Duration,
+) {
+    let status = status.map_or_else(|| "error".to_string(), |status| status.as_u16().to_string());
+    crate::metrics::request_durations().record(platform, operation, &status, elapsed);
+}
+
+/// Send a request, recording its latency under `operation`
+pub async fn send_timed(
+    platform: &str,
+    operation: &str,
+    request: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let started = std::time::Instant::now();
+    let result = request.send().await;
+    record_request_duration(platform, operation, result.as_ref().ok().map(|response| response.status()), started.elapsed());
+    result
+}
+
+/// Send a request, and once more after the wait a 429 response asks for
+///
+/// `send` builds and sends a fresh request each call. The wait is capped at
+/// `max_wait`; a 429 without a parseable wait, or a second 429, is returned
+/// for the caller to map. A retry the running job's RetryBudget can't cover
+/// fails with RetryBudgetExhausted instead.
+pub async fn send_with_retry_after<F, Fut>(max_wait: std::time::Duration, mut send: F) -> Result<reqwest::Response, ConnectorError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<reqwest::Response, ConnectorError>>,
+{
+    let response = send().await?;
+    if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Ok(response);
+    }
+
+    let Some(wait) = retry_after(response.headers(), Utc::now()) else {
+        return Ok(response);
+    };
+    // Retrying spends from the running job's budget, if it has one
+    if let Some(budget) = RetryBudget::current() {
+        budget.spend(wait.min(max_wait))?;
+    }
+
+    if wait > max_wait {
+        tracing::warn!("Rate limited for {:?}, retrying after the {:?} cap", wait, max_wait);
+    } else {
+        tracing::warn!("Rate limited, retrying in {:?}", wait);
+    }
+
+    tokio::time::sleep(wait.min(max_wait)).await;
+    send().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(latitude: f64, longitude: f64) -> GeoLocation {
+        GeoLocation { latitude, longitude, radius_km: 1.0, name: None }
+    }
+
+    /// Deterministic xorshift generator so failures are reproducible
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, bound: usize) -> usize {
+            (self.next() % bound as u64) as usize
+        }
+    }
+
+    /// Random mix of 1- to 4-byte characters, including combining marks and ZWJ emoji
+    fn random_multibyte_string(rng: &mut XorShift) -> String {
+        const ALPHABET: &[&str] = &["a", " ", "é", "ß", "中", "文", "🙂", "👩‍💻", "e\u{301}", "\u{200b}", "ق"];
+        (0..rng.below(40)).map(|_| ALPHABET[rng.below(ALPHABET.len())]).collect()
+    }
+
+    #[test]
+    fn test_safe_truncate_random_multibyte() {
+        let mut rng = XorShift(0x5eed_1234_abcd_ef01);
+
+        for _ in 0..2_000 {
+            let s = random_multibyte_string(&mut rng);
+            let max_chars = rng.below(50);
+            let truncated = safe_truncate(&s, max_chars);
+
+            assert!(s.starts_with(truncated));
+            assert_eq!(truncated.chars().count(), s.chars().count().min(max_chars));
+        }
+
+        assert_eq!(safe_truncate("héllo wörld", 4), "héll");
+        assert_eq!(safe_truncate("🙂🙂", 5), "🙂🙂");
+    }
+
+    #[test]
+    fn test_safe_slice_random_cut_points() {
+        let mut rng = XorShift(0x0bad_cafe_f00d_d00d);
+
+        for _ in 0..2_000 {
+            let s = random_multibyte_string(&mut rng);
+            let a = rng.below(s.len() + 8);
+            let b = rng.below(s.len() + 8);
+            let sliced = safe_slice(&s, a..b);
+
+            // Result is a whole-character substring lying within the requested bytes
+            assert!(sliced.len() <= b.saturating_sub(a));
+            if !sliced.is_empty() {
+                let offset = sliced.as_ptr() as usize - s.as_ptr() as usize;
+                assert!(offset >= a && offset + sliced.len() <= b.min(s.len()));
+            }
+            assert!(std::str::from_utf8(sliced.as_bytes()).is_ok());
+        }
+
+        // Cutting inside "é" (bytes 1..3) drops it rather than panicking
+        assert_eq!(safe_slice("héllo", 2..5), "ll");
+        assert_eq!(safe_slice("héllo", 0..2), "h");
+    }
+
+    #[test]
+    fn test_anonymize_user_id_per_algorithm() {
+        let algorithms = [HashAlgorithm::Sha256, HashAlgorithm::Blake3, HashAlgorithm::HmacSha256];
+        let hashes: Vec<String> = algorithms
+            .iter()
+            .map(|&hash_algorithm| {
+                let config = PrivacyConfig { hash_algorithm, ..PrivacyConfig::default() };
+                let first = anonymize_user_id("t2_abc123", &config);
+                assert_eq!(first, anonymize_user_id("t2_abc123", &config));
+                first
+            })
+            .collect();
+
+        assert_ne!(hashes[0], hashes[1]);
+        assert_ne!(hashes[0], hashes[2]);
+        assert_ne!(hashes[1], hashes[2]);
+
+        // Default keeps the original salted SHA-256 so stored hashes stay valid
+        let config = PrivacyConfig::default();
+        let legacy = hex::encode(Sha256::digest(format!("{}t2_abc123", config.salt).as_bytes()));
+        assert_eq!(hashes[0], legacy);
+    }
+
+    #[test]
+    fn test_generalize_location_near_equator() {
+        let generalized = generalize_location(&location(0.0123, 36.8219), 10.0).unwrap();
+
+        assert!((generalized.latitude - 0.0).abs() < 1e-9);
+        assert!((generalized.longitude - 36.830_758_174_631_7).abs() < 1e-6);
+        assert_eq!(generalized.radius_km, 10.0);
+
+        // A point ~3km away lands in the same cell
+        let neighbour = generalize_location(&location(0.03, 36.80), 10.0).unwrap();
+        assert_eq!(neighbour.latitude, generalized.latitude);
+        assert_eq!(neighbour.longitude, generalized.longitude);
+    }
+
+    #[test]
+    fn test_generalize_location_near_60_north() {
+        let generalized = generalize_location(&location(60.1699, 24.9384), 10.0).unwrap();
+
+        assert!((generalized.latitude - 60.140_720_241_648_1).abs() < 1e-6);
+        assert!((generalized.longitude - 24.899_384_691_605_6).abs() < 1e-6);
+
+        // At 60°N a longitude degree is half as long, so 0.1° (~5.5km) still shares a cell
+        let neighbour = generalize_location(&location(60.1699, 24.8384), 10.0).unwrap();
+        assert_eq!(neighbour.longitude, generalized.longitude);
+    }
+
+    #[test]
+    fn test_location_flag_only_set_when_generalized() {
+        let mut post = test_utils::post("1", "somewhere");
+        post.privacy_flags.location_generalized = true;
+
+        apply_privacy_compliance(&mut post, &PrivacyConfig::default());
+        assert!(!post.privacy_flags.location_generalized);
+
+        post.location = Some(location(51.5072, -0.1276));
+        apply_privacy_compliance(&mut post, &PrivacyConfig { location_precision_km: 0.0, ..PrivacyConfig::default() });
+        assert!(!post.privacy_flags.location_generalized);
+        assert_eq!(post.location.as_ref().unwrap().latitude, 51.5072);
+
+        apply_privacy_compliance(&mut post, &PrivacyConfig::default());
+        assert!(post.privacy_flags.location_generalized);
+        assert_eq!(post.location.as_ref().unwrap().radius_km, 10.0);
+    }
+
+    #[test]
+    fn test_normalize_text_defeats_invisible_and_fullwidth_obfuscation() {
+        let injected = "Massive fl\u{200B}ood in the c\u{200D}ity #fl\u{2060}ood @re\u{FEFF}porter";
+        let normalized = normalize_text(injected);
+
+        assert_eq!(normalized, "Massive flood in the city #flood @reporter");
+        assert_eq!(extract_hashtags(&normalized), vec!["flood"]);
+        assert_eq!(extract_mentions(&normalized), vec!["reporter"]);
+
+        // Fullwidth letters, bidi overrides and letter variation selectors
+        assert_eq!(normalize_text("ＦＬＯＯＤ　ｗａｒｎｉｎｇ ＃ｒａｉｎ"), "FLOOD warning #rain");
+        assert_eq!(normalize_text("\u{202E}flood\u{202C} a\u{FE0F}lert"), "flood alert");
+    }
+
+    #[test]
+    fn test_normalize_text_keeps_emoji_and_paragraphs() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let heart = "\u{2764}\u{FE0F}";
+        let text = format!("Stay   safe {} {}\n\n\n\nUpdates  below\t\tsoon\n", family, heart);
+
+        assert_eq!(
+            normalize_text(&text),
+            format!("Stay safe {} {}\n\nUpdates below soon", family, heart)
+        );
+    }
+
+    #[test]
+    fn test_decode_html_entities() {
+        assert_eq!(decode_html_entities("Rain &amp; wind"), "Rain & wind");
+        assert_eq!(decode_html_entities("Don&#39;t go, it&#x27;s flooded"), "Don't go, it's flooded");
+        assert_eq!(decode_html_entities("&quot;Stay home&quot;"), "\"Stay home\"");
+        assert_eq!(
+            decode_html_entities("&lt;b&gt; 5&nbsp;cm &euro; &#128168; &amp;lt; AT&T &bogus; &#0; &amp"),
+            "<b> 5\u{a0}cm \u{20ac} \u{1f4a8} &lt; AT&T &bogus; &#0; &amp"
+        );
+    }
+
+    #[test]
+    fn test_long_content_truncated_at_word_boundary() {
+        let mut post = test_utils::post("1", "Flood waters rising near #riverside, see https://news.example/flood");
+        post.hashtags = vec!["riverside".to_string()];
+        post.urls = vec!["https://news.example/flood".to_string()];
+        let config = PrivacyConfig { max_content_chars: Some(20), ..PrivacyConfig::default() };
+
+        apply_privacy_compliance(&mut post, &config);
+
+        assert_eq!(post.content, "Flood waters rising…");
+        assert!(post.content.chars().count() <= 20);
+        assert_eq!(post.metadata["content_truncated"], serde_json::json!(true));
+        assert_eq!(post.metadata["original_content_length"], serde_json::json!(67));
+        assert_eq!(post.hashtags, vec!["riverside"]);
+        assert_eq!(post.urls, vec!["https://news.example/flood"]);
+
+        // A single overlong word is cut mid-word
+        assert_eq!(truncate_on_word_boundary("Supercalifragilistic", 6).unwrap(), "Super…");
+    }
+
+    #[test]
+    fn test_short_content_not_truncated() {
+        let mut post = test_utils::post("1", "Short post");
+        let config = PrivacyConfig { max_content_chars: Some(10), ..PrivacyConfig::default() };
+
+        apply_privacy_compliance(&mut post, &config);
+
+        assert_eq!(post.content, "Short post");
+        assert!(!post.metadata.contains_key("content_truncated"));
+        assert!(!post.metadata.contains_key("original_content_length"));
+    }
+
+    fn media(media_type: &str) -> MediaAttachment {
+        MediaAttachment {
+            media_type: media_type.to_string(),
+            url: format!("https://media.example/{}", media_type),
+            alt_text: None,
+            dimensions: None,
+            file_size: None,
+        }
+    }
+
+    #[test]
+    fn test_match_spans_for_two_of_three_terms() {
+        let terms = query_terms(r#"Flood OR "relief camp" -rumour lang:en #evacuation"#);
+        assert_eq!(terms, vec!["flood", "relief camp", "evacuation"]);
+
+        // "Floods" isn't a word-bounded match; offsets count characters, not bytes
+        let mut post = test_utils::post("1", "Mumbaí FLOOD update: Floods rising, flood relief camp open");
+        highlight_matches(&mut post, &terms);
+        let spans: Vec<MatchSpan> = serde_json::from_value(post.metadata["matches"].clone()).unwrap();
+        assert_eq!(spans, vec![
+            MatchSpan { term: "flood".to_string(), start: 7, end: 12 },
+            MatchSpan { term: "flood".to_string(), start: 36, end: 41 },
+            MatchSpan { term: "relief camp".to_string(), start: 42, end: 53 },
+        ]);
+
+        // Overlapping matches merge into one span
+        let terms = vec!["relief camp".to_string(), "camp open".to_string()];
+        assert_eq!(match_spans("relief camp open", &terms), vec![
+            MatchSpan { term: "relief camp|camp open".to_string(), start: 0, end: 16 },
+        ]);
+    }
+
+    #[test]
+    fn test_filter_by_content_type_each_variant() {
+        let mut photo = test_utils::post("photo", "tweet photo");
+        photo.media = vec![media("photo")];
+        let mut image = test_utils::post("image", "reddit image");
+        image.media = vec![media("image")];
+        image.urls = vec!["https://i.redd.it/abc.jpg".to_string()];
+        let mut video = test_utils::post("video", "clip");
+        video.media = vec![media("video")];
+        let mut gif = test_utils::post("gif", "gif");
+        gif.media = vec![media("animated_gif")];
+        let mut link = test_utils::post("link", "read this");
+        link.urls = vec!["https://news.example/story".to_string()];
+        let text = test_utils::post("text", "just words");
+
+        let mixed = vec![photo, image, video, gif, link, text];
+        let ids = |content_type: Option<ContentType>| -> Vec<String> {
+            filter_by_content_type(mixed.clone(), content_type.as_ref())
+                .into_iter()
+                .map(|post| post.id)
+                .collect()
+        };
+
+        assert_eq!(ids(Some(ContentType::Image)), vec!["photo", "image"]);
+        assert_eq!(ids(Some(ContentType::Video)), vec!["video", "gif"]);
+        assert_eq!(ids(Some(ContentType::Link)), vec!["link"]);
+        assert_eq!(ids(Some(ContentType::Text)), vec!["text"]);
+        assert_eq!(ids(Some(ContentType::All)).len(), 6);
+        assert_eq!(ids(None).len(), 6);
+    }
+
+    #[test]
+    fn test_filter_by_bounds_handles_antimeridian() {
+        let located = |id: &str, latitude: f64, longitude: f64| {
+            let mut post = test_utils::post(id, id);
+            post.location = Some(location(latitude, longitude));
+            post
+        };
+        let posts = vec![
+            located("suva", -18.14, 178.44),
+            located("apia", -13.83, -171.76),
+            located("sydney", -33.87, 151.21),
+            test_utils::post("unknown", "unknown"),
+        ];
+        let ids = |bounds: &GeoBounds, require_location: bool| -> Vec<String> {
+            filter_by_bounds(posts.clone(), Some(bounds), require_location)
+                .into_iter()
+                .map(|post| post.id)
+                .collect()
+        };
+
+        // Fiji to Samoa, wrapping around 180
+        let pacific = GeoBounds { north: -10.0, south: -25.0, east: -170.0, west: 170.0 };
+        assert!(pacific.crosses_antimeridian());
+        assert_eq!(ids(&pacific, false), vec!["suva", "apia", "unknown"]);
+        assert_eq!(ids(&pacific, true), vec!["suva", "apia"]);
+
+        let mumbai = GeoBounds { north: 19.3, south: 18.85, east: 73.0, west: 72.75 };
+        assert!(!mumbai.crosses_antimeridian());
+        assert_eq!(ids(&mumbai, false), vec!["unknown"]);
+
+        assert_eq!(filter_by_bounds(posts.clone(), None, false).len(), 4);
+        assert_eq!(filter_by_bounds(posts, None, true).len(), 3);
+    }
+
+    #[test]
+    fn test_unix_timestamp_rejects_zero_and_invalid() {
+        assert_eq!(unix_timestamp(1709294400.9), Utc.timestamp_opt(1709294400, 0).single());
+        assert_eq!(unix_timestamp(0.0), None);
+        assert_eq!(unix_timestamp(-5.0), None);
+        assert_eq!(unix_timestamp(f64::NAN), None);
+        assert_eq!(unix_timestamp(f64::INFINITY), None);
+    }
+
+    #[test]
+    fn test_sort_posts_for_each_order() {
+        let dated = |id: &str, day: u32, likes: u64, shares: u64| {
+            let mut post = test_utils::post(id, id);
+            post.created_at = Utc.with_ymd_and_hms(2024, 7, day, 12, 0, 0).unwrap();
+            post.metrics.likes = likes;
+            post.metrics.shares = shares;
+            post
+        };
+        // "b" and "c" share a timestamp and an engagement score (6.0)
+        let posts = vec![
+            dated("a", 2, 1, 0),
+            dated("b", 3, 6, 0),
+            dated("c", 3, 0, 2),
+            dated("d", 1, 10, 5),
+        ];
+        let ids = |order: SortOrder| -> Vec<String> {
+            let mut sorted = posts.clone();
+            sort_posts(&mut sorted, order);
+            sorted.into_iter().map(|post| post.id).collect()
+        };
+
+        assert_eq!(ids(SortOrder::Relevance), vec!["a", "b", "c", "d"]);
+        assert_eq!(ids(SortOrder::NewestFirst), vec!["b", "c", "a", "d"]);
+        assert_eq!(ids(SortOrder::OldestFirst), vec!["d", "a", "b", "c"]);
+        assert_eq!(ids(SortOrder::MostEngaged), vec!["d", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_obfuscated_terms_are_caught() {
+        let classifier = SensitivityClassifier::default();
+
+        for text in ["feeling su1c1d3 tonight", "S.U.I.C.I.D.E", "this is n$fw", "so much g0r3 in that clip"] {
+            let score = classifier.classify(text);
+            assert!(score.is_sensitive(), "{:?} scored {:?}", text, score);
+            assert!(contains_sensitive_content(text));
+        }
+
+        let mixed = classifier.classify("nsfw, graphic violence");
+        assert_eq!(mixed.categories, vec!["adult", "violence"]);
+        assert!(mixed.score > 0.9 && mixed.score <= 1.0);
+    }
+
+    #[test]
+    fn test_benign_text_scores_low() {
+        let classifier = SensitivityClassifier::default();
+
+        for text in ["Explicit instructions for the flood shelter", "Gorey street closed", "Suicidesquad trailer", "Lovely sunset"] {
+            let score = classifier.classify(text);
+            assert!(!score.is_sensitive(), "{:?} scored {:?}", text, score);
+        }
+
+        let mut post = test_utils::post("1", "Traffic update: roads open");
+        apply_privacy_compliance(&mut post, &PrivacyConfig::default());
+        assert!(!post.metadata.contains_key("content_warnings"));
+
+        let mut post = test_utils::post("2", "trigger warning: self harm");
+        apply_privacy_compliance(&mut post, &PrivacyConfig::default());
+        assert_eq!(post.metadata["content_warnings"], serde_json::json!(["self_harm"]));
+    }
+
+    #[test]
+    fn test_custom_pattern_is_validated() {
+        let entry = |pattern: &str| LexiconEntry {
+            term: "threat".to_string(),
+            category: "violence".to_string(),
+            weight: 0.7,
+            pattern: Some(pattern.to_string()),
+        };
+
+        let classifier = SensitivityClassifier::new(&[entry(r"\bbomb threat\b")]).unwrap();
+        assert_eq!(classifier.classify("BOMB THREAT reported").categories, vec!["violence"]);
+        assert!(matches!(SensitivityClassifier::new(&[entry("(unclosed")]), Err(ConnectorError::ConfigError { .. })));
+    }
+
+    #[test]
+    fn test_canonicalize_url_strips_tracking_params() {
+        assert_eq!(
+            canonicalize_url("HTTPS://News.Example.COM:443/story?id=42&utm_source=twitter&UTM_Medium=social&fbclid=IwAR0x&lang=en%20gb"),
+            "https://news.example.com/story?id=42&lang=en%20gb"
+        );
+        assert_eq!(canonicalize_url("http://example.com:80/a?gclid=1&utm_campaign=x#top"), "http://example.com/a#top");
+        assert_eq!(canonicalize_url_with("https://example.com/?ref=feed&id=1", &["ref"]), "https://example.com/?id=1");
+
+        let mut post = test_utils::post("1", "");
+        post.urls = vec!["https://example.com/a?utm_source=x".to_string(), "https://example.com/b".to_string()];
+        apply_privacy_compliance(&mut post, &PrivacyConfig::default());
+        assert_eq!(post.urls, vec!["https://example.com/a", "https://example.com/b"]);
+        assert_eq!(post.metadata["original_urls"], serde_json::json!(["https://example.com/a?utm_source=x", "https://example.com/b"]));
+    }
+
+    #[test]
+    fn test_canonicalize_url_keeps_clean_urls() {
+        for url in ["https://example.com/path?q=flood+relief&page=2", "https://example.com/", "not a url"] {
+            assert_eq!(canonicalize_url(url), url);
+        }
+
+        let mut post = test_utils::post("1", "");
+        post.urls = vec!["https://example.com/path?q=flood".to_string()];
+        apply_privacy_compliance(&mut post, &PrivacyConfig::default());
+        assert_eq!(post.urls, vec!["https://example.com/path?q=flood"]);
+        assert!(!post.metadata.contains_key("original_urls"));
+    }
+
+    #[test]
+    fn test_lexicon_loaded_from_file() {
+        let directory = std::env::temp_dir().join(format!("sentinel-lexicon-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let plain = directory.join("terms.txt");
+        std::fs::write(&plain, "# local terms\nswatting | violence | 0.8\n\ndoxxing\n").unwrap();
+        let classifier = SensitivityClassifier::from_file(&plain).unwrap();
+        assert_eq!(classifier.classify("reports of sw4tting downtown").categories, vec!["violence"]);
+        assert_eq!(classifier.classify("doxxing thread").categories, vec!["custom"]);
+        assert!(classifier.classify("doxxing thread").is_sensitive());
+        assert!(!classifier.classify("graphic violence").is_sensitive());
+
+        let toml = directory.join("terms.toml");
+        std::fs::write(&toml, "[[terms]]\nterm = \"swatting\"\ncategory = \"violence\"\nweight = 0.8\n").unwrap();
+        assert!(SensitivityClassifier::from_file(&toml).unwrap().classify("swatting call").is_sensitive());
+
+        std::fs::write(&plain, "swatting | violence | high\n").unwrap();
+        let Err(ConnectorError::ConfigError { message }) = SensitivityClassifier::from_file(&plain) else {
+            panic!("malformed weight accepted");
+        };
+        assert!(message.ends_with("terms.txt:1: invalid weight 'high'"), "{}", message);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_retry_after_seconds_and_http_date() {
+        let headers = |name: &'static str, value: &'static str| {
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert(name, reqwest::header::HeaderValue::from_static(value));
+            headers
+        };
+        let now = Utc.with_ymd_and_hms(2015, 10, 21, 7, 27, 30).unwrap();
+        let secs = std::time::Duration::from_secs;
+
+        assert_eq!(retry_after(&headers("retry-after", "120"), now), Some(secs(120)));
+        assert_eq!(retry_after(&headers("retry-after", "Wed, 21 Oct 2015 07:28:00 GMT"), now), Some(secs(30)));
+        assert_eq!(retry_after(&headers("retry-after", "Wed, 21 Oct 2015 07:00:00 GMT"), now), Some(secs(0)));
+        assert_eq!(retry_after(&headers("x-rate-limit-reset", "1445412500"), now), Some(secs(50)));
+        assert_eq!(retry_after(&headers("retry-after", "soon"), now), None);
+        assert_eq!(retry_after(&reqwest::header::HeaderMap::new(), now), None);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_wait_is_capped() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "3600"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = reqwest::Client::new();
+        let started = std::time::Instant::now();
+        let response = send_with_retry_after(std::time::Duration::from_millis(50), || async {
+            client.get(server.uri()).send().await.map_err(|e| ConnectorError::NetworkError { source: e })
+        }).await.unwrap();
+
+        assert_eq!(response.status(), 200);
+        assert!(started.elapsed() >= std::time::Duration::from_millis(50));
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    fn kept(filter: &ContentFilter, posts: &[SocialPost]) -> Vec<String> {
+        filter.apply(posts.to_vec()).into_iter().map(|post| post.id).collect()
+    }
+
+    fn content_filter_posts() -> Vec<SocialPost> {
+        vec![
+            test_utils::post("1", "Flood warning for Chennai"),
+            test_utils::post("2", "FREE crypto giveaway, click now"),
+            test_utils::post("3", "Flood relief: win FREE crypto"),
+            test_utils::post("4", "Traffic update"),
+        ]
+    }
+
+    #[test]
+    fn test_content_allow_only() {
+        let filter = ContentFilter::new(&["(?i)flood".to_string(), "traffic".to_string()], &[]).unwrap();
+        assert_eq!(kept(&filter, &content_filter_posts()), vec!["1", "3"]);
+    }
+
+    #[test]
+    fn test_content_deny_only() {
+        let filter = ContentFilter::new(&[], &[r"(?i)free\s+crypto".to_string()]).unwrap();
+        assert_eq!(kept(&filter, &content_filter_posts()), vec!["1", "4"]);
+
+        assert!(ContentFilter::default().is_empty());
+        assert_eq!(kept(&ContentFilter::default(), &content_filter_posts()), vec!["1", "2", "3", "4"]);
+    }
+
+    #[test]
+    fn test_content_allow_and_deny_combined() {
+        let filter = ContentFilter::new(&["(?i)flood".to_string()], &["(?i)crypto".to_string()]).unwrap();
+        assert_eq!(kept(&filter, &content_filter_posts()), vec!["1"]);
+
+        let invalid = ContentFilter::new(&["flood".to_string()], &["free(crypto".to_string()]);
+        assert!(matches!(invalid, Err(ConnectorError::ConfigError { message }) if message.contains("content_deny")));
+    }
+}
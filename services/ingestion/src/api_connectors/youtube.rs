@@ -45,6 +45,21 @@ impl ApiConnector for YouTubeConnector {
         !self.api_key.is_empty()
     }
 
+    fn capabilities(&self) -> Capabilities {
+        // Placeholder until the Data API integration lands
+        Capabilities::default()
+    }
+
+    fn request_cost(&self, op: Operation) -> u32 {
+        match op {
+            // search.list
+            Operation::Search => 100,
+            // videos.list; channel uploads come from playlistItems.list
+            // rather than a channel-filtered search.list
+            Operation::GetById | Operation::UserPosts | Operation::Trending => 1,
+        }
+    }
+
     async fn get_rate_limit_status(&self) -> Result<RateLimitInfo, ConnectorError> {
         // TODO: Implement YouTube rate limit tracking
         Ok(RateLimitInfo {
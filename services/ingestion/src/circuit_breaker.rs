@@ -0,0 +1,258 @@
+/*
+ * SentinelBERT Connector Circuit Breaker
+ *
+ * This module stops the ingestion service from hammering a platform that is
+ * failing. After enough consecutive failures the circuit opens and requests
+ * are rejected locally until the reset timeout elapses.
+ *
+ * States:
+ * - Closed: requests pass through; consecutive failures are counted
+ * - Open: requests fail fast without calling the platform
+ * - HalfOpen: the reset timeout elapsed; a cheap probe decides whether the
+ *   platform has recovered before any real query is sent
+ *
+ * The probe is configurable per connector so recovery checks don't spend
+ * quota on a full user query.
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use serde::Deserialize;                 // Config deserialization
+use std::future::Future;                // Wrapped connector operations
+use std::sync::Mutex;                   // Breaker state (never held across await)
+use std::time::{Duration, Instant};     // Reset timeout tracking
+use tracing::{info, warn};              // Structured logging
+
+use crate::api_connectors::{ApiConnector, ConnectorError, SearchParams};
+
+/// How a half-open circuit tests whether the platform has recovered
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProbeStrategy {
+    /// Call validate_credentials (one cheap authenticated request)
+    #[default]
+    ValidateCredentials,
+
+    /// Search for `query` with a single result
+    MinimalSearch { query: String },
+}
+
+/// Circuit breaker settings for one connector
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit
+    pub failure_threshold: u32,
+
+    /// Seconds to stay open before probing
+    pub reset_timeout_secs: u64,
+
+    /// Probe used in the half-open state
+    pub probe: ProbeStrategy,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            reset_timeout_secs: 60,
+            probe: ProbeStrategy::default(),
+        }
+    }
+}
+
+/// Current circuit state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+struct BreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+/// Per-connector circuit breaker
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+            }),
+        }
+    }
+
+    /// Current state, moving Open to HalfOpen once the reset timeout elapsed
+    pub fn state(&self) -> CircuitState {
+        let mut inner = self.inner.lock().unwrap();
+        let reset_timeout = Duration::from_secs(self.config.reset_timeout_secs);
+
+        if inner.state == CircuitState::Open
+            && inner.opened_at.is_none_or(|at| at.elapsed() >= reset_timeout)
+        {
+            inner.state = CircuitState::HalfOpen;
+        }
+
+        inner.state
+    }
+
+    /// Run `operation` against `connector` through the breaker
+    ///
+    /// An open circuit fails fast. A half-open circuit runs the configured
+    /// probe first and only runs `operation` if the probe succeeds.
+    pub async fn call<T, F, Fut>(&self, connector: &dyn ApiConnector, operation: F) -> Result<T, ConnectorError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, ConnectorError>>,
+    {
+        match self.state() {
+            CircuitState::Open => {
+                return Err(ConnectorError::Generic {
+                    message: format!("Circuit open for {}", connector.platform_name()),
+                });
+            },
+            CircuitState::HalfOpen => {
+                if let Err(e) = self.probe(connector).await {
+                    warn!("Circuit probe failed for {}: {}", connector.platform_name(), e);
+                    self.trip();
+                    return Err(e);
+                }
+
+                info!("Circuit probe succeeded for {}, closing circuit", connector.platform_name());
+                self.record_success();
+            },
+            CircuitState::Closed => {}
+        }
+
+        let result = operation().await;
+        match &result {
+            Ok(_) => self.record_success(),
+            Err(_) => self.record_failure(connector.platform_name()),
+        }
+        result
+    }
+
+    /// Run the configured cheap recovery check
+    async fn probe(&self, connector: &dyn ApiConnector) -> Result<(), ConnectorError> {
+        match &self.config.probe {
+            ProbeStrategy::ValidateCredentials => match connector.validate_credentials().await? {
+                true => Ok(()),
+                false => Err(ConnectorError::InvalidCredentials),
+            },
+            ProbeStrategy::MinimalSearch { query } => {
+                let params = SearchParams {
+                    query: query.clone(),
+                    max_results: Some(1),
+                    ..Default::default()
+                };
+                connector.search_posts(&params).await.map(|_| ())
+            },
+        }
+    }
+
+    fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+    }
+
+    fn record_failure(&self, platform: &str) {
+        let tripped = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.consecutive_failures += 1;
+            inner.consecutive_failures >= self.config.failure_threshold.max(1)
+        };
+
+        if tripped {
+            warn!("Opening circuit for {} after repeated failures", platform);
+            self.trip();
+        }
+    }
+
+    fn trip(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.state = CircuitState::Open;
+        inner.opened_at = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils::MockConnector;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn quick_reset_breaker(probe: ProbeStrategy) -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            reset_timeout_secs: 0,
+            probe,
+        })
+    }
+
+    async fn failing_query() -> Result<(), ConnectorError> {
+        Err(ConnectorError::ApiError { code: 503, message: "unavailable".to_string() })
+    }
+
+    #[tokio::test]
+    async fn test_half_open_uses_probe_not_user_query() {
+        let connector = MockConnector::new("counting");
+        let breaker = quick_reset_breaker(ProbeStrategy::ValidateCredentials);
+
+        for _ in 0..2 {
+            assert!(breaker.call(&connector, failing_query).await.is_err());
+        }
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // The probe runs before the user operation; the failed query isn't replayed
+        let user_queries = AtomicU32::new(0);
+        let result = breaker.call(&connector, || async {
+            user_queries.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }).await;
+
+        assert!(result.is_ok());
+        assert_eq!(connector.validations(), 1);
+        assert_eq!(connector.searches().len(), 0);
+        assert_eq!(user_queries.load(Ordering::SeqCst), 1);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_minimal_search_probe_and_open_circuit_fails_fast() {
+        let connector = MockConnector::new("counting");
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            reset_timeout_secs: 3600,
+            probe: ProbeStrategy::MinimalSearch { query: "ping".to_string() },
+        });
+
+        assert!(breaker.call(&connector, failing_query).await.is_err());
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // Open circuit rejects without running anything
+        assert!(breaker.call(&connector, failing_query).await.is_err());
+        assert_eq!(connector.searches().len(), 0);
+
+        let probing = quick_reset_breaker(ProbeStrategy::MinimalSearch { query: "ping".to_string() });
+        for _ in 0..2 {
+            let _ = probing.call(&connector, failing_query).await;
+        }
+        assert!(probing.call(&connector, || async { Ok(()) }).await.is_ok());
+        assert_eq!(connector.searches().len(), 1);
+        assert_eq!(connector.validations(), 0);
+    }
+}
@@ -0,0 +1,353 @@
+/*
+ * SentinelBERT Content Deduplication
+ *
+ * This module suppresses posts whose content has already been seen, so the
+ * same text collected through several queries (or several runs) is only
+ * emitted once.
+ *
+ * Dedup Scopes:
+ * - PerRun: only posts seen earlier in the current run are suppressed
+ * - Persistent: posts already present in the PostStore are suppressed too,
+ *   using the store's content hash index
+ *
+ * Streaming Dedup:
+ * The StreamingDeduplicator checks posts one at a time as the writer task
+ * receives them, against the SimHashes of recently seen content. It keeps
+ * at most `capacity` entries, each for at most `ttl_secs`, so memory stays
+ * bounded however long collection runs. Near-identical text (a few words
+ * changed) is caught too, within `max_distance` differing bits.
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use anyhow::Result;                     // Simplified error handling with context
+use chrono::{DateTime, Utc};            // Streaming window expiry
+use serde::{Deserialize, Serialize};    // Config (de)serialization
+use sha2::{Digest, Sha256};             // Stable content hashing
+use std::collections::{HashSet, VecDeque}; // Hashes seen during this run, streaming window
+
+use crate::api_connectors::SocialPost;
+use crate::storage::PostStore;
+
+/// How far back deduplication looks when deciding whether a post is new
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupScope {
+    /// Only suppress duplicates within the current run
+    #[default]
+    PerRun,
+
+    /// Also suppress posts already persisted in the store
+    Persistent,
+}
+
+/// Compute the deduplication hash for a post's content
+///
+/// Content is lowercased and whitespace-collapsed before hashing so that
+/// trivial formatting differences don't defeat deduplication.
+pub fn content_hash(post: &SocialPost) -> String {
+    let normalized = post.content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+
+    let digest = Sha256::digest(normalized.as_bytes());
+    hex::encode(digest)
+}
+
+/// Whether a post has any words to deduplicate on
+fn has_text(post: &SocialPost) -> bool {
+    post.content.split_whitespace().next().is_some()
+}
+
+/// Content deduplicator for a single ingestion run
+pub struct Deduplicator {
+    /// Configured dedup scope
+    scope: DedupScope,
+
+    /// Content hashes emitted during this run
+    seen: HashSet<String>,
+}
+
+impl Deduplicator {
+    pub fn new(scope: DedupScope) -> Self {
+        Self {
+            scope,
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Drop posts that are duplicates under the configured scope
+    ///
+    /// Under `Persistent` scope the store's content hash index is consulted
+    /// for every post that hasn't already been seen in this run. Posts with
+    /// no text (image- or link-only) all hash alike, so they are never
+    /// treated as duplicates.
+    pub async fn filter(&mut self, posts: Vec<SocialPost>, store: &dyn PostStore) -> Result<Vec<SocialPost>> {
+        let mut unique = Vec::with_capacity(posts.len());
+
+        for post in posts {
+            if !has_text(&post) {
+                unique.push(post);
+                continue;
+            }
+
+            let hash = content_hash(&post);
+
+            if self.seen.contains(&hash) {
+                continue;
+            }
+
+            if self.scope == DedupScope::Persistent && store.contains_content_hash(&hash).await? {
+                self.seen.insert(hash);
+                continue;
+            }
+
+            self.seen.insert(hash);
+            unique.push(post);
+        }
+
+        Ok(unique)
+    }
+}
+
+/// 64-bit SimHash of a post's lowercased words
+///
+/// Texts differing in a few words get hashes differing in a few bits.
+pub fn simhash(post: &SocialPost) -> u64 {
+    let mut weights = [0i32; 64];
+
+    for word in post.content.split_whitespace() {
+        let hash = fnv1a(word.to_lowercase().as_bytes());
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            *weight += if hash & (1 << bit) != 0 { 1 } else { -1 };
+        }
+    }
+
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight > 0)
+        .fold(0, |hash, (bit, _)| hash | (1 << bit))
+}
+
+/// FNV-1a, a fast hash that is stable across builds
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Window of recent content checked by the StreamingDeduplicator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StreamingDedupConfig {
+    /// Most recent hashes kept; the oldest is evicted first
+    pub capacity: usize,
+
+    /// Seconds a hash is kept (no expiry when unset)
+    pub ttl_secs: Option<u64>,
+
+    /// Differing bits at or below which two posts count as duplicates
+    pub max_distance: u32,
+}
+
+impl Default for StreamingDedupConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            ttl_secs: Some(3600),
+            max_distance: 3,
+        }
+    }
+}
+
+/// Online deduplicator over a bounded window of recent content
+pub struct StreamingDeduplicator {
+    config: StreamingDedupConfig,
+
+    /// SimHashes of kept posts with when they were seen, oldest first
+    recent: VecDeque<(DateTime<Utc>, u64)>,
+}
+
+impl StreamingDeduplicator {
+    pub fn new(config: StreamingDedupConfig) -> Self {
+        Self {
+            recent: VecDeque::with_capacity(config.capacity.min(1024)),
+            config,
+        }
+    }
+
+    /// Whether a post repeats content seen within the window
+    ///
+    /// A post that isn't a duplicate is added to the window.
+    pub fn is_duplicate(&mut self, post: &SocialPost) -> bool {
+        self.is_duplicate_at(post, Utc::now())
+    }
+
+    /// `is_duplicate` as of `now`
+    ///
+    /// Posts with no text are never duplicates and are not recorded: their
+    /// SimHash is 0, which would match every other empty post.
+    pub fn is_duplicate_at(&mut self, post: &SocialPost, now: DateTime<Utc>) -> bool {
+        // A TTL too large to subtract from `now` never expires anything
+        let cutoff = self.config.ttl_secs
+            .and_then(|ttl| chrono::Duration::try_seconds(i64::try_from(ttl).ok()?))
+            .and_then(|ttl| now.checked_sub_signed(ttl));
+        if let Some(cutoff) = cutoff {
+            while self.recent.front().is_some_and(|(seen, _)| *seen <= cutoff) {
+                self.recent.pop_front();
+            }
+        }
+
+        if !has_text(post) {
+            return false;
+        }
+
+        let hash = simhash(post);
+        if self.recent.iter().any(|(_, seen)| (seen ^ hash).count_ones() <= self.config.max_distance) {
+            return true;
+        }
+
+        if self.config.capacity == 0 {
+            return false;
+        }
+        while self.recent.len() >= self.config.capacity {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((now, hash));
+        false
+    }
+
+    /// Hashes currently in the window
+    pub fn len(&self) -> usize {
+        self.recent.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils::post;
+    use crate::api_connectors::MediaAttachment;
+    use crate::storage::InMemoryPostStore;
+
+    #[test]
+    fn test_content_hash_ignores_case_and_whitespace() {
+        let a = post("1", "Breaking  news\ttoday");
+        let b = post("2", "breaking news TODAY");
+        assert_eq!(content_hash(&a), content_hash(&b));
+    }
+
+    #[tokio::test]
+    async fn test_persistent_scope_suppresses_stored_posts() {
+        let store = InMemoryPostStore::new();
+        store.save_posts(&[post("1", "already collected")]).await.unwrap();
+
+        let incoming = vec![post("2", "already collected"), post("3", "brand new")];
+
+        let mut per_run = Deduplicator::new(DedupScope::PerRun);
+        let emitted = per_run.filter(incoming.clone(), &store).await.unwrap();
+        assert_eq!(emitted.len(), 2);
+
+        let mut persistent = Deduplicator::new(DedupScope::Persistent);
+        let emitted = persistent.filter(incoming, &store).await.unwrap();
+        assert_eq!(emitted.len(), 1);
+        assert_eq!(emitted[0].id, "3");
+    }
+
+    #[tokio::test]
+    async fn test_duplicates_within_run_are_suppressed() {
+        let store = InMemoryPostStore::new();
+        let mut dedup = Deduplicator::new(DedupScope::PerRun);
+
+        let first = dedup.filter(vec![post("1", "same text")], &store).await.unwrap();
+        let second = dedup.filter(vec![post("2", "same text")], &store).await.unwrap();
+
+        assert_eq!(first.len(), 1);
+        assert!(second.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_posts_without_text_are_not_duplicates() {
+        let store = InMemoryPostStore::new();
+        let image = |id: &str, url: &str| {
+            let mut post = post(id, " ");
+            post.media.push(MediaAttachment {
+                media_type: "image".to_string(),
+                url: url.to_string(),
+                alt_text: None,
+                dimensions: None,
+                file_size: None,
+            });
+            post
+        };
+
+        let mut dedup = Deduplicator::new(DedupScope::Persistent);
+        let emitted = dedup
+            .filter(vec![image("1", "https://i.example/a.jpg"), image("2", "https://i.example/b.jpg")], &store)
+            .await
+            .unwrap();
+
+        let ids: Vec<&str> = emitted.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "2"]);
+    }
+
+    #[test]
+    fn test_streaming_dedup_catches_repeats_within_ttl_only() {
+        let mut dedup = StreamingDeduplicator::new(StreamingDedupConfig {
+            capacity: 100,
+            ttl_secs: Some(600),
+            max_distance: 3,
+        });
+        let start = Utc::now();
+        let text = "Bridge closed on the eastern highway after heavy overnight flooding, avoid the area";
+
+        assert!(!dedup.is_duplicate_at(&post("1", text), start));
+        assert!(dedup.is_duplicate_at(&post("2", &text.to_uppercase()), start + chrono::Duration::minutes(5)));
+        assert!(!dedup.is_duplicate_at(&post("3", "Relief camp opened at the central school"), start + chrono::Duration::minutes(6)));
+
+        // Once the original has expired the same text is new again
+        assert!(!dedup.is_duplicate_at(&post("4", text), start + chrono::Duration::minutes(11)));
+        assert_eq!(dedup.len(), 2);
+    }
+
+    #[test]
+    fn test_streaming_dedup_evicts_oldest_beyond_capacity() {
+        let mut dedup = StreamingDeduplicator::new(StreamingDedupConfig {
+            capacity: 2,
+            ttl_secs: None,
+            max_distance: 0,
+        });
+        let now = Utc::now();
+
+        assert!(!dedup.is_duplicate_at(&post("1", "water level rising at the dam"), now));
+        assert!(!dedup.is_duplicate_at(&post("2", "power cut across the north district"), now));
+        assert!(dedup.is_duplicate_at(&post("3", "water level rising at the dam"), now));
+
+        // A third distinct post pushes the first out of the window
+        assert!(!dedup.is_duplicate_at(&post("4", "trains suspended on the harbour line"), now));
+        assert!(!dedup.is_duplicate_at(&post("5", "water level rising at the dam"), now));
+        assert_eq!(dedup.len(), 2);
+    }
+
+    #[test]
+    fn test_streaming_dedup_ignores_empty_posts_and_huge_ttl() {
+        let mut dedup = StreamingDeduplicator::new(StreamingDedupConfig {
+            capacity: 100,
+            ttl_secs: Some(u64::MAX),
+            max_distance: 3,
+        });
+        let now = Utc::now();
+
+        assert!(!dedup.is_duplicate_at(&post("1", ""), now));
+        assert!(!dedup.is_duplicate_at(&post("2", "  "), now));
+        assert_eq!(dedup.len(), 0);
+
+        // Posts with text are still tracked, and never expire
+        assert!(!dedup.is_duplicate_at(&post("3", "evacuation ordered"), now));
+        assert!(dedup.is_duplicate_at(&post("4", "evacuation ordered"), now + chrono::Duration::days(365)));
+    }
+}
@@ -0,0 +1,77 @@
+/*
+ * SentinelBERT Post Enrichment
+ *
+ * This module defines the hook deployments use to attach their own
+ * computed fields (sentiment, toxicity, geocoding, ...) to posts without
+ * forking the crate. The IngestionService runs its enrichers in order on
+ * every deduplicated batch, before scoring and before any sink sees it.
+ *
+ * Failures:
+ * An enricher error is logged and the post moves on to the next enricher,
+ * unless the service runs in strict mode, where it fails the batch.
+ *
+ * Built-in Enrichers (by config name):
+ * - `content_stats`: metadata["content_length"] (characters) and
+ *   metadata["word_count"]
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use anyhow::{bail, Result};             // Simplified error handling
+use async_trait::async_trait;           // Async trait methods
+
+use crate::api_connectors::SocialPost;
+
+/// Custom processing applied to each post before storage
+#[async_trait]
+pub trait Enricher: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &str;
+
+    /// Add or adjust fields on one post
+    async fn enrich(&self, post: &mut SocialPost) -> Result<()>;
+}
+
+/// Records content length and word count in metadata
+pub struct ContentStatsEnricher;
+
+#[async_trait]
+impl Enricher for ContentStatsEnricher {
+    fn name(&self) -> &str {
+        "content_stats"
+    }
+
+    async fn enrich(&self, post: &mut SocialPost) -> Result<()> {
+        let content_length = post.content.chars().count();
+        let word_count = post.content.split_whitespace().count();
+
+        post.metadata.insert("content_length".to_string(), serde_json::json!(content_length));
+        post.metadata.insert("word_count".to_string(), serde_json::json!(word_count));
+        Ok(())
+    }
+}
+
+/// Built-in enricher for a config name
+pub fn built_in(name: &str) -> Result<Box<dyn Enricher>> {
+    match name {
+        "content_stats" => Ok(Box::new(ContentStatsEnricher)),
+        other => bail!("Unknown enricher '{}' (available: content_stats)", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils::post;
+
+    #[tokio::test]
+    async fn test_content_stats_counts_characters_and_words() {
+        let mut post = post("1", "बाढ़ in  Mumbai today");
+        built_in("content_stats").unwrap().enrich(&mut post).await.unwrap();
+
+        assert_eq!(post.metadata["content_length"], serde_json::json!(21));
+        assert_eq!(post.metadata["word_count"], serde_json::json!(4));
+        assert!(built_in("sentiment").is_err());
+    }
+}
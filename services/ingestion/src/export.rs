@@ -0,0 +1,459 @@
+/*
+ * SentinelBERT Post Export
+ *
+ * This module writes collected posts to files for offline analysis outside
+ * the SentinelBERT pipeline (pandas, Polars, DuckDB, ...) and for reviewers
+ * working in spreadsheets (CSV).
+ *
+ * Parquet Schema:
+ * - id, platform, content, author_id_hash, author_username: string
+ * - language: nullable string
+ * - created_at: timestamp (microseconds, UTC)
+ * - likes, shares, comments: uint64; views: nullable uint64
+ * - engagement_rate, latitude, longitude: nullable float64
+ * - hashtags, mentions, urls: list<string>
+ * - sensitive_content: bool
+ * - metadata: platform-specific metadata serialized as a JSON string
+ *
+ * CSV Columns:
+ * id, platform, author_username, created_at (RFC 3339), content (newlines
+ * replaced with spaces), likes, shares, comments, hashtags (joined with ';')
+ *
+ * JSON Lines:
+ * One serialized SocialPost per line. `write_jsonl` consumes an iterator,
+ * so a run can be streamed to disk without holding it in memory. JSON
+ * escapes newlines inside strings, so every line parses on its own.
+ *
+ * Redaction:
+ * The Parquet and CSV writers take a RedactionProfile applied to copies of
+ * the posts (JSON Lines callers apply it per post as they stream), so
+ * the same in-memory posts can be exported at several sensitivity levels.
+ * Presets: none (keep everything), partner (no raw metadata or media,
+ * location coarsened to 25 km) and public (also no location, URLs,
+ * mentions or usernames).
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use anyhow::{Context, Result};      // Simplified error handling with context
+use serde::{Deserialize, Serialize}; // Redaction profiles in config
+use std::borrow::Cow;               // Unredacted exports borrow the posts
+use chrono::SecondsFormat;          // RFC 3339 timestamps in CSV
+use arrow::array::{
+    ArrayRef, BooleanArray, Float64Array, ListBuilder, StringArray, StringBuilder,
+    TimestampMicrosecondArray, UInt64Array,
+};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;    // Arrow -> Parquet encoding
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::fs::File;
+use std::io::{BufWriter, Write};    // Buffered JSON Lines output
+use std::path::Path;
+use std::sync::Arc;                 // Arrow schema and array handles
+
+use crate::api_connectors::utils::generalize_location;
+use crate::api_connectors::SocialPost;
+
+/// Lines written between flushes of a JSON Lines export
+const JSONL_FLUSH_EVERY: u64 = 1000;
+
+/// Username written in place of a redacted author username
+const REDACTED_USERNAME: &str = "[redacted]";
+
+/// Fields stripped or coarsened before export
+///
+/// The default keeps everything.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionProfile {
+    /// Remove the location entirely
+    pub drop_location: bool,
+
+    /// Snap the location to a grid of this many km (ignored with drop_location)
+    pub coarsen_location_km: Option<f64>,
+
+    /// Empty the platform-specific metadata
+    pub drop_metadata: bool,
+
+    /// Remove media attachments and their URLs
+    pub drop_media: bool,
+
+    /// Remove URLs extracted from the content
+    pub drop_urls: bool,
+
+    /// Remove (hashed) mentions
+    pub drop_mentions: bool,
+
+    /// Replace the author username; the id hash is kept for grouping
+    pub drop_author_username: bool,
+}
+
+impl RedactionProfile {
+    /// For trusted partners: no raw metadata or media, location to 25 km
+    pub fn partner() -> Self {
+        Self {
+            coarsen_location_km: Some(25.0),
+            drop_metadata: true,
+            drop_media: true,
+            ..Self::default()
+        }
+    }
+
+    /// For publication: partner redaction plus no location, URLs, mentions or usernames
+    pub fn public() -> Self {
+        Self {
+            drop_location: true,
+            drop_urls: true,
+            drop_mentions: true,
+            drop_author_username: true,
+            ..Self::partner()
+        }
+    }
+
+    /// Strip or coarsen the selected fields of a post
+    pub fn apply(&self, post: &mut SocialPost) {
+        if self.drop_location {
+            post.location = None;
+        } else if let Some(km) = self.coarsen_location_km {
+            if let Some(location) = &post.location {
+                post.location = generalize_location(location, km);
+                post.privacy_flags.location_generalized = true;
+            }
+        }
+        if self.drop_metadata {
+            post.metadata.clear();
+        }
+        if self.drop_media {
+            post.media.clear();
+        }
+        if self.drop_urls {
+            post.urls.clear();
+        }
+        if self.drop_mentions {
+            post.mentions.clear();
+        }
+        if self.drop_author_username {
+            post.author.username = REDACTED_USERNAME.to_string();
+        }
+    }
+
+    /// Posts with this profile applied, borrowed when nothing is redacted
+    fn redact<'a>(&self, posts: &'a [SocialPost]) -> Cow<'a, [SocialPost]> {
+        if *self == Self::default() {
+            return Cow::Borrowed(posts);
+        }
+
+        let mut posts = posts.to_vec();
+        for post in &mut posts {
+            self.apply(post);
+        }
+        Cow::Owned(posts)
+    }
+}
+
+/// Columnar schema used for Parquet exports
+pub fn parquet_schema() -> Schema {
+    let string_list = || DataType::List(Arc::new(Field::new("item", DataType::Utf8, true)));
+
+    Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("platform", DataType::Utf8, false),
+        Field::new("content", DataType::Utf8, false),
+        Field::new("language", DataType::Utf8, true),
+        Field::new("author_id_hash", DataType::Utf8, false),
+        Field::new("author_username", DataType::Utf8, false),
+        Field::new("created_at", DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())), false),
+        Field::new("likes", DataType::UInt64, false),
+        Field::new("shares", DataType::UInt64, false),
+        Field::new("comments", DataType::UInt64, false),
+        Field::new("views", DataType::UInt64, true),
+        Field::new("engagement_rate", DataType::Float64, true),
+        Field::new("latitude", DataType::Float64, true),
+        Field::new("longitude", DataType::Float64, true),
+        Field::new("hashtags", string_list(), false),
+        Field::new("mentions", string_list(), false),
+        Field::new("urls", string_list(), false),
+        Field::new("sensitive_content", DataType::Boolean, false),
+        Field::new("metadata", DataType::Utf8, false),
+    ])
+}
+
+/// Write posts to a Snappy-compressed Parquet file, replacing any existing file
+pub fn write_parquet(posts: &[SocialPost], path: &Path, redaction: &RedactionProfile) -> Result<()> {
+    let batch = to_record_batch(&redaction.redact(posts))?;
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(properties))?;
+    writer.write(&batch)?;
+    writer.close()?;
+
+    Ok(())
+}
+
+/// Header row for CSV exports
+pub const CSV_HEADER: [&str; 9] = [
+    "id", "platform", "author_username", "created_at", "content",
+    "likes", "shares", "comments", "hashtags",
+];
+
+/// Write posts to a CSV file with a header row, replacing any existing file
+///
+/// Fields containing commas or quotes are quoted; newlines in content are
+/// replaced with spaces so every post stays on one row.
+pub fn write_csv(posts: &[SocialPost], path: &Path, redaction: &RedactionProfile) -> Result<()> {
+    let mut writer = csv::Writer::from_path(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+
+    writer.write_record(CSV_HEADER)?;
+    for post in redaction.redact(posts).iter() {
+        let content = post.content.replace("\r\n", " ").replace(['\r', '\n'], " ");
+        writer.write_record([
+            post.id.as_str(),
+            post.platform.as_str(),
+            post.author.username.as_str(),
+            &post.created_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            &content,
+            &post.metrics.likes.to_string(),
+            &post.metrics.shares.to_string(),
+            &post.metrics.comments.to_string(),
+            &post.hashtags.join(";"),
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Flatten posts into a single record batch
+/// Write posts as JSON Lines, one post per line, returning the number written
+///
+/// Output is flushed every thousand posts, so a streaming reader (or a
+/// crash) sees whole lines at regular intervals.
+pub fn write_jsonl<W: Write>(posts: impl Iterator<Item = SocialPost>, writer: W) -> Result<u64> {
+    let mut writer = BufWriter::new(writer);
+    let mut count = 0;
+
+    for post in posts {
+        serde_json::to_writer(&mut writer, &post)
+            .with_context(|| format!("Failed to serialize post {}", post.id))?;
+        writer.write_all(b"\n")?;
+        count += 1;
+
+        if count % JSONL_FLUSH_EVERY == 0 {
+            writer.flush()?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(count)
+}
+
+fn to_record_batch(posts: &[SocialPost]) -> Result<RecordBatch> {
+    let strings = |value: fn(&SocialPost) -> &str| -> ArrayRef {
+        Arc::new(StringArray::from_iter_values(posts.iter().map(value)))
+    };
+    let counts = |value: fn(&SocialPost) -> u64| -> ArrayRef {
+        Arc::new(UInt64Array::from_iter_values(posts.iter().map(value)))
+    };
+    let lists = |value: fn(&SocialPost) -> &Vec<String>| -> ArrayRef {
+        let mut builder = ListBuilder::new(StringBuilder::new());
+        for post in posts {
+            for item in value(post) {
+                builder.values().append_value(item);
+            }
+            builder.append(true);
+        }
+        Arc::new(builder.finish())
+    };
+
+    let metadata = posts
+        .iter()
+        .map(|post| serde_json::to_string(&post.metadata))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let columns: Vec<ArrayRef> = vec![
+        strings(|p| &p.id),
+        strings(|p| &p.platform),
+        strings(|p| &p.content),
+        Arc::new(StringArray::from_iter(posts.iter().map(|p| p.language.as_deref()))),
+        strings(|p| &p.author.id_hash),
+        strings(|p| &p.author.username),
+        Arc::new(
+            TimestampMicrosecondArray::from_iter_values(posts.iter().map(|p| p.created_at.timestamp_micros()))
+                .with_timezone("UTC"),
+        ),
+        counts(|p| p.metrics.likes),
+        counts(|p| p.metrics.shares),
+        counts(|p| p.metrics.comments),
+        Arc::new(UInt64Array::from_iter(posts.iter().map(|p| p.metrics.views))),
+        Arc::new(Float64Array::from_iter(posts.iter().map(|p| p.metrics.engagement_rate))),
+        Arc::new(Float64Array::from_iter(posts.iter().map(|p| p.location.as_ref().map(|l| l.latitude)))),
+        Arc::new(Float64Array::from_iter(posts.iter().map(|p| p.location.as_ref().map(|l| l.longitude)))),
+        lists(|p| &p.hashtags),
+        lists(|p| &p.mentions),
+        lists(|p| &p.urls),
+        Arc::new(BooleanArray::from_iter(posts.iter().map(|p| Some(p.privacy_flags.sensitive_content)))),
+        Arc::new(StringArray::from(metadata)),
+    ];
+
+    Ok(RecordBatch::try_new(Arc::new(parquet_schema()), columns)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils::post;
+    use crate::api_connectors::{GeoLocation, MediaAttachment};
+    use arrow::array::{Array, AsArray};
+    use arrow::datatypes::{TimestampMicrosecondType, UInt64Type};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    #[test]
+    fn test_parquet_round_trip() {
+        let mut first = post("1", "Flood warning #rain");
+        first.hashtags = vec!["rain".to_string()];
+        first.language = Some("en".to_string());
+        first.metrics.likes = 12;
+        first.metrics.views = Some(340);
+        first.metadata.insert("subreddit".to_string(), serde_json::json!("news"));
+        let second = post("2", "No tags here");
+
+        let path = std::env::temp_dir().join(format!("sentinel-export-{}.parquet", uuid::Uuid::new_v4()));
+        write_parquet(&[first.clone(), second], &path, &RedactionProfile::default()).unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<RecordBatch> = reader.map(Result::unwrap).collect();
+        std::fs::remove_file(&path).unwrap();
+
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.schema().as_ref(), &parquet_schema());
+
+        let column = |name: &str| batch.column_by_name(name).unwrap();
+        assert_eq!(column("content").as_string::<i32>().value(0), "Flood warning #rain");
+        assert_eq!(column("language").as_string::<i32>().value(0), "en");
+        assert!(column("language").is_null(1));
+        assert_eq!(column("likes").as_primitive::<UInt64Type>().value(0), 12);
+        assert_eq!(column("views").as_primitive::<UInt64Type>().value(0), 340);
+        assert_eq!(
+            column("created_at").as_primitive::<TimestampMicrosecondType>().value(0),
+            first.created_at.timestamp_micros()
+        );
+
+        let hashtags = column("hashtags").as_list::<i32>();
+        assert_eq!(hashtags.value(0).as_string::<i32>().value(0), "rain");
+        assert_eq!(hashtags.value(1).len(), 0);
+
+        let metadata: serde_json::Value = serde_json::from_str(column("metadata").as_string::<i32>().value(0)).unwrap();
+        assert_eq!(metadata, serde_json::json!({ "subreddit": "news" }));
+    }
+
+    fn sensitive_post() -> SocialPost {
+        let mut post = post("1", "Shelter at https://example.org/map cc @volunteer");
+        post.location = Some(GeoLocation { latitude: 19.0760, longitude: 72.8777, radius_km: 0.1, name: None });
+        post.media.push(MediaAttachment {
+            media_type: "image".to_string(),
+            url: "https://cdn.example.org/photo.jpg".to_string(),
+            alt_text: None,
+            dimensions: None,
+            file_size: None,
+        });
+        post.urls = vec!["https://example.org/map".to_string()];
+        post.mentions = vec!["hashed-volunteer".to_string()];
+        post.metadata.insert("subreddit".to_string(), serde_json::json!("mumbai"));
+        post
+    }
+
+    #[test]
+    fn test_public_profile_removes_location_and_media() {
+        let original = sensitive_post();
+        let mut post = original.clone();
+        RedactionProfile::public().apply(&mut post);
+
+        assert!(post.location.is_none());
+        assert!(post.media.is_empty() && post.urls.is_empty() && post.mentions.is_empty());
+        assert!(post.metadata.is_empty());
+        assert_eq!(post.author.username, REDACTED_USERNAME);
+        assert_eq!((&post.content, &post.author.id_hash), (&original.content, &original.author.id_hash));
+
+        // Partner exports keep a coarse location
+        let mut partner = original.clone();
+        RedactionProfile::partner().apply(&mut partner);
+        let location = partner.location.unwrap();
+        assert_ne!(location.latitude, 19.0760);
+        assert!((location.latitude - 19.0760).abs() < 0.25);
+        assert_eq!(partner.urls, original.urls);
+    }
+
+    #[test]
+    fn test_default_profile_keeps_everything() {
+        let original = sensitive_post();
+        let mut post = original.clone();
+        RedactionProfile::default().apply(&mut post);
+
+        assert_eq!(serde_json::to_value(&post).unwrap(), serde_json::to_value(&original).unwrap());
+        assert!(matches!(RedactionProfile::default().redact(std::slice::from_ref(&original)), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_jsonl_lines_parse_independently() {
+        let posts = vec![
+            post("1", "first"),
+            post("2", "multi-line\ncontent with \"quotes\""),
+            post("3", "third"),
+        ];
+
+        let mut output = Vec::new();
+        let count = write_jsonl(posts.clone().into_iter(), &mut output).unwrap();
+        assert_eq!(count, 3);
+
+        let lines: Vec<&str> = std::str::from_utf8(&output).unwrap().lines().collect();
+        assert_eq!(lines.len(), 3);
+        for (line, original) in lines.iter().zip(&posts) {
+            let parsed: SocialPost = serde_json::from_str(line).unwrap();
+            assert_eq!((&parsed.id, &parsed.content), (&original.id, &original.content));
+        }
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let mut first = post("1", "Flood warning, \"stay safe\"\nmore at 11");
+        first.hashtags = vec!["rain".to_string(), "flood".to_string()];
+        first.metrics.likes = 12;
+        first.metrics.shares = 3;
+        let second = post("2", "No tags here");
+
+        let path = std::env::temp_dir().join(format!("sentinel-export-{}.csv", uuid::Uuid::new_v4()));
+        write_csv(&[first.clone(), second], &path, &RedactionProfile::default()).unwrap();
+
+        let mut reader = csv::Reader::from_path(&path).unwrap();
+        let header: Vec<String> = reader.headers().unwrap().iter().map(String::from).collect();
+        let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        let raw = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(header, CSV_HEADER);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(raw.lines().count(), 3);
+        assert!(rows.iter().all(|row| row.len() == CSV_HEADER.len()));
+
+        let row = &rows[0];
+        assert_eq!(&row[0], "1");
+        assert_eq!(&row[3], first.created_at.to_rfc3339_opts(SecondsFormat::Secs, true));
+        assert_eq!(&row[4], "Flood warning, \"stay safe\" more at 11");
+        assert_eq!((&row[5], &row[6], &row[7]), ("12", "3", "0"));
+        assert_eq!(&row[8], "rain;flood");
+        assert_eq!(&rows[1][8], "");
+    }
+}
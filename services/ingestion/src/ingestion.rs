@@ -0,0 +1,2033 @@
+/*
+ * SentinelBERT Ingestion Service
+ *
+ * This module orchestrates data collection: it fans a search out to every
+ * configured API connector, deduplicates the results, and hands the
+ * surviving posts to the PostStore and any registered output sinks.
+ *
+ * Processing Flow:
+ * 1. Search each configured connector (through its circuit breaker)
+ * 2. Drop posts outside the search's bounding box, if any
+ * 3. Record query term match spans (after connectors normalized the text)
+ * 4. Buffer results until a batch is full
+ * 5. Drop duplicate content (per-run or persistent scope)
+ * 6. Run the registered enrichers, in order
+ * 7. Score each post's completeness (metadata `completeness`)
+ * 8. Fan out to every registered sink (store, webhooks, ...)
+ *
+ * Collection passes return each query's posts in its SearchParams
+ * `sort_order` (API order unless set); sorting is stable.
+ *
+ * Scheduled runs can be incremental: with a WatermarkStore set, each
+ * (platform, query) search resumes from the newest post collected last
+ * time and saves the new high-water mark afterwards.
+ *
+ * Each job (collection pass, backfill, scheduled search) runs in a tracing
+ * span carrying a fresh `job_id`; each search adds `platform` and `query`.
+ * Connectors run inside these spans, so their own log lines carry the
+ * job_id without it being passed to them. Collection passes also record
+ * the id in their CollectionReport, and every post a job stores is tagged
+ * with it (see `PostStore::delete_by_job`).
+ *
+ * Each job also runs with its own RetryBudget (see retry_budget.rs), capping
+ * the retries its requests may make in total. `run_job` drives a single
+ * keyword, hashtag, user timeline or trending topics IngestionJob and
+ * marks it Failed when, for example, that budget runs out.
+ *
+ * Concurrency:
+ * Each platform has a semaphore (default 4 permits) that every search must
+ * hold while it runs, bounding simultaneous connections per platform
+ * however many queries, loops and backfills are active. It sits in front
+ * of the connectors' own rate limiting, which still applies.
+ *
+ * Quotas:
+ * A platform with a daily or monthly quota (see quota.rs) is checked
+ * before each request (searches, user timelines, trending topics and post
+ * lookups), which fails with QuotaExhausted once the cap is reached.
+ * Request quotas are debited by the connector's cost for the operation.
+ * Post quotas also lower the search's max_results to what is left and cut
+ * longer results short.
+ *
+ * Every search updates the shared Metrics registry (posts, API calls,
+ * errors, rate limit hits and remaining quota per platform).
+ *
+ * Sampling:
+ * With a SamplingStrategy set, fetched posts are sampled before
+ * deduplication. A FirstN cap also stops further searches and backfill
+ * pages once met.
+ *
+ * Backfill:
+ * `backfill` sweeps a historical date range in fixed windows, oldest
+ * first, paging within each window by moving its end back to the oldest
+ * post of each full page. Windows beyond a connector's search history
+ * (e.g. Twitter recent search) are clamped or skipped with a warning.
+ *
+ * Writer Queue:
+ * While `start` runs, fetched posts go through a bounded queue to a single
+ * writer task, which hands them to the sinks in batches of `batch_size`
+ * or whatever has arrived after the flush interval. When the sinks lag,
+ * the queue fills and fetchers wait on it instead of piling up posts.
+ * With streaming dedup configured, the writer drops posts repeating
+ * recently seen content before they reach a batch (see dedup.rs).
+ *
+ * Shutdown:
+ * `shutdown` stops new searches, waits (up to the drain timeout) for
+ * in-flight searches to finish and the writer to drain the queue, then
+ * flushes the buffer so fetched posts are never discarded, and finally
+ * lets each sink flush its own queue.
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use anyhow::{bail, Context, Result}; // Simplified error handling with context
+use chrono::{DateTime, Utc};        // Backfill date windows
+use futures::future::join_all;      // Run per-platform loops concurrently
+use serde::Serialize;               // Machine-readable collection reports
+use std::collections::{HashMap, HashSet}; // Search params, backfill paging
+use std::sync::Arc;                 // Shared ownership across async tasks
+use std::time::Duration;            // Poll interval and drain timeout
+use tokio::sync::{mpsc, watch, Mutex, Semaphore}; // Writer queue, shutdown signalling, async-aware locks, request slots
+use tracing::{debug, info, info_span, instrument, warn, Instrument}; // Structured logging and job spans
+use uuid::Uuid;                     // Job ids for log correlation
+
+use crate::api_connectors::utils::{ContentFilter, filter_by_bounds, highlight_matches, query_terms, sort_posts};
+use crate::api_connectors::{
+    ApiConnector, CompletenessWeights, ConnectorError, ConnectorRegistry, Operation, SearchParams, SocialPost, SortOrder,
+};
+use crate::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use crate::config::Config;
+use crate::dedup::{DedupScope, Deduplicator, StreamingDedupConfig, StreamingDeduplicator};
+use crate::enrich::{self, Enricher};
+use crate::media::ShortUrlResolver;
+use crate::metrics::Metrics;
+use crate::models::{IngestionJob, JobStatus, JobType, Platform};
+use crate::quota::{DailyQuota, QuotaConfig, QuotaUnit};
+use crate::retry_budget::{RetryBudget, RetryBudgetConfig};
+use crate::sampling::{RateWindow, Sampler, SamplingStrategy};
+use crate::sinks::{KafkaSink, Sink, StoreSink, WebhookSink};
+use crate::validation::{connector_status, ConnectorStatus};
+use crate::storage::{PostStore, PostgresPostStore};
+use crate::watermark::{query_hash, PostgresWatermarkStore, Watermark, WatermarkStore};
+
+/// Most pages fetched within one backfill window
+const BACKFILL_MAX_PAGES: usize = 10;
+
+tokio::task_local! {
+    /// Id of the job running on this task, as in its span
+    static JOB_ID: Uuid;
+}
+
+/// Id of the job running on this task (its span's `job_id`), if any
+pub fn current_job_id() -> Option<Uuid> {
+    JOB_ID.try_with(|job_id| *job_id).ok()
+}
+
+/// Runs a future as one job, alongside tracing's `Instrument`
+trait InJob: std::future::Future + Sized {
+    /// Run inside a "job" span carrying `job_id`, as the current job
+    fn in_job(self, job_id: Uuid) -> impl std::future::Future<Output = Self::Output> {
+        JOB_ID.scope(job_id, self).instrument(info_span!("job", job_id = %job_id))
+    }
+}
+
+impl<F: std::future::Future> InJob for F {}
+
+/// Settings for the continuous collection loop started by `start`
+#[derive(Debug, Clone)]
+pub struct RunSettings {
+    /// Queries searched on every platform each cycle
+    pub queries: Vec<String>,
+
+    /// Maximum results requested per query
+    pub max_results: u32,
+
+    /// Pause between collection cycles
+    pub poll_interval: Duration,
+
+    /// Buffered posts that trigger a flush to the sinks
+    pub batch_size: usize,
+
+    /// Longest the writer holds a partial batch
+    pub flush_interval: Duration,
+
+    /// Posts queued for the writer before fetchers wait
+    pub queue_capacity: usize,
+
+    /// How long shutdown waits for in-flight searches
+    pub drain_timeout: Duration,
+
+    /// Keep report entries for platforms that were searched but found nothing
+    pub report_zero_results: bool,
+
+    /// Sampling applied to each job's fetched posts
+    pub sampling: SamplingStrategy,
+
+    /// Order of each query's merged results
+    pub sort_order: SortOrder,
+
+    /// Field weights for each post's `completeness` metadata score
+    pub completeness_weights: CompletenessWeights,
+
+    /// Fail the batch when an enricher fails instead of skipping it
+    pub strict_enrichment: bool,
+
+    /// Retry limits applied to each job
+    pub retry_budget: RetryBudgetConfig,
+}
+
+impl Default for RunSettings {
+    fn default() -> Self {
+        Self {
+            queries: Vec::new(),
+            max_results: 100,
+            poll_interval: Duration::from_secs(300),
+            batch_size: 1000,
+            flush_interval: Duration::from_secs(5),
+            queue_capacity: 10_000,
+            drain_timeout: Duration::from_secs(30),
+            report_zero_results: true,
+            sampling: SamplingStrategy::None,
+            sort_order: SortOrder::Relevance,
+            completeness_weights: CompletenessWeights::default(),
+            strict_enrichment: false,
+            retry_budget: RetryBudgetConfig::default(),
+        }
+    }
+}
+
+/// Outcome of searching one platform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlatformStatus {
+    /// The search completed (possibly with zero results)
+    Ok,
+
+    /// The search failed or the circuit was open
+    Error,
+
+    /// Not searched because the connector isn't configured, can't search,
+    /// or a FirstN sample was already full
+    Skipped,
+}
+
+/// Report entry for one platform
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformReport {
+    pub platform: String,
+    pub status: PlatformStatus,
+
+    /// Posts returned by the platform, before deduplication
+    pub count: usize,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// What one query collected from each platform
+///
+/// Every connector gets an entry, so "searched, found nothing" (Ok with a
+/// count of 0) is distinguishable from a skipped or failed platform.
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionReport {
+    /// Id of the job that collected it (the `job_id` log field)
+    pub job_id: Uuid,
+
+    pub query: String,
+    pub platforms: Vec<PlatformReport>,
+
+    /// Posts handed to the sinks after deduplication
+    pub stored: usize,
+}
+
+impl CollectionReport {
+    /// Report entry for a platform, if present
+    pub fn platform(&self, platform: &str) -> Option<&PlatformReport> {
+        self.platforms.iter().find(|entry| entry.platform == platform)
+    }
+}
+
+/**
+ * IngestionService - Coordinates connectors, storage and sinks
+ */
+pub struct IngestionService {
+    /// Connectors searched on each run
+    connectors: Vec<Arc<dyn ApiConnector>>,
+
+    /// Circuit breaker for each connector, by index
+    breakers: Vec<CircuitBreaker>,
+
+    /// Primary post storage, consulted for persistent deduplication
+    store: Arc<dyn PostStore>,
+
+    /// Destinations receiving every deduplicated batch, in order
+    sinks: Vec<Arc<dyn Sink>>,
+
+    /// Custom processing run on every deduplicated batch, in order
+    enrichers: Vec<Box<dyn Enricher>>,
+
+    /// Content deduplication state for this service's lifetime
+    deduplicator: Mutex<Deduplicator>,
+
+    /// Near-duplicate window applied by the writer task, if configured
+    streaming_dedup: Option<std::sync::Mutex<StreamingDeduplicator>>,
+
+    /// Collection loop settings
+    settings: RunSettings,
+
+    /// Posts fetched but not yet handed to the sinks
+    buffer: Mutex<Vec<SocialPost>>,
+
+    /// Sender into the writer queue while `start` runs
+    queue: std::sync::Mutex<Option<mpsc::Sender<SocialPost>>>,
+
+    /// Set once shutdown begins; loops stop scheduling new searches
+    shutdown: watch::Sender<bool>,
+
+    /// Number of per-platform loops still running
+    running: watch::Sender<usize>,
+
+    /// Counters exported on the metrics endpoint
+    metrics: Arc<Metrics>,
+
+    /// Per-query watermarks; scheduled runs are incremental when set
+    watermarks: Option<Arc<dyn WatermarkStore>>,
+
+    /// Per-minute sampling budget shared by every search
+    rate_window: Arc<std::sync::Mutex<RateWindow>>,
+
+    /// Concurrent search slots per platform, shared by its connectors
+    request_slots: HashMap<&'static str, Arc<Semaphore>>,
+
+    /// Daily or monthly collection caps per platform
+    quota: Arc<DailyQuota>,
+}
+
+/// Searches in flight at once per platform unless configured
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+impl IngestionService {
+    /// Create a service whose sink list starts with the given store
+    pub fn new(
+        connectors: Vec<Arc<dyn ApiConnector>>,
+        store: Arc<dyn PostStore>,
+        dedup_scope: DedupScope,
+    ) -> Self {
+        let store_sink: Arc<dyn Sink> = Arc::new(StoreSink::new(Arc::clone(&store)));
+        let breakers = connectors
+            .iter()
+            .map(|_| CircuitBreaker::new(CircuitBreakerConfig::default()))
+            .collect();
+        let request_slots = connectors
+            .iter()
+            .map(|connector| (connector.platform_name(), Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_REQUESTS))))
+            .collect();
+
+        Self {
+            connectors,
+            breakers,
+            store,
+            sinks: vec![store_sink],
+            enrichers: Vec::new(),
+            deduplicator: Mutex::new(Deduplicator::new(dedup_scope)),
+            streaming_dedup: None,
+            settings: RunSettings::default(),
+            buffer: Mutex::new(Vec::new()),
+            queue: std::sync::Mutex::new(None),
+            shutdown: watch::channel(false).0,
+            running: watch::channel(0).0,
+            metrics: Arc::new(Metrics::new()),
+            watermarks: None,
+            rate_window: Arc::default(),
+            request_slots,
+            quota: Arc::default(),
+        }
+    }
+
+    /// Build the service from configuration: connectors, PostgreSQL store,
+    /// webhook sinks, circuit breakers and collection settings
+    ///
+    /// Fails if no platform is enabled with its credentials.
+    pub async fn from_config(config: &Config) -> Result<Self> {
+        let registry = ConnectorRegistry::from_config(config)?;
+        if registry.is_empty() {
+            bail!("No platform is enabled with credentials, nothing to collect");
+        }
+        let connectors = registry.connectors();
+
+        let store = Arc::new(
+            PostgresPostStore::connect(&config.database.url(), config.database.max_connections).await?
+        );
+
+        let watermarks = PostgresWatermarkStore::new(store.pool().clone());
+        let mut service = Self::new(connectors, store, config.processing.dedup_scope);
+
+        if config.collection.incremental {
+            service.set_watermark_store(Arc::new(watermarks));
+        }
+
+        for webhook in &config.sinks.webhooks {
+            service.add_sink(Arc::new(WebhookSink::new(webhook.clone())?));
+        }
+
+        if let Some(kafka) = &config.sinks.kafka {
+            service.add_sink(Arc::new(KafkaSink::new(kafka.clone())?));
+        }
+
+        if let Some(streaming_dedup) = &config.processing.streaming_dedup {
+            service.set_streaming_dedup(streaming_dedup.clone());
+        }
+
+        for name in &config.processing.enrichers {
+            service.add_enricher(enrich::built_in(name)?);
+        }
+
+        if config.processing.resolve_short_urls {
+            service.add_enricher(Box::new(ShortUrlResolver::new(config.processing.short_urls.clone())?));
+        }
+
+        for platform in config.circuit_breakers.keys() {
+            service.set_circuit_breaker(platform, config.circuit_breaker_for(platform));
+        }
+
+        for (platform, limit) in &config.collection.max_concurrent_requests {
+            service.set_max_concurrent_requests(platform, *limit);
+        }
+
+        for (platform, quota) in &config.collection.quotas {
+            service.set_quota(platform, quota.clone());
+        }
+
+        service.set_run_settings(RunSettings {
+            queries: config.collection.queries.clone(),
+            max_results: config.collection.max_results,
+            poll_interval: Duration::from_secs(config.collection.poll_interval_secs),
+            batch_size: config.processing.batch_size,
+            flush_interval: Duration::from_secs(config.processing.flush_interval_secs),
+            queue_capacity: config.processing.queue_capacity,
+            drain_timeout: Duration::from_secs(config.processing.shutdown_drain_timeout_secs),
+            report_zero_results: config.processing.report_zero_result_platforms,
+            sampling: config.collection.sampling,
+            sort_order: config.collection.sort_order,
+            completeness_weights: config.processing.completeness_weights.clone(),
+            strict_enrichment: config.processing.strict_enrichment,
+            retry_budget: config.collection.retry_budget.clone(),
+        });
+
+        Ok(service)
+    }
+
+    /// Register an additional sink that receives the same posts as the store
+    pub fn add_sink(&mut self, sink: Arc<dyn Sink>) {
+        self.sinks.push(sink);
+    }
+
+    /// Register an enricher, run after those already registered
+    pub fn add_enricher(&mut self, enricher: Box<dyn Enricher>) {
+        self.enrichers.push(enricher);
+    }
+
+    /// Replace the circuit breaker settings for a platform's connectors
+    pub fn set_circuit_breaker(&mut self, platform: &str, config: CircuitBreakerConfig) {
+        for (connector, breaker) in self.connectors.iter().zip(self.breakers.iter_mut()) {
+            if connector.platform_name() == platform {
+                *breaker = CircuitBreaker::new(config.clone());
+            }
+        }
+    }
+
+    /// Limit how many searches may run at once on a platform (at least one)
+    pub fn set_max_concurrent_requests(&mut self, platform: &str, limit: usize) {
+        if let Some(slots) = self.request_slots.get_mut(platform) {
+            *slots = Arc::new(Semaphore::new(limit.max(1)));
+        }
+    }
+
+    /// Cap how many posts or requests a platform may use per day or month
+    pub fn set_quota(&mut self, platform: &str, config: QuotaConfig) {
+        self.quota.set_limit(platform, config);
+    }
+
+    /// Quota usage tracker, for saving and restoring usage across runs
+    pub fn quota(&self) -> Arc<DailyQuota> {
+        Arc::clone(&self.quota)
+    }
+
+    /// Make scheduled runs incremental, tracking watermarks in `store`
+    pub fn set_watermark_store(&mut self, store: Arc<dyn WatermarkStore>) {
+        self.watermarks = Some(store);
+    }
+
+    /// Drop recently seen content in the writer task, within this window
+    pub fn set_streaming_dedup(&mut self, config: StreamingDedupConfig) {
+        self.streaming_dedup = Some(std::sync::Mutex::new(StreamingDeduplicator::new(config)));
+    }
+
+    /// Replace the collection loop settings
+    pub fn set_run_settings(&mut self, settings: RunSettings) {
+        self.settings = settings;
+    }
+
+    /// Credential validity and remaining quota of every connector
+    ///
+    /// All connectors are checked concurrently; see `connector_status`.
+    pub async fn status_report(&self) -> Vec<ConnectorStatus> {
+        join_all(self.connectors.iter().map(|connector| connector_status(connector.as_ref()))).await
+    }
+
+    /// Shared metrics registry, for serving /metrics and /healthz
+    pub fn metrics(&self) -> Arc<Metrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    /// How long shutdown waits for in-flight searches
+    pub fn drain_timeout(&self) -> Duration {
+        self.settings.drain_timeout
+    }
+
+    /// Whether shutdown has begun
+    pub fn is_shutting_down(&self) -> bool {
+        *self.shutdown.borrow()
+    }
+
+    /// Run the collection loop for every connector until shutdown
+    ///
+    /// Each platform cycles through the configured queries, then waits for
+    /// the poll interval. Fetched posts go through the writer queue, which
+    /// is drained before returning, as are any buffered posts.
+    pub async fn start(&self) -> Result<()> {
+        let (sender, receiver) = mpsc::channel(self.settings.queue_capacity.max(1));
+        *self.queue.lock().unwrap() = Some(sender);
+
+        // The writer counts as running so shutdown waits for it to drain
+        self.running.send_replace(self.connectors.len() + 1);
+        self.metrics.set_running(true);
+        info!("Starting collection on {} connectors", self.connectors.len());
+
+        let fetchers = async {
+            join_all((0..self.connectors.len()).map(|index| async move {
+                self.run_connector(index).await;
+                self.running.send_modify(|running| *running = running.saturating_sub(1));
+            }))
+            .await;
+
+            // Dropping the last sender closes the queue once it is drained
+            self.queue.lock().unwrap().take();
+        };
+
+        let writer = async {
+            self.write_queue(receiver).await;
+            self.running.send_modify(|running| *running = running.saturating_sub(1));
+        };
+
+        tokio::join!(fetchers, writer);
+
+        self.flush().await?;
+        Ok(())
+    }
+
+    /// Hand queued posts to the sinks in batches until the queue closes
+    ///
+    /// A batch goes out once it reaches `batch_size` or the flush interval
+    /// passes, whichever comes first. Sink errors are logged; the writer
+    /// keeps going so fetchers never wait on a stopped queue.
+    async fn write_queue(&self, mut queue: mpsc::Receiver<SocialPost>) {
+        let batch_size = self.settings.batch_size.max(1);
+        let mut batch = Vec::with_capacity(batch_size);
+
+        let mut flush_timer = tokio::time::interval(self.settings.flush_interval.max(Duration::from_millis(1)));
+        flush_timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        flush_timer.tick().await;
+
+        loop {
+            let held = batch.len();
+            let room = batch_size - held;
+            let (closed, timed_out) = tokio::select! {
+                received = queue.recv_many(&mut batch, room) => (received == 0, false),
+                _ = flush_timer.tick() => (false, true),
+            };
+            self.metrics.set_queue_depth(queue.len());
+
+            // Only the posts just received; those held over were checked already
+            if let Some(dedup) = &self.streaming_dedup {
+                let mut dedup = dedup.lock().unwrap();
+                let received = batch.len();
+                let mut position = 0;
+                batch.retain(|post| {
+                    position += 1;
+                    position <= held || !dedup.is_duplicate(post)
+                });
+                if batch.len() < received {
+                    debug!("Writer dropped {} recently seen posts", received - batch.len());
+                }
+            }
+
+            if !batch.is_empty() && (closed || timed_out || batch.len() >= batch_size) {
+                if let Err(e) = self.process_posts(std::mem::take(&mut batch)).await {
+                    warn!("Writer failed to process queued posts: {}", e);
+                }
+                flush_timer.reset();
+            }
+
+            if closed {
+                break;
+            }
+        }
+    }
+
+    /// Collection loop for a single connector
+    async fn run_connector(&self, index: usize) {
+        let connector = &self.connectors[index];
+        if !can_search(connector.as_ref()) {
+            return;
+        }
+
+        let mut shutdown = self.shutdown.subscribe();
+
+        while !self.is_shutting_down() {
+            for query in &self.settings.queries {
+                if self.is_shutting_down() {
+                    break;
+                }
+
+                let params = self.search_params(query);
+
+                self.retry_budget().scope(async {
+                    let mut sampler = self.sampler();
+                    if let Ok(posts) = self.search_incremental(index, &within_sample(&params, &sampler)).await {
+                        sampler.extend(posts);
+                        if let Err(e) = self.enqueue(sampler.finish()).await {
+                            warn!("Failed to process posts from {}: {}", connector.platform_name(), e);
+                        }
+                    }
+                })
+                .in_job(Uuid::new_v4())
+                .await;
+            }
+
+            // Sleep until the next cycle, waking early on shutdown
+            tokio::select! {
+                _ = tokio::time::sleep(self.settings.poll_interval) => {},
+                _ = shutdown.wait_for(|stopping| *stopping) => {},
+            }
+        }
+    }
+
+    /// Fresh retry budget for one job
+    fn retry_budget(&self) -> Arc<RetryBudget> {
+        Arc::new(RetryBudget::new(self.settings.retry_budget.clone()))
+    }
+
+    /// Sampler for one job, sharing the service's per-minute budget
+    fn sampler(&self) -> Sampler<SocialPost> {
+        Sampler::new(self.settings.sampling).with_rate_window(Arc::clone(&self.rate_window))
+    }
+
+    /// Search parameters for one configured query
+    fn search_params(&self, query: &str) -> SearchParams {
+        SearchParams {
+            query: query.to_string(),
+            max_results: Some(self.settings.max_results),
+            sort_order: self.settings.sort_order,
+            ..Default::default()
+        }
+    }
+
+    /// Run a single collection pass over the configured queries
+    ///
+    /// Returns the deduplicated posts, which have also gone to every sink,
+    /// and a report per query.
+    pub async fn collect_once(&self) -> Result<(Vec<SocialPost>, Vec<CollectionReport>)> {
+        let mut collected = Vec::new();
+        let reports = self.collect_once_each(|posts| {
+            collected.extend(posts);
+            Ok(())
+        }).await?;
+        Ok((collected, reports))
+    }
+
+    /// Run a single collection pass, handing posts to `on_posts` a page at
+    /// a time as they arrive
+    ///
+    /// Only one page is held at a time, for streaming exports; see
+    /// `collect_pages`. An error from `on_posts` stops the pass.
+    pub async fn collect_once_each<F>(&self, mut on_posts: F) -> Result<Vec<CollectionReport>>
+    where
+        F: FnMut(Vec<SocialPost>) -> Result<()>,
+    {
+        let mut reports = Vec::new();
+        for query in &self.settings.queries {
+            let params = self.search_params(query);
+            let job_id = Uuid::new_v4();
+            let report = self.retry_budget()
+                .scope(self.collect_pages(job_id, &params, &mut on_posts))
+                .in_job(job_id)
+                .await?;
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+
+    /// Stop collection and drain in-flight work to storage
+    ///
+    /// New searches stop immediately. In-flight searches get up to the drain
+    /// timeout to finish, after which the buffer is flushed regardless.
+    pub async fn shutdown(&self) -> Result<()> {
+        info!("Shutting down ingestion service");
+        self.shutdown.send_replace(true);
+        self.metrics.set_running(false);
+
+        let mut running = self.running.subscribe();
+        let drained = tokio::time::timeout(
+            self.settings.drain_timeout,
+            running.wait_for(|running| *running == 0),
+        )
+        .await;
+
+        if drained.is_err() {
+            warn!("Drain timeout of {:?} elapsed with searches still in flight", self.settings.drain_timeout);
+        }
+
+        let flushed = self.flush().await?;
+        info!("Flushed {} buffered posts during shutdown", flushed);
+
+        for sink in &self.sinks {
+            if let Err(e) = sink.flush().await {
+                warn!("Sink {} failed to flush during shutdown: {}", sink.name(), e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Hand posts on for storage, waiting while the writer queue is full
+    ///
+    /// While `start` runs, posts go to the writer queue and nothing is
+    /// stored by this call. Otherwise they are buffered and flushed once a
+    /// full batch has accumulated. Returns how many posts this call stored.
+    pub async fn enqueue(&self, mut posts: Vec<SocialPost>) -> Result<usize> {
+        // Tagged now, since the batch is processed after the job has ended
+        tag_with_current_job(&mut posts);
+
+        let queue = self.queue.lock().unwrap().clone();
+        if let Some(queue) = queue {
+            for post in posts {
+                if queue.send(post).await.is_err() {
+                    bail!("Writer queue closed with posts still arriving");
+                }
+                self.metrics.set_queue_depth(queue.max_capacity() - queue.capacity());
+            }
+            return Ok(0);
+        }
+
+        let batch = {
+            let mut buffer = self.buffer.lock().await;
+            buffer.extend(posts);
+            if buffer.len() < self.settings.batch_size {
+                return Ok(0);
+            }
+            std::mem::take(&mut *buffer)
+        };
+
+        self.process_posts(batch).await
+    }
+
+    /// Hand every buffered post to the sinks
+    pub async fn flush(&self) -> Result<usize> {
+        let batch = std::mem::take(&mut *self.buffer.lock().await);
+        self.process_posts(batch).await
+    }
+
+    /// Search every configured connector and process the results
+    ///
+    /// Connector failures are logged and skipped so one platform being down
+    /// doesn't block the others; repeated failures open that connector's
+    /// circuit until its probe succeeds. The report covers every connector.
+    pub async fn ingest(&self, params: &SearchParams) -> Result<CollectionReport> {
+        let job_id = Uuid::new_v4();
+        Ok(self.retry_budget().scope(self.collect(job_id, params)).in_job(job_id).await?.1)
+    }
+
+    /// Run one keyword, hashtag, user timeline or trending topics job on its
+    /// platform's connector
+    ///
+    /// The job is marked Running, then Completed, or Failed with the error
+    /// in `error_message`, e.g. once its retry budget runs out;
+    /// `retry_count` records the retries it used. Returns the stored posts,
+    /// each tagged with the job's id (see `PostStore::delete_by_job`).
+    pub async fn run_job(&self, job: &mut IngestionJob) -> Result<Vec<SocialPost>> {
+        job.status = JobStatus::Running;
+        job.started_at = Some(Utc::now());
+
+        let budget = self.retry_budget();
+        let result = Arc::clone(&budget)
+            .scope(self.run_job_search(job))
+            .in_job(job.id)
+            .await;
+
+        job.retry_count = budget.retries();
+        job.completed_at = Some(Utc::now());
+        match &result {
+            Ok(_) => job.status = JobStatus::Completed,
+            Err(e) => {
+                warn!("Job {} failed: {:#}", job.id, e);
+                job.status = JobStatus::Failed;
+                job.error_message = Some(format!("{:#}", e));
+            },
+        }
+        result
+    }
+
+    /// Collect and process for `run_job`
+    ///
+    /// Keyword and hashtag jobs run one search. User timeline jobs fetch the
+    /// posts of each of `users`; trending topic jobs search each topic
+    /// trending at the first of `locations` (worldwide when empty).
+    async fn run_job_search(&self, job: &IngestionJob) -> Result<Vec<SocialPost>> {
+        let index = self.connector_index(job.platform)?;
+
+        let queries = match job.job_type {
+            JobType::KeywordSearch => vec![job.parameters.keywords.join(" ")],
+            JobType::HashtagSearch => vec![job.parameters.hashtags.iter()
+                .map(|tag| format!("#{}", tag.trim_start_matches('#')))
+                .collect::<Vec<_>>()
+                .join(" ")],
+            JobType::UserTimeline => {
+                let mut posts = Vec::new();
+                for user in &job.parameters.users {
+                    posts.extend(self.user_posts(index, user, job.parameters.limit)
+                        .await
+                        .with_context(|| format!("{} timeline of {} failed", job.platform, user))?);
+                }
+                return self.process_batch(posts).await;
+            },
+            JobType::TrendingTopics => {
+                let connector = &self.connectors[index];
+                let location = job.parameters.locations.first().map(String::as_str);
+                self.request(index, Operation::Trending, || connector.get_trending_topics(location))
+                    .await
+                    .with_context(|| format!("{} trending topics failed", job.platform))?
+            },
+            ref other => bail!("{:?} jobs are not supported", other),
+        };
+
+        let mut posts = Vec::new();
+        for query in queries {
+            let mut params = self.search_params(&query);
+            params.max_results = job.parameters.limit.or(params.max_results);
+            params.start_date = job.parameters.date_range.as_ref().map(|range| range.start);
+            params.end_date = job.parameters.date_range.as_ref().map(|range| range.end);
+            params.language = job.parameters.language_filter.clone();
+
+            posts.extend(self.search(index, &params)
+                .await
+                .with_context(|| format!("{} search failed", job.platform))?);
+        }
+        self.process_batch(posts).await
+    }
+
+    /// Recent posts by one user on connector `index`, counted against its quotas
+    async fn user_posts(&self, index: usize, user_id: &str, limit: Option<u32>) -> Result<Vec<SocialPost>, ConnectorError> {
+        let connector = &self.connectors[index];
+        let mut posts = self.request(index, Operation::UserPosts, || connector.get_user_posts(user_id, limit)).await?;
+        self.charge_quota(connector.platform_name(), &mut posts);
+        self.metrics.record_posts(connector.platform_name(), posts.len());
+        Ok(posts)
+    }
+
+    /// Look up one post by id on `platform`, counted against its request quota
+    ///
+    /// The post is returned as fetched, without being processed or stored.
+    pub async fn get_post(&self, platform: Platform, post_id: &str) -> Result<Option<SocialPost>> {
+        let index = self.connector_index(platform)?;
+        let connector = &self.connectors[index];
+        self.request(index, Operation::GetById, || connector.get_post_by_id(post_id))
+            .await
+            .with_context(|| format!("{} lookup of {} failed", platform, post_id))
+    }
+
+    /// Index of the connector serving `platform`
+    fn connector_index(&self, platform: Platform) -> Result<usize> {
+        self.connectors.iter()
+            .position(|connector| connector.platform_name().parse::<Platform>().ok() == Some(platform))
+            .with_context(|| format!("No {} connector is configured", platform))
+    }
+
+    /// Search every configured connector and return the processed posts,
+    /// reporting them under `job_id`
+    async fn collect(&self, job_id: Uuid, params: &SearchParams) -> Result<(Vec<SocialPost>, CollectionReport)> {
+        let mut sampler = self.sampler();
+        let mut platforms = Vec::new();
+
+        for (index, connector) in self.connectors.iter().enumerate() {
+            let platform = connector.platform_name().to_string();
+
+            if !can_search(connector.as_ref()) || sampler.is_full() {
+                platforms.push(PlatformReport { platform, status: PlatformStatus::Skipped, count: 0, error: None });
+                continue;
+            }
+
+            match self.search(index, &within_sample(params, &sampler)).await {
+                Ok(posts) => {
+                    platforms.push(PlatformReport { platform, status: PlatformStatus::Ok, count: posts.len(), error: None });
+                    sampler.extend(posts);
+                },
+                Err(e) => {
+                    platforms.push(PlatformReport { platform, status: PlatformStatus::Error, count: 0, error: Some(e.to_string()) });
+                }
+            }
+        }
+
+        if !self.settings.report_zero_results {
+            platforms.retain(|entry| entry.status != PlatformStatus::Ok || entry.count > 0);
+        }
+
+        let mut posts = self.process_batch(sampler.finish()).await?;
+        sort_posts(&mut posts, params.sort_order);
+
+        let report = CollectionReport {
+            job_id,
+            query: params.query.clone(),
+            platforms,
+            stored: posts.len(),
+        };
+
+        Ok((posts, report))
+    }
+
+    /// Search every configured connector from its watermark a page at a
+    /// time, handing each processed page to `on_posts`
+    ///
+    /// Like an incremental `collect`, except that posts come in fetch order
+    /// rather than `params.sort_order`, and a platform failing part way
+    /// keeps the pages already handed over. A Reservoir sample is handed
+    /// over once complete, since later posts can still replace its members.
+    async fn collect_pages<F>(&self, job_id: Uuid, params: &SearchParams, on_posts: &mut F) -> Result<CollectionReport>
+    where
+        F: FnMut(Vec<SocialPost>) -> Result<()>,
+    {
+        let mut sampler = self.sampler();
+        let mut platforms = Vec::new();
+        let mut stored = 0;
+
+        for (index, connector) in self.connectors.iter().enumerate() {
+            let platform = connector.platform_name().to_string();
+
+            if !can_search(connector.as_ref()) || sampler.is_full() {
+                platforms.push(PlatformReport { platform, status: PlatformStatus::Skipped, count: 0, error: None });
+                continue;
+            }
+
+            let watermark = self.load_watermark(index, params).await;
+            let mut newest = watermark.clone();
+            let mut page_token: Option<String> = None;
+            let mut count = 0;
+            let mut error = None;
+
+            loop {
+                // Stop at max_results across pages, and at a full sample
+                let mut page_params = SearchParams {
+                    since_id: watermark.as_ref().map(|mark| mark.post_id.clone()),
+                    ..within_sample(params, &sampler)
+                };
+                if let Some(max) = page_params.max_results {
+                    let left = max.saturating_sub(u32::try_from(count).unwrap_or(u32::MAX));
+                    if left == 0 {
+                        break;
+                    }
+                    page_params.max_results = Some(left);
+                }
+                if page_token.is_some() {
+                    self.wait_for_rate_limit(index).await;
+                }
+
+                let (mut posts, next) = match self.search_page(index, &page_params, page_token.as_deref()).await {
+                    Ok(page) => page,
+                    Err(e) => {
+                        error = Some(e.to_string());
+                        break;
+                    }
+                };
+                if let Some(mark) = &watermark {
+                    posts.retain(|post| mark.precedes(post));
+                }
+                newest = Watermark::advance(newest, &posts);
+                count += posts.len();
+
+                sampler.extend(posts);
+                let page = self.process_batch(sampler.take_settled()).await?;
+                stored += page.len();
+                if !page.is_empty() {
+                    on_posts(page)?;
+                }
+
+                match next {
+                    Some(token) if !sampler.is_full() => page_token = Some(token),
+                    _ => break,
+                }
+            }
+
+            self.save_watermark(index, params, watermark.as_ref(), newest).await;
+            let status = if error.is_some() { PlatformStatus::Error } else { PlatformStatus::Ok };
+            platforms.push(PlatformReport { platform, status, count, error });
+        }
+
+        let rest = self.process_batch(sampler.finish()).await?;
+        stored += rest.len();
+        if !rest.is_empty() {
+            on_posts(rest)?;
+        }
+
+        if !self.settings.report_zero_results {
+            platforms.retain(|entry| entry.status != PlatformStatus::Ok || entry.count > 0);
+        }
+
+        Ok(CollectionReport {
+            job_id,
+            query: params.query.clone(),
+            platforms,
+            stored,
+        })
+    }
+
+    /// Sweep `params`' date range in sequential windows of `window`
+    ///
+    /// Every configured connector is searched window by window, oldest
+    /// first, waiting out an exhausted rate limit between windows. Results
+    /// are merged, deduplicated and handed to the sinks like any other run.
+    /// The range needs a start date (or day); the end defaults to now.
+    pub async fn backfill(&self, params: &SearchParams, window: Duration) -> Result<Vec<SocialPost>> {
+        self.retry_budget().scope(self.sweep(params, window)).in_job(Uuid::new_v4()).await
+    }
+
+    /// Body of `backfill`, run within the job's retry budget
+    async fn sweep(&self, params: &SearchParams, window: Duration) -> Result<Vec<SocialPost>> {
+        let params = &params.with_resolved_dates()?;
+        let Some(start) = params.start_date else {
+            bail!("Backfill needs a start date");
+        };
+        let end = params.end_date.unwrap_or_else(Utc::now);
+        let window = chrono::Duration::from_std(window)?;
+        if window <= chrono::Duration::zero() {
+            bail!("Backfill window must be positive");
+        }
+
+        let mut windows = Vec::new();
+        let mut window_start = start;
+        while window_start < end {
+            let window_end = (window_start + window).min(end);
+            windows.push((window_start, window_end));
+            window_start = window_end;
+        }
+
+        let mut sampler = self.sampler();
+        for (index, connector) in self.connectors.iter().enumerate() {
+            if !can_search(connector.as_ref()) || sampler.is_full() {
+                continue;
+            }
+            let platform = connector.platform_name();
+
+            // Clamp to the connector's reachable history
+            let earliest = connector.max_search_age().map(|age| Utc::now() - age);
+            if let Some(earliest) = earliest.filter(|earliest| start < *earliest) {
+                warn!(
+                    "{} only searches back to {}; backfill gap from {} to {}",
+                    platform, earliest, start, earliest.min(end)
+                );
+            }
+
+            for (window_start, window_end) in &windows {
+                if sampler.is_full() {
+                    break;
+                }
+                let window_start = match earliest {
+                    Some(earliest) if *window_end <= earliest => continue,
+                    Some(earliest) => (*window_start).max(earliest),
+                    None => *window_start,
+                };
+
+                self.wait_for_rate_limit(index).await;
+                self.backfill_window(index, params, window_start, *window_end, &mut sampler).await;
+            }
+        }
+
+        self.process_batch(sampler.finish()).await
+    }
+
+    /// Search one backfill window, paging back from its end, into `sampler`
+    ///
+    /// A full page means there may be more, so the next page ends at the
+    /// oldest post seen. Paging stops on a short page, a page with nothing
+    /// new (connectors that ignore dates), a failure, the page cap, or a
+    /// full sample.
+    async fn backfill_window(
+        &self,
+        index: usize,
+        params: &SearchParams,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        sampler: &mut Sampler<SocialPost>,
+    ) {
+        let mut seen = HashSet::new();
+        let mut page_end = end;
+
+        for _ in 0..BACKFILL_MAX_PAGES {
+            if sampler.is_full() {
+                break;
+            }
+
+            let page_params = SearchParams {
+                start_date: Some(start),
+                end_date: Some(page_end),
+                since_id: None,
+                ..within_sample(params, sampler)
+            };
+
+            let Ok(page) = self.search(index, &page_params).await else {
+                break;
+            };
+            let full_page = page_params.max_results.is_some_and(|max| page.len() >= max as usize);
+
+            let new: Vec<SocialPost> = page.into_iter().filter(|post| seen.insert(post.id.clone())).collect();
+            let oldest = new.iter().map(|post| post.created_at).min();
+            sampler.extend(new);
+
+            match oldest {
+                Some(oldest) if full_page && oldest > start && oldest < page_end => page_end = oldest,
+                _ => break,
+            }
+        }
+    }
+
+    /// Sleep until a connector's rate limit resets if it reports none left
+    async fn wait_for_rate_limit(&self, index: usize) {
+        let connector = &self.connectors[index];
+        let Ok(status) = connector.get_rate_limit_status().await else {
+            return;
+        };
+
+        if status.remaining == 0 {
+            if let Ok(wait) = (status.reset_time - Utc::now()).to_std() {
+                info!("{} rate limit exhausted, pausing for {:?}", connector.platform_name(), wait);
+                tokio::time::sleep(wait).await;
+            }
+        }
+    }
+
+    /// Search one connector for posts newer than its watermark for the query
+    ///
+    /// The watermark is passed as `since_id` and also applied to the results,
+    /// then advanced to the newest post returned. Without a watermark store
+    /// this is a plain search. Watermark store failures are logged and fall
+    /// back to a full search rather than failing collection.
+    async fn search_incremental(&self, index: usize, params: &SearchParams) -> Result<Vec<SocialPost>, ConnectorError> {
+        let watermark = self.load_watermark(index, params).await;
+        let params = SearchParams {
+            since_id: watermark.as_ref().map(|mark| mark.post_id.clone()),
+            ..params.clone()
+        };
+        let mut posts = self.search(index, &params).await?;
+
+        if let Some(mark) = &watermark {
+            posts.retain(|post| mark.precedes(post));
+        }
+
+        let next = Watermark::advance(watermark.clone(), &posts);
+        self.save_watermark(index, &params, watermark.as_ref(), next).await;
+        Ok(posts)
+    }
+
+    /// Stored watermark of connector `index` for the query, if any
+    async fn load_watermark(&self, index: usize, params: &SearchParams) -> Option<Watermark> {
+        let store = self.watermarks.as_ref()?;
+        let platform = self.connectors[index].platform_name();
+        store.load(platform, &query_hash(&params.query)).await.unwrap_or_else(|e| {
+            warn!("Failed to load {} watermark for \"{}\": {}", platform, params.query, e);
+            None
+        })
+    }
+
+    /// Store `next` as the watermark for the query if it moved past `previous`
+    async fn save_watermark(&self, index: usize, params: &SearchParams, previous: Option<&Watermark>, next: Option<Watermark>) {
+        let (Some(store), Some(next)) = (&self.watermarks, next) else {
+            return;
+        };
+        if previous == Some(&next) {
+            return;
+        }
+
+        let platform = self.connectors[index].platform_name();
+        if let Err(e) = store.save(platform, &query_hash(&params.query), &next).await {
+            warn!("Failed to save {} watermark for \"{}\": {}", platform, params.query, e);
+        }
+    }
+
+    /// Search one connector through its circuit breaker, recording metrics
+    ///
+    /// Failures are logged and counted before being returned. Successful
+    /// results record where the query terms matched (metadata `matches`).
+    #[instrument(skip_all, fields(platform = self.connectors[index].platform_name(), query = %params.query))]
+    async fn search(&self, index: usize, params: &SearchParams) -> Result<Vec<SocialPost>, ConnectorError> {
+        let connector = &self.connectors[index];
+        let (params, content_filter) = self.prepare_search(index, params)?;
+        let result = self.request(index, Operation::Search, || connector.search_posts(&params)).await;
+        self.finish_search(index, &params, &content_filter, result).await
+    }
+
+    /// One page of `search`, with the token for the next page if any
+    ///
+    /// A page the connector returned empty ends the search, even if the
+    /// platform handed out another token.
+    #[instrument(skip_all, fields(platform = self.connectors[index].platform_name(), query = %params.query))]
+    async fn search_page(
+        &self,
+        index: usize,
+        params: &SearchParams,
+        page_token: Option<&str>,
+    ) -> Result<(Vec<SocialPost>, Option<String>), ConnectorError> {
+        let connector = &self.connectors[index];
+        let (params, content_filter) = self.prepare_search(index, params)?;
+        let (posts, next) = match self.request(index, Operation::Search, || connector.search_next_page(&params, page_token)).await {
+            Ok((posts, next)) => {
+                let next = next.filter(|_| !posts.is_empty());
+                (Ok(posts), next)
+            },
+            Err(e) => (Err(e), None),
+        };
+        Ok((self.finish_search(index, &params, &content_filter, posts).await?, next))
+    }
+
+    /// Params as connector `index` may be searched with, and the content
+    /// filter they ask for
+    ///
+    /// Bad patterns fail here, before any request is made.
+    fn prepare_search(&self, index: usize, params: &SearchParams) -> Result<(SearchParams, ContentFilter), ConnectorError> {
+        // Connectors only see UTC instants
+        let params = params.with_resolved_dates()?;
+        let content_filter = ContentFilter::from_params(&params)?;
+        let platform = self.connectors[index].platform_name();
+        Ok((self.within_quota(platform, &params), content_filter))
+    }
+
+    /// Record a search's outcome and filter its posts
+    async fn finish_search(
+        &self,
+        index: usize,
+        params: &SearchParams,
+        content_filter: &ContentFilter,
+        result: Result<Vec<SocialPost>, ConnectorError>,
+    ) -> Result<Vec<SocialPost>, ConnectorError> {
+        let connector = &self.connectors[index];
+        let platform = connector.platform_name();
+
+        if let Ok(status) = connector.get_rate_limit_status().await {
+            self.metrics.set_rate_limit_remaining(platform, status.remaining);
+        }
+
+        match result {
+            Ok(mut posts) => {
+                self.charge_quota(platform, &mut posts);
+                info!("Collected {} posts from {}", posts.len(), platform);
+                self.metrics.record_posts(platform, posts.len());
+
+                let posts = filter_by_bounds(posts, params.bounds.as_ref(), params.require_location);
+                let mut posts = content_filter.apply(posts);
+
+                let terms = query_terms(&params.query);
+                for post in &mut posts {
+                    highlight_matches(post, &terms);
+                }
+                Ok(posts)
+            },
+            Err(e) => {
+                warn!("Search failed on {}: {}", platform, e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Make one request to connector `index`, priced at `operation`'s cost
+    ///
+    /// Fails without calling the connector once the platform's quota is used
+    /// up, or a request quota has fewer units left than the cost. Failures
+    /// are counted before being returned.
+    async fn request<T, F, Fut>(&self, index: usize, operation: Operation, call: F) -> Result<T, ConnectorError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<T, ConnectorError>>,
+    {
+        let connector = &self.connectors[index];
+        let platform = connector.platform_name();
+        let cost = connector.request_cost(operation);
+        self.quota.check_request(platform, cost, Utc::now())?;
+
+        // Held for the whole request; the semaphore is never closed
+        let _slot = self.request_slots[platform].acquire().await.expect("request slots closed");
+
+        // Counted inside the operation so circuit-open rejections aren't API calls
+        let result = self.breakers[index].call(connector.as_ref(), || {
+            self.metrics.record_api_call(platform);
+            self.quota.record_request(platform, cost, Utc::now());
+            call()
+        }).await;
+
+        if let Err(e) = &result {
+            self.metrics.record_error(platform);
+            if matches!(e, ConnectorError::RateLimitExceeded { .. }) {
+                self.metrics.record_rate_limit_hit(platform);
+            }
+        }
+        result
+    }
+
+    /// Search params allowed by a platform's quota
+    ///
+    /// A post quota caps max_results at the posts it has left.
+    fn within_quota(&self, platform: &str, params: &SearchParams) -> SearchParams {
+        let mut params = params.clone();
+        if let (Some(QuotaUnit::Posts), Some(remaining)) = (self.quota.unit(platform), self.quota.remaining(platform, Utc::now())) {
+            let remaining = u32::try_from(remaining).unwrap_or(u32::MAX);
+            params.max_results = Some(params.max_results.map_or(remaining, |max| max.min(remaining)));
+        }
+        params
+    }
+
+    /// Count collected posts against a post quota, dropping any beyond it
+    fn charge_quota(&self, platform: &str, posts: &mut Vec<SocialPost>) {
+        let now = Utc::now();
+        if self.quota.unit(platform) != Some(QuotaUnit::Posts) {
+            return;
+        }
+
+        if let Some(remaining) = self.quota.remaining(platform, now) {
+            if posts.len() as u64 > remaining {
+                warn!("{} quota reached, dropping {} of {} posts", platform, posts.len() as u64 - remaining, posts.len());
+                posts.truncate(remaining as usize);
+            }
+        }
+        self.quota.record_posts(platform, posts.len() as u64, now);
+    }
+
+    /// Run every enricher over the batch
+    ///
+    /// Failures are logged and skipped, or fail the batch in strict mode.
+    async fn enrich(&self, posts: &mut [SocialPost]) -> Result<()> {
+        for post in posts.iter_mut() {
+            for enricher in &self.enrichers {
+                if let Err(e) = enricher.enrich(post).await {
+                    if self.settings.strict_enrichment {
+                        return Err(e.context(format!("Enricher {} failed on {} post {}", enricher.name(), post.platform, post.id)));
+                    }
+                    warn!("Enricher {} failed on {} post {}: {:#}", enricher.name(), post.platform, post.id, e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Deduplicate a batch of posts and fan it out to every sink
+    ///
+    /// Every sink is attempted even if an earlier one fails; the first
+    /// failure is returned once all sinks have been given the batch.
+    pub async fn process_posts(&self, posts: Vec<SocialPost>) -> Result<usize> {
+        Ok(self.process_batch(posts).await?.len())
+    }
+
+    /// Drop incomplete posts, then deduplicate, enrich, score and fan out a
+    /// batch, returning the posts that survived
+    ///
+    /// Posts not yet tagged with a job are tagged with the current one.
+    async fn process_batch(&self, mut posts: Vec<SocialPost>) -> Result<Vec<SocialPost>> {
+        tag_with_current_job(&mut posts);
+        let posts = self.drop_incomplete(posts);
+        let mut posts = self.deduplicator
+            .lock()
+            .await
+            .filter(posts, self.store.as_ref())
+            .await?;
+
+        if posts.is_empty() {
+            return Ok(posts);
+        }
+
+        self.enrich(&mut posts).await?;
+
+        for post in &mut posts {
+            let score = post.completeness_score_with(&self.settings.completeness_weights);
+            post.metadata.insert("completeness".to_string(), serde_json::json!(score));
+        }
+
+        let mut first_error = None;
+        for sink in &self.sinks {
+            if let Err(e) = sink.consume(&posts).await {
+                warn!("Sink {} failed to consume {} posts: {}", sink.name(), posts.len(), e);
+                first_error.get_or_insert(e);
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(posts),
+        }
+    }
+
+    /// Drop posts marked as lacking required fields, counting them per platform
+    fn drop_incomplete(&self, posts: Vec<SocialPost>) -> Vec<SocialPost> {
+        let (incomplete, complete): (Vec<SocialPost>, Vec<SocialPost>) = posts
+            .into_iter()
+            .partition(|post| post.metadata.contains_key("missing_required_fields"));
+
+        for post in &incomplete {
+            debug!("Dropping {} post {} lacking required fields {}", post.platform, post.id, post.metadata["missing_required_fields"]);
+            self.metrics.record_filtered_out(&post.platform, 1);
+        }
+
+        complete
+    }
+}
+
+/// Tag posts not yet carrying a job id with the current job's, if any
+fn tag_with_current_job(posts: &mut [SocialPost]) {
+    if let Some(job_id) = current_job_id() {
+        for post in posts.iter_mut().filter(|post| post.job_id().is_none()) {
+            post.set_job_id(job_id);
+        }
+    }
+}
+
+/// Whether a connector is configured and supports search at all
+fn can_search(connector: &dyn ApiConnector) -> bool {
+    connector.is_configured() && connector.capabilities().supports_search
+}
+
+/// Search params capped at the room left in a FirstN sample
+///
+/// Lowering max_results makes connectors stop paging once the cap is met.
+fn within_sample(params: &SearchParams, sampler: &Sampler<SocialPost>) -> SearchParams {
+    let mut params = params.clone();
+    if let Some(room) = sampler.remaining() {
+        let room = u32::try_from(room).unwrap_or(u32::MAX);
+        params.max_results = Some(params.max_results.map_or(room, |max| max.min(room)));
+    }
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils::{post, search_params, CapturedLogs, MockConnector};
+    use crate::api_connectors::*;
+    use crate::sinks::MemorySink;
+    use crate::storage::InMemoryPostStore;
+    use async_trait::async_trait;
+
+    /// Connector whose searches take `delay` and return one post
+    fn slow_connector(delay: Duration) -> Arc<MockConnector> {
+        let connector = MockConnector::new("slow")
+            .with_posts(vec![post("slow-0", "flood result 0")])
+            .with_delay(delay)
+            .with_rate_limit(RateLimitInfo {
+                remaining: 42,
+                limit: 50,
+                reset_time: chrono::Utc::now(),
+                window_duration: chrono::Duration::minutes(1),
+            });
+        Arc::new(connector)
+    }
+
+    /// Connector returning a fixed number of posts for every search
+    fn mock_connector(platform: &'static str, configured: bool, results: usize) -> Arc<dyn ApiConnector> {
+        let posts = (0..results)
+            .map(|i| post(&format!("{}-{}", platform, i), &format!("{} post {}", platform, i)))
+            .collect();
+        let connector = MockConnector::new(platform).with_posts(posts);
+        Arc::new(if configured { connector } else { connector.unconfigured() })
+    }
+
+    #[tokio::test]
+    async fn test_report_includes_zero_result_platforms() {
+        let connectors = vec![
+            mock_connector("twitter", true, 2),
+            mock_connector("reddit", true, 0),
+            mock_connector("youtube", false, 0),
+        ];
+        let mut service = IngestionService::new(connectors, Arc::new(InMemoryPostStore::new()), DedupScope::PerRun);
+
+        let params = service.search_params("flood");
+        let report = service.ingest(&params).await.unwrap();
+
+        let reddit = report.platform("reddit").expect("empty platform must be reported");
+        assert_eq!((reddit.status, reddit.count), (PlatformStatus::Ok, 0));
+        assert_eq!(report.platform("youtube").unwrap().status, PlatformStatus::Skipped);
+        assert_eq!(report.platform("twitter").unwrap().count, 2);
+        assert_eq!(report.stored, 2);
+
+        // Opting out drops only the searched-but-empty entries
+        service.set_run_settings(RunSettings { report_zero_results: false, ..RunSettings::default() });
+        let report = service.ingest(&params).await.unwrap();
+        let platforms: Vec<&str> = report.platforms.iter().map(|entry| entry.platform.as_str()).collect();
+        assert_eq!(platforms, vec!["twitter", "youtube"]);
+    }
+
+    #[tokio::test]
+    async fn test_connectors_without_search_are_skipped() {
+        let instagram: Arc<dyn ApiConnector> = Arc::new(InstagramConnector::new("token".to_string(), PrivacyConfig::default()));
+        let connectors = vec![mock_connector("twitter", true, 1), instagram];
+        let service = IngestionService::new(connectors, Arc::new(InMemoryPostStore::new()), DedupScope::PerRun);
+
+        let report = service.ingest(&service.search_params("flood")).await.unwrap();
+
+        let instagram = report.platform("instagram").unwrap();
+        assert_eq!((instagram.status, &instagram.error), (PlatformStatus::Skipped, &None));
+        assert_eq!(service.metrics().platform("instagram").api_calls_made, 0);
+    }
+
+    #[tokio::test]
+    async fn test_first_n_sample_stops_searching_other_platforms() {
+        let connectors = vec![mock_connector("twitter", true, 3), mock_connector("reddit", true, 3)];
+        let mut service = IngestionService::new(connectors, Arc::new(InMemoryPostStore::new()), DedupScope::PerRun);
+        service.set_run_settings(RunSettings {
+            sampling: SamplingStrategy::FirstN { limit: 2 },
+            ..RunSettings::default()
+        });
+
+        let report = service.ingest(&service.search_params("flood")).await.unwrap();
+
+        assert_eq!(report.platform("twitter").unwrap().count, 3);
+        assert_eq!(report.platform("reddit").unwrap().status, PlatformStatus::Skipped);
+        assert_eq!(report.stored, 2);
+    }
+
+    #[tokio::test]
+    async fn test_first_n_sample_stops_paging_at_the_cap() {
+        let posts = (0..50).map(|i| post(&i.to_string(), &format!("flood post {}", i))).collect();
+        let connector = Arc::new(MockConnector::new("twitter").with_posts(posts).with_page_size(10));
+        let ingest = |sampling| {
+            let mut service = IngestionService::new(vec![connector.clone()], Arc::new(InMemoryPostStore::new()), DedupScope::PerRun);
+            service.set_run_settings(RunSettings { sampling, ..RunSettings::default() });
+            async move {
+                let params = SearchParams { max_results: Some(50), ..service.search_params("flood") };
+                service.ingest(&params).await.unwrap()
+            }
+        };
+
+        ingest(SamplingStrategy::None).await;
+        assert_eq!(connector.page_requests(), 5);
+
+        // Capped at 15, the search stops after the second page of ten
+        let report = ingest(SamplingStrategy::FirstN { limit: 15 }).await;
+        assert_eq!(connector.page_requests(), 5 + 2);
+        assert_eq!(connector.searches()[1].max_results, Some(15));
+        assert_eq!(report.stored, 15);
+    }
+
+    #[tokio::test]
+    async fn test_collect_once_each_hands_over_each_page_as_it_arrives() {
+        let posts = (0..25).map(|i| post(&i.to_string(), &format!("flood post {}", i))).collect();
+        let connector = Arc::new(MockConnector::new("twitter").with_posts(posts).with_page_size(10));
+        let mut service = IngestionService::new(vec![connector.clone()], Arc::new(InMemoryPostStore::new()), DedupScope::PerRun);
+        service.set_run_settings(RunSettings { queries: vec!["flood".to_string()], ..RunSettings::default() });
+
+        // Each page reaches the callback before the next one is requested
+        let mut batches = Vec::new();
+        let reports = service.collect_once_each(|batch| {
+            batches.push((batch.len(), connector.page_requests()));
+            Ok(())
+        }).await.unwrap();
+
+        assert_eq!(batches, vec![(10, 1), (10, 2), (5, 3)]);
+        assert_eq!((reports[0].platform("twitter").unwrap().count, reports[0].stored), (25, 25));
+    }
+
+    #[tokio::test]
+    async fn test_second_scheduled_run_only_returns_newer_posts() {
+        let timed = |id: &str, minutes_ago: i64| {
+            let mut post = post(id, &format!("post {}", id));
+            post.created_at = chrono::Utc::now() - chrono::Duration::minutes(minutes_ago);
+            post
+        };
+        // Each search returns the whole timeline as it stands
+        let (older, newer) = (timed("1", 10), timed("2", 5));
+        let pages = vec![vec![older.clone(), newer.clone()], vec![older, newer, timed("3", 0)]];
+        let connector = Arc::new(MockConnector::new("timeline").with_pages(pages));
+        let watermarks = Arc::new(crate::watermark::InMemoryWatermarkStore::new());
+
+        // Each run is a fresh service, as after a restart, sharing the watermark store
+        let run = || {
+            let mut service = IngestionService::new(
+                vec![connector.clone()],
+                Arc::new(InMemoryPostStore::new()),
+                DedupScope::PerRun,
+            );
+            service.set_watermark_store(watermarks.clone());
+            service.set_run_settings(RunSettings { queries: vec!["flood".to_string()], ..RunSettings::default() });
+            service
+        };
+
+        let (first, _) = run().collect_once().await.unwrap();
+        assert_eq!(first.len(), 2);
+
+        let (second, reports) = run().collect_once().await.unwrap();
+        let ids: Vec<&str> = second.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["3"]);
+        assert_eq!(reports[0].platform("timeline").unwrap().count, 1);
+
+        let since_ids: Vec<Option<String>> = connector.searches().iter().map(|p| p.since_id.clone()).collect();
+        assert_eq!(since_ids, vec![None, Some("2".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_searches_each_window() {
+        let connector = Arc::new(MockConnector::new("timeline"));
+        let service = IngestionService::new(vec![connector.clone()], Arc::new(InMemoryPostStore::new()), DedupScope::PerRun);
+
+        let end = chrono::Utc::now();
+        let start = end - chrono::Duration::days(3);
+        let params = SearchParams { start_date: Some(start), end_date: Some(end), ..service.search_params("flood") };
+
+        service.backfill(&params, Duration::from_secs(24 * 3600)).await.unwrap();
+
+        let windows: Vec<_> = connector.searches().iter()
+            .map(|p| (p.start_date.unwrap(), p.end_date.unwrap()))
+            .collect();
+        let day = chrono::Duration::days(1);
+        assert_eq!(windows, vec![
+            (start, start + day),
+            (start + day, start + day * 2),
+            (start + day * 2, end),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_status_report_covers_each_connector() {
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/connector");
+        let connectors: Vec<Arc<dyn ApiConnector>> = vec![
+            Arc::new(FixtureConnector::from_dir(fixtures).unwrap()),
+            mock_connector("reddit", false, 0),
+        ];
+        let service = IngestionService::new(connectors, Arc::new(InMemoryPostStore::new()), DedupScope::PerRun);
+
+        let statuses = service.status_report().await;
+
+        let fixture = &statuses[0];
+        assert_eq!((fixture.platform.as_str(), fixture.configured, fixture.valid), ("fixture", true, Some(true)));
+        assert_eq!((fixture.remaining, fixture.limit), (Some(u32::MAX), Some(u32::MAX)));
+        assert_eq!(fixture.message, None);
+
+        let reddit = &statuses[1];
+        assert_eq!((reddit.configured, reddit.valid, reddit.remaining), (false, None, None));
+        assert_eq!(reddit.message.as_deref(), Some("missing credentials; rate limit unavailable: Connector error: untracked"));
+
+        let table = crate::validation::render_status_table(&statuses);
+        assert!(table.lines().nth(2).unwrap().starts_with("reddit    no          -              -          -"));
+    }
+
+    #[tokio::test]
+    async fn test_logs_within_a_job_carry_job_id() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_span_list(true)
+            .with_max_level(tracing::Level::DEBUG)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/connector");
+        let connector: Arc<dyn ApiConnector> = Arc::new(FixtureConnector::from_dir(fixtures).unwrap());
+        let service = IngestionService::new(vec![connector], Arc::new(InMemoryPostStore::new()), DedupScope::PerRun);
+        let first = service.ingest(&search_params("mumbai flood")).await.unwrap();
+        let second = service.ingest(&search_params("mumbai flood")).await.unwrap();
+
+        let job_ids: Vec<String> = logs.contents()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .filter(|line| line["fields"]["message"].as_str().is_some_and(|message| message.starts_with("Fixture")))
+            .map(|line| line["spans"][0]["job_id"].as_str().expect("connector log without job_id").to_string())
+            .collect();
+
+        // Logged inside the connector's search_posts, one job per ingest,
+        // each reported under the same id
+        assert_eq!(job_ids, vec![first.job_id.to_string(), second.job_id.to_string()]);
+        assert_ne!(first.job_id, second.job_id);
+    }
+
+    #[tokio::test]
+    async fn test_all_sinks_receive_same_posts() {
+        let store = Arc::new(InMemoryPostStore::new());
+        let first = Arc::new(MemorySink::new());
+        let second = Arc::new(MemorySink::new());
+
+        let mut service = IngestionService::new(Vec::new(), store.clone(), DedupScope::PerRun);
+        service.add_sink(first.clone());
+        service.add_sink(second.clone());
+
+        let stored = service
+            .process_posts(vec![post("1", "first"), post("2", "second")])
+            .await
+            .unwrap();
+
+        assert_eq!(stored, 2);
+        assert_eq!(store.len().await, 2);
+
+        let first_ids: Vec<String> = first.posts().await.into_iter().map(|p| p.id).collect();
+        let second_ids: Vec<String> = second.posts().await.into_iter().map(|p| p.id).collect();
+        assert_eq!(first_ids, vec!["1", "2"]);
+        assert_eq!(first_ids, second_ids);
+        assert!(first.posts().await.iter().all(|p| p.metadata["completeness"] == serde_json::json!(0.0)));
+    }
+
+    /// Tags every post, or fails on the post with the given id
+    struct TagEnricher {
+        fail_on: &'static str,
+    }
+
+    #[async_trait]
+    impl Enricher for TagEnricher {
+        fn name(&self) -> &str {
+            "tag"
+        }
+
+        async fn enrich(&self, post: &mut SocialPost) -> Result<()> {
+            if post.id == self.fail_on {
+                bail!("cannot tag");
+            }
+            post.metadata.insert("tag".to_string(), serde_json::json!("flood-watch"));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_enrichers_run_before_storage() {
+        let store = Arc::new(InMemoryPostStore::new());
+        let mut service = IngestionService::new(Vec::new(), store.clone(), DedupScope::PerRun);
+        service.add_enricher(Box::new(TagEnricher { fail_on: "2" }));
+        service.add_enricher(enrich::built_in("content_stats").unwrap());
+
+        let stored = service.process_posts(vec![post("1", "first post"), post("2", "second")]).await.unwrap();
+
+        let mut posts = store.posts().await;
+        posts.sort_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(stored, 2);
+        assert_eq!(posts[0].metadata["tag"], "flood-watch");
+        assert_eq!(posts[0].metadata["word_count"], serde_json::json!(2));
+        // The failed enricher is skipped; later ones still run
+        assert!(!posts[1].metadata.contains_key("tag"));
+        assert_eq!(posts[1].metadata["word_count"], serde_json::json!(1));
+
+        service.set_run_settings(RunSettings { strict_enrichment: true, ..RunSettings::default() });
+        assert!(service.process_posts(vec![post("2", "third")]).await.is_err());
+        assert_eq!(store.len().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_deleted_author_dropped_when_author_required() {
+        let privacy = PrivacyConfig {
+            required_fields: RequiredFields { author: true, ..RequiredFields::default() },
+            ..PrivacyConfig::default()
+        };
+        let mut deleted = post("1", "removed by author");
+        deleted.author.username = "deleted".to_string();
+        let mut kept = post("2", "still here");
+        for post in [&mut deleted, &mut kept] {
+            utils::apply_privacy_compliance(post, &privacy);
+        }
+        assert_eq!(deleted.metadata["missing_required_fields"], serde_json::json!(["author"]));
+
+        let store = Arc::new(InMemoryPostStore::new());
+        let service = IngestionService::new(Vec::new(), store.clone(), DedupScope::PerRun);
+        assert_eq!(service.process_posts(vec![deleted, kept]).await.unwrap(), 1);
+        assert_eq!(store.posts().await[0].id, "2");
+        assert_eq!(service.metrics().platform("reddit").filtered_out, 1);
+
+        // Nothing is required by default
+        let mut deleted = post("3", "removed by author");
+        deleted.author.username = "deleted".to_string();
+        utils::apply_privacy_compliance(&mut deleted, &PrivacyConfig::default());
+        assert!(!deleted.metadata.contains_key("missing_required_fields"));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_drains_buffered_and_in_flight_posts() {
+        let store = Arc::new(InMemoryPostStore::new());
+        let connector = slow_connector(Duration::from_millis(200));
+
+        let mut service = IngestionService::new(vec![connector.clone()], store.clone(), DedupScope::PerRun);
+        service.set_run_settings(RunSettings {
+            queries: vec!["flood".to_string()],
+            poll_interval: Duration::from_secs(3600),
+            batch_size: 100,
+            drain_timeout: Duration::from_secs(5),
+            ..RunSettings::default()
+        });
+        let service = Arc::new(service);
+
+        // Buffered below the batch size, so nothing is stored yet
+        service.enqueue(vec![post("1", "queued one"), post("2", "queued two")]).await.unwrap();
+        assert!(store.is_empty().await);
+
+        let runner = Arc::clone(&service);
+        let handle = tokio::spawn(async move { runner.start().await });
+
+        // Shut down while the first search is still in flight
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        service.shutdown().await.unwrap();
+        handle.await.unwrap().unwrap();
+
+        let mut ids: Vec<String> = store.posts().await.into_iter().map(|p| p.id).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["1", "2", "slow-0"]);
+        assert_eq!(connector.searches().len(), 1);
+
+        let metrics = service.metrics().platform("slow");
+        assert_eq!((metrics.api_calls_made, metrics.posts_collected), (1, 1));
+        assert_eq!(metrics.rate_limit_remaining, Some(42));
+        assert!(!service.metrics().is_running());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_concurrency_limit_serializes_searches_per_platform() {
+        let max_in_flight = |limit: Option<usize>| async move {
+            let connector = slow_connector(Duration::from_secs(1));
+            let mut service = IngestionService::new(vec![connector.clone()], Arc::new(InMemoryPostStore::new()), DedupScope::PerRun);
+            if let Some(limit) = limit {
+                service.set_max_concurrent_requests("slow", limit);
+            }
+
+            let (flood, storm) = (service.search_params("flood"), service.search_params("storm"));
+            let (first, second) = tokio::join!(service.search(0, &flood), service.search(0, &storm));
+            assert!(first.is_ok() && second.is_ok());
+            connector.max_in_flight()
+        };
+
+        assert_eq!(max_in_flight(None).await, 2);
+        assert_eq!(max_in_flight(Some(1)).await, 1);
+    }
+
+    /// Twitter stand-in whose searches hit `url`, retrying 429s via the shared helper
+    fn rate_limited_connector(url: String) -> Arc<MockConnector> {
+        let connector = MockConnector::new("twitter").with_search(move |_| {
+            let url = url.clone();
+            Box::pin(async move {
+                let client = reqwest::Client::new();
+                let response = utils::send_with_retry_after(Duration::from_secs(5), || async {
+                    client.get(&url).send().await.map_err(|e| ConnectorError::NetworkError { source: e })
+                })
+                .await?;
+                Err(ConnectorError::ApiError { code: response.status().as_u16(), message: "rate limited".to_string() })
+            })
+        });
+        Arc::new(connector)
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_retry_budget_fails_job() {
+        use crate::models::{JobParameters, JobStatus, JobType};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+            .mount(&server)
+            .await;
+
+        let run = |max_retries: u32| {
+            let server_uri = server.uri();
+            async move {
+                let connector = rate_limited_connector(server_uri);
+                let mut service = IngestionService::new(vec![connector], Arc::new(InMemoryPostStore::new()), DedupScope::PerRun);
+                service.set_run_settings(RunSettings {
+                    retry_budget: RetryBudgetConfig { max_retries, max_retry_time_secs: 60 },
+                    ..RunSettings::default()
+                });
+
+                let parameters = JobParameters { keywords: vec!["flood".to_string()], ..JobParameters::default() };
+                let mut job = IngestionJob::new(Platform::Twitter, JobType::KeywordSearch, parameters);
+                assert!(service.run_job(&mut job).await.is_err());
+                job
+            }
+        };
+
+        // With budget left the 429 is retried once, then reported
+        let job = run(1).await;
+        assert_eq!((job.status, job.retry_count), (JobStatus::Failed, 1));
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+        // Without, the job fails on the first 429 and never retries
+        let job = run(0).await;
+        assert_eq!((job.status, job.retry_count), (JobStatus::Failed, 0));
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+        assert!(job.completed_at.is_some());
+        let message = job.error_message.unwrap();
+        assert!(message.starts_with("twitter search failed: Retry budget exhausted after 0 retries"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_job_posts_are_tagged_and_deleted_by_job() {
+        use crate::models::{JobParameters, JobType};
+        use crate::storage::PostStore;
+
+        let store = Arc::new(InMemoryPostStore::new());
+        let connectors = vec![mock_connector("twitter", true, 2), mock_connector("reddit", true, 3)];
+        let service = IngestionService::new(connectors, store.clone(), DedupScope::PerRun);
+
+        let parameters = JobParameters { keywords: vec!["flood".to_string()], ..JobParameters::default() };
+        let mut twitter_job = IngestionJob::new(Platform::Twitter, JobType::KeywordSearch, parameters.clone());
+        let mut reddit_job = IngestionJob::new(Platform::Reddit, JobType::KeywordSearch, parameters);
+        service.run_job(&mut twitter_job).await.unwrap();
+        service.run_job(&mut reddit_job).await.unwrap();
+
+        let stored = store.posts().await;
+        assert_eq!(stored.len(), 5);
+        assert_eq!(stored.iter().filter(|post| post.job_id() == Some(twitter_job.id)).count(), 2);
+
+        // The tag survives a JSON round trip, as in the Postgres payload
+        let reloaded: SocialPost = serde_json::from_str(&serde_json::to_string(&stored[0]).unwrap()).unwrap();
+        assert_eq!(reloaded.job_id(), stored[0].job_id());
+
+        assert_eq!(store.delete_by_job(twitter_job.id).await.unwrap(), 2);
+        let remaining = store.posts().await;
+        assert_eq!(remaining.len(), 3);
+        assert!(remaining.iter().all(|post| post.job_id() == Some(reddit_job.id)));
+        assert!(!store.contains_content_hash(&crate::dedup::content_hash(&post("x", "twitter post 0"))).await.unwrap());
+        assert!(store.contains_content_hash(&crate::dedup::content_hash(&post("x", "reddit post 0"))).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_collection_pass_posts_are_tagged_with_its_job() {
+        use crate::storage::PostStore;
+
+        let store = Arc::new(InMemoryPostStore::new());
+        let service = IngestionService::new(vec![mock_connector("twitter", true, 2)], store.clone(), DedupScope::PerRun);
+        let first = service.ingest(&search_params("flood")).await.unwrap();
+
+        // Already-tagged posts keep their job
+        let mut tagged = post("tagged", "collected by another job");
+        let other_job = Uuid::new_v4();
+        tagged.set_job_id(other_job);
+        service.process_posts(vec![tagged]).await.unwrap();
+
+        let stored = store.posts().await;
+        assert_eq!(stored.iter().filter(|post| post.job_id() == Some(first.job_id)).count(), 2);
+        assert_eq!(stored.iter().filter(|post| post.job_id() == Some(other_job)).count(), 1);
+
+        assert_eq!(store.delete_by_job(first.job_id).await.unwrap(), 2);
+        assert_eq!(store.posts().await.len(), 1);
+    }
+
+    /// Sink that takes a second per batch
+    struct SlowSink;
+
+    #[async_trait]
+    impl Sink for SlowSink {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        async fn consume(&self, _posts: &[SocialPost]) -> anyhow::Result<()> {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            Ok(())
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_sink_blocks_fetchers_at_queue_capacity() {
+        let store = Arc::new(InMemoryPostStore::new());
+        let mut service = IngestionService::new(vec![mock_connector("mock", true, 10)], store.clone(), DedupScope::PerRun);
+        service.add_sink(Arc::new(SlowSink));
+        service.set_run_settings(RunSettings {
+            queries: vec!["flood".to_string()],
+            poll_interval: Duration::from_secs(3600),
+            batch_size: 1,
+            queue_capacity: 2,
+            drain_timeout: Duration::from_secs(60),
+            ..RunSettings::default()
+        });
+        let service = Arc::new(service);
+
+        let runner = Arc::clone(&service);
+        let handle = tokio::spawn(async move { runner.start().await });
+
+        // Two batches written, two more queued; the fetcher waits on the rest
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert_eq!(store.len().await, 2);
+        assert_eq!(service.metrics().queue_depth(), 2);
+
+        service.shutdown().await.unwrap();
+        handle.await.unwrap().unwrap();
+
+        assert_eq!(store.len().await, 10);
+        assert_eq!(service.metrics().queue_depth(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_jobs_and_lookups_are_debited_from_request_quota() {
+        use crate::models::{JobParameters, JobType};
+        use crate::quota::QuotaWindow;
+
+        let posts = ["alice", "bob"].iter()
+            .flat_map(|user| (0..3).map(move |i| {
+                let mut post = post(&format!("{}-{}", user, i), &format!("post {} by {}", i, user));
+                post.author.username = user.to_string();
+                post
+            }))
+            .collect();
+        let connector = MockConnector::new("twitter").with_posts(posts).with_trending(&["#flood", "#rain"]);
+        let mut service = IngestionService::new(vec![Arc::new(connector)], Arc::new(InMemoryPostStore::new()), DedupScope::PerRun);
+        service.set_quota("twitter", QuotaConfig { limit: 6, unit: QuotaUnit::Requests, window: QuotaWindow::Daily });
+        let remaining = || service.quota().remaining("twitter", Utc::now());
+
+        // One request per user
+        let parameters = JobParameters { users: vec!["alice".to_string(), "bob".to_string()], ..JobParameters::default() };
+        let mut job = IngestionJob::new(Platform::Twitter, JobType::UserTimeline, parameters);
+        assert_eq!(service.run_job(&mut job).await.unwrap().len(), 6);
+        assert_eq!(remaining(), Some(4));
+
+        // One for the trends, then a search per trending topic
+        let mut job = IngestionJob::new(Platform::Twitter, JobType::TrendingTopics, JobParameters::default());
+        service.run_job(&mut job).await.unwrap();
+        assert_eq!(remaining(), Some(1));
+
+        assert!(service.get_post(Platform::Twitter, "alice-0").await.unwrap().is_some());
+        assert_eq!(remaining(), Some(0));
+
+        // An exhausted quota fails the lookup without calling the connector
+        assert!(service.get_post(Platform::Twitter, "alice-1").await.is_err());
+        assert_eq!(service.metrics().platform("twitter").api_calls_made, 6);
+    }
+
+    #[tokio::test]
+    async fn test_collection_halts_at_quota_and_resumes_after_reset() {
+        use crate::quota::{QuotaEntry, QuotaUsage, QuotaWindow};
+
+        let connectors = vec![mock_connector("twitter", true, 3), mock_connector("reddit", true, 3)];
+        let mut service = IngestionService::new(connectors, Arc::new(InMemoryPostStore::new()), DedupScope::PerRun);
+        service.set_quota("twitter", QuotaConfig { limit: 5, unit: QuotaUnit::Posts, window: QuotaWindow::Daily });
+        let params = service.search_params("flood");
+
+        let report = service.ingest(&params).await.unwrap();
+        assert_eq!(report.platform("twitter").unwrap().count, 3);
+
+        // Only what's left of the quota is kept
+        let report = service.ingest(&params).await.unwrap();
+        assert_eq!(report.platform("twitter").unwrap().count, 2);
+
+        let report = service.ingest(&params).await.unwrap();
+        let twitter = report.platform("twitter").unwrap();
+        assert_eq!(twitter.status, PlatformStatus::Error);
+        assert!(twitter.error.as_deref().unwrap().contains("quota"), "{:?}", twitter.error);
+        assert_eq!(service.metrics().platform("twitter").api_calls_made, 2);
+        // Other platforms keep collecting
+        assert_eq!(report.platform("reddit").unwrap().count, 3);
+
+        // Usage saved yesterday belongs to a window that has since reset
+        let yesterday = QuotaWindow::Daily.start(Utc::now()) - chrono::Duration::days(1);
+        service.quota().restore(vec![QuotaEntry {
+            platform: "twitter".to_string(),
+            usage: QuotaUsage { window_start: yesterday, used: 5 },
+        }]);
+        let report = service.ingest(&params).await.unwrap();
+        assert_eq!(report.platform("twitter").unwrap().count, 3);
+    }
+}
@@ -7,32 +7,63 @@
  * 
  * Key Features:
  * - Asynchronous processing using Tokio runtime
- * - Structured logging with tracing
+ * - Structured logging with tracing (text, or JSON via `--log-format json`)
  * - Configuration management
- * - Graceful shutdown handling
+ * - Graceful shutdown handling (SIGINT/SIGTERM on Unix, Ctrl+C elsewhere)
+ * - Sensitivity lexicon from a file (`processing.sensitivity_lexicon`),
+ *   reloaded on SIGHUP
  * - Multi-platform data ingestion
+ * - Credential validation without ingesting (`validate` subcommand)
+ * - Prometheus metrics (/metrics) and health (/healthz) endpoints
+ * - One-shot collection to a Parquet or CSV file (`--export-parquet`, `--export-csv`)
+ * - Incremental runs across invocations via a JSON state file (`--state-file`),
+ *   which also keeps the platforms' daily/monthly quota usage
  * 
  * Author: SentinelBERT Team
  * License: MIT
  */
 
 // External crate imports for error handling, CLI parsing, and async operations
-use anyhow::Result;           // Simplified error handling with context
-use clap::Parser;             // Command-line argument parsing
+use anyhow::{Context, Result}; // Simplified error handling with context
+use futures::StreamExt;        // Polling the Twitter filtered stream
+use clap::{Parser, Subcommand, ValueEnum}; // Command-line argument parsing
+use std::path::PathBuf;        // Export file paths
 use std::sync::Arc;           // Thread-safe reference counting for shared data
 use tokio::signal;            // Async signal handling for graceful shutdown
 use tracing::{info, warn};    // Structured logging for observability
+use tracing_subscriber::EnvFilter; // RUST_LOG-based log filtering
 
 // Internal module declarations - each handles specific functionality
+mod analytics;     // Author, volume-over-time and hashtag co-occurrence summaries
+mod api_connectors; // Social media platform API clients
+mod circuit_breaker; // Fail-fast and probe-based recovery per connector
 mod config;        // Configuration management and validation
+mod dedup;         // Content deduplication across queries and runs
+mod enrich;        // Enricher hooks attaching custom fields before storage
+mod export;        // Parquet, CSV and JSON Lines export for offline analysis
 mod ingestion;     // Core ingestion service logic
+mod media;         // Media download and content-addressed archival
+mod metrics;       // Prometheus metrics and health endpoints
 mod models;        // Data models and structures
-mod platforms;     // Platform-specific API connectors
+mod quota;         // Daily/monthly per-platform collection caps
 mod rate_limiter;  // Rate limiting to respect API quotas
+mod replay;        // Reprocessing of stored raw API payloads
+mod retry_budget;  // Per-job caps on total retries and retry wait time
+mod sampling;      // FirstN / reservoir / per-minute sampling of fetched posts
+mod sinks;         // Output sinks (webhooks) fed alongside storage
 mod storage;       // Database and cache storage operations
+mod validation;    // Connector credential validation (validate subcommand)
+mod watermark;     // Since-id watermarks for incremental collection
 
 // Import main configuration and service types
-use config::Config;
+use crate::api_connectors::{ConfiguredConnector, ConnectorError, ConnectorRegistry, SocialPost};
+use crate::api_connectors::reddit::Listing;
+use crate::api_connectors::utils::{set_default_classifier, SensitivityClassifier};
+use crate::config::Config;
+use crate::dedup::DedupScope;
+use crate::models::Platform;
+use crate::storage::InMemoryPostStore;
+use crate::watermark::FileWatermarkStore;
 use ingestion::IngestionService;
 
 /**
@@ -49,14 +80,179 @@ struct Cli {
     /// Default: config.toml in current directory
     #[arg(short, long, default_value = "config.toml")]
     config: String,
+
+    /// Log output format; json emits one object per line including the
+    /// enclosing span fields (platform, query, job_id) for log aggregation
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
+    /// Run one collection pass over the configured queries, write the
+    /// results to this Parquet file and exit
+    #[arg(long, value_name = "PATH")]
+    export_parquet: Option<PathBuf>,
+
+    /// Run one collection pass over the configured queries, write the
+    /// results to this CSV file and exit (may be combined with --export-parquet)
+    #[arg(long, value_name = "PATH")]
+    export_csv: Option<PathBuf>,
+
+    /// Run one collection pass over the configured queries, streaming the
+    /// results to this JSON Lines file a page at a time as they arrive, and exit
+    #[arg(long, value_name = "PATH")]
+    export_jsonl: Option<PathBuf>,
+
+    /// Keep per-query watermarks in this JSON file, so each invocation
+    /// (e.g. from cron) only collects posts newer than the last one; loaded
+    /// at startup and written at shutdown
+    #[arg(long, value_name = "PATH")]
+    state_file: Option<PathBuf>,
+
+    /// Sensitivity level of the exported fields
+    #[arg(long, value_enum, default_value_t = Redaction::None)]
+    redaction: Redaction,
+
+    /// Optional subcommand; runs the ingestion service when omitted
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Log output format
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum LogFormat {
+    /// Human-readable lines
+    Text,
+
+    /// Newline-delimited JSON
+    Json,
+}
+
+/// Export redaction level, see export::RedactionProfile
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Redaction {
+    /// Export every field
+    None,
+
+    /// No raw metadata or media, location coarsened to 25 km
+    Partner,
+
+    /// Partner redaction plus no location, URLs, mentions or usernames
+    Public,
+}
+
+impl Redaction {
+    fn profile(self) -> export::RedactionProfile {
+        match self {
+            Redaction::None => export::RedactionProfile::default(),
+            Redaction::Partner => export::RedactionProfile::partner(),
+            Redaction::Public => export::RedactionProfile::public(),
+        }
+    }
+}
+
+/// Subcommands that run instead of the ingestion service
+#[derive(Subcommand)]
+enum Command {
+    /// Check every configured connector's credentials and exit
+    ///
+    /// Exits non-zero if any required connector fails validation.
+    Validate {
+        /// Emit machine-readable JSON instead of a table
+        #[arg(long)]
+        json: bool,
+
+        /// Only validate this platform (repeatable; aliases such as x, ig
+        /// and yt are accepted)
+        #[arg(long = "platform", value_name = "PLATFORM")]
+        platforms: Vec<Platform>,
+    },
+
+    /// Report every connector's credential validity and remaining quota and exit
+    Status {
+        /// Emit machine-readable JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Collect tweets from the Twitter filtered stream until shut down
+    ///
+    /// Replaces the account's stream rules with the given ones. Needs
+    /// Elevated API access.
+    Stream {
+        /// Filtered stream rule, e.g. "flood lang:en" (repeatable)
+        #[arg(long = "rule", value_name = "RULE", required = true)]
+        rules: Vec<String>,
+    },
+
+    /// Collect one subreddit listing (e.g. rising posts) into storage and exit
+    Listing {
+        /// Subreddit, with or without r/ (several may be joined with +)
+        subreddit: String,
+
+        #[arg(long, value_enum, default_value_t = ListingSort::Hot)]
+        sort: ListingSort,
+
+        /// Most posts to collect (Reddit's default result count when omitted)
+        #[arg(long)]
+        limit: Option<u32>,
+    },
+
+    /// Collect a Reddit post and its comment tree into storage and exit
+    Thread {
+        /// Post id, with or without its t3_ prefix
+        post_id: String,
+
+        /// Most comments to request
+        #[arg(long)]
+        limit: Option<u32>,
+    },
+}
+
+/// Subreddit listing sort, see api_connectors::reddit::Listing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ListingSort {
+    Hot,
+    New,
+    Rising,
+    Top,
+    Controversial,
+}
+
+impl ListingSort {
+    fn listing(self) -> Listing {
+        match self {
+            ListingSort::Hot => Listing::Hot,
+            ListingSort::New => Listing::New,
+            ListingSort::Rising => Listing::Rising,
+            ListingSort::Top => Listing::Top,
+            ListingSort::Controversial => Listing::Controversial,
+        }
+    }
+}
+
+/**
+ * Initialize structured logging with environment-based filtering
+ *
+ * This allows runtime log level control via RUST_LOG environment variable
+ * in either format. Example: RUST_LOG=debug cargo run -- --log-format json
+ * Logs go to stderr so subcommand output on stdout stays machine-readable.
+ */
+fn init_logging(format: LogFormat) {
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(std::io::stderr);
+
+    match format {
+        LogFormat::Text => builder.init(),
+        LogFormat::Json => builder.json().with_current_span(true).with_span_list(true).init(),
+    }
 }
 
 /**
  * Main application entry point
  * 
  * This function initializes the ingestion service with the following steps:
- * 1. Set up structured logging with tracing
- * 2. Parse command line arguments
+ * 1. Parse command line arguments
+ * 2. Set up structured logging with tracing
  * 3. Load configuration from file
  * 4. Initialize the ingestion service
  * 5. Start background workers
@@ -67,50 +263,364 @@ struct Cli {
  */
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize structured logging with environment-based filtering
-    // This allows runtime log level control via RUST_LOG environment variable
-    // Example: RUST_LOG=debug cargo run
-    tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .init();
-
     // Parse command line arguments using clap
     let cli = Cli::parse();
+
+    init_logging(cli.log_format);
     
     // Load configuration from specified TOML file
     // This includes API keys, database connections, rate limits, etc.
     let config = Config::from_file(&cli.config)?;
     info!("Configuration loaded from {}", cli.config);
 
+    // A malformed lexicon file is a startup error, not a silent fallback
+    if let Some(path) = &config.processing.sensitivity_lexicon {
+        let classifier = SensitivityClassifier::from_file(path)
+            .with_context(|| format!("Invalid sensitivity lexicon {}", path))?;
+        set_default_classifier(classifier);
+        info!("Sensitivity lexicon loaded from {}", path);
+    }
+
+    match cli.command {
+        Some(Command::Validate { json, platforms }) => return run_validate(&config, json, &platforms).await,
+        Some(Command::Status { json }) => return run_status(&config, json).await,
+        Some(Command::Stream { rules }) => return run_stream(&config, rules).await,
+        Some(Command::Listing { subreddit, sort, limit }) => {
+            let reddit = ConnectorRegistry::reddit_from_config(&config)?;
+            let posts = reddit.get_listing(&subreddit, sort.listing(), limit).await?;
+            return store_posts(&config, posts).await;
+        },
+        Some(Command::Thread { post_id, limit }) => {
+            let reddit = ConnectorRegistry::reddit_from_config(&config)?;
+            let post_id = post_id.trim_start_matches("t3_");
+            let (post, comments) = reddit.get_post_with_comments(post_id, limit).await?
+                .with_context(|| format!("Reddit post {} not found", post_id))?;
+            return store_posts(&config, std::iter::once(post).chain(comments).collect()).await;
+        },
+        None => {},
+    }
+
     // Initialize the main ingestion service with loaded configuration
     // Arc (Atomically Reference Counted) allows safe sharing across async tasks
-    let ingestion_service = Arc::new(IngestionService::new(config).await?);
+    let mut service = IngestionService::from_config(&config).await?;
+
+    // A state file replaces the database watermarks, making runs incremental,
+    // and carries quota usage over from earlier runs
+    let state = match &cli.state_file {
+        Some(path) => {
+            let mut state = FileWatermarkStore::open(path).await;
+            state.track_quota(service.quota());
+            Some(Arc::new(state))
+        },
+        None => None,
+    };
+    if let Some(state) = &state {
+        service.set_watermark_store(state.clone());
+    }
+
+    let ingestion_service = Arc::new(service);
     info!("Ingestion service initialized");
 
+    // SIGHUP swaps in a freshly read lexicon file
+    let reload_handle = config.processing.sensitivity_lexicon.clone()
+        .map(|path| tokio::spawn(reload_lexicon_on_hangup(path)));
+
+    if cli.export_parquet.is_some() || cli.export_csv.is_some() || cli.export_jsonl.is_some() {
+        let redaction = cli.redaction.profile();
+
+        // Parquet and CSV need the whole run; JSON Lines is written as it arrives
+        let buffer_all = cli.export_parquet.is_some() || cli.export_csv.is_some();
+        let jsonl = match &cli.export_jsonl {
+            Some(path) => {
+                let file = std::fs::File::create(path)
+                    .with_context(|| format!("Failed to create {}", path.display()))?;
+                let (sender, receiver) = std::sync::mpsc::channel::<SocialPost>();
+                let redaction = redaction.clone();
+                let writer = tokio::task::spawn_blocking(move || {
+                    export::write_jsonl(receiver.into_iter().map(|mut post| {
+                        redaction.apply(&mut post);
+                        post
+                    }), file)
+                });
+                Some((path, sender, writer))
+            },
+            None => None,
+        };
+
+        let mut posts = Vec::new();
+        let reports = ingestion_service.collect_once_each(|batch| {
+            let Some((_, sender, _)) = &jsonl else {
+                posts.extend(batch);
+                return Ok(());
+            };
+            if buffer_all {
+                posts.extend(batch.iter().cloned());
+            }
+            for post in batch {
+                sender.send(post).context("JSON Lines writer stopped")?;
+            }
+            Ok(())
+        }).await?;
+
+        for report in &reports {
+            for entry in &report.platforms {
+                info!("{:?} on {}: {} posts ({:?})", report.query, entry.platform, entry.count, entry.status);
+            }
+        }
+        if let Some((path, sender, writer)) = jsonl {
+            drop(sender);
+            let count = writer.await??;
+            info!("Exported {} posts to {}", count, path.display());
+        }
+        if let Some(path) = &cli.export_parquet {
+            export::write_parquet(&posts, path, &redaction)?;
+            info!("Exported {} posts to {}", posts.len(), path.display());
+        }
+        if let Some(path) = &cli.export_csv {
+            export::write_csv(&posts, path, &redaction)?;
+            info!("Exported {} posts to {}", posts.len(), path.display());
+        }
+        save_state(state.as_deref()).await?;
+        return Ok(());
+    }
+
+    // Serve /metrics and /healthz on the monitoring port
+    let metrics_handle = if config.monitoring.metrics_enabled {
+        let listener = std::net::TcpListener::bind(("0.0.0.0", config.monitoring.metrics_port))?;
+        info!("Serving metrics on port {}", config.monitoring.metrics_port);
+        Some(tokio::spawn(metrics::serve(
+            listener,
+            ingestion_service.metrics(),
+            config.monitoring.metrics_path.clone(),
+        )))
+    } else {
+        None
+    };
+
     // Start ingestion workers in a separate async task
     // This allows the main thread to handle shutdown signals
     let service_clone = Arc::clone(&ingestion_service);
-    let ingestion_handle = tokio::spawn(async move {
+    let mut ingestion_handle = tokio::spawn(async move {
         // Start the ingestion service - this runs until shutdown
         if let Err(e) = service_clone.start().await {
             warn!("Ingestion service error: {}", e);
         }
     });
 
-    // Wait for shutdown signal (Ctrl+C)
-    // This blocks the main thread until SIGINT is received
+    // Wait for shutdown signal
+    // Kubernetes sends SIGTERM rather than SIGINT, so both are handled on Unix
     info!("SentinelBERT Ingestion Service started. Press Ctrl+C to shutdown.");
-    signal::ctrl_c().await?;
-    info!("Shutdown signal received");
+    let received = wait_for_shutdown_signal().await?;
+    info!("Shutdown signal received: {}", received);
 
     // Perform graceful shutdown
-    // 1. Stop accepting new ingestion jobs
-    // 2. Complete in-flight requests
-    // 3. Close database connections
-    // 4. Clean up resources
+    // 1. Stop scheduling new searches
+    // 2. Wait (up to the drain timeout) for in-flight searches
+    // 3. Flush buffered posts to storage and sinks
+    // 4. Let the worker task finish; abort only if it overruns the drain timeout
     ingestion_service.shutdown().await?;
-    ingestion_handle.abort();
+    if tokio::time::timeout(ingestion_service.drain_timeout(), &mut ingestion_handle).await.is_err() {
+        warn!("Ingestion workers did not stop within the drain timeout, aborting");
+        ingestion_handle.abort();
+    }
+
+    if let Some(handle) = metrics_handle {
+        handle.abort();
+    }
+    if let Some(handle) = reload_handle {
+        handle.abort();
+    }
+    save_state(state.as_deref()).await?;
 
     info!("SentinelBERT Ingestion Service stopped");
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Write the `--state-file` watermarks and quota usage, if one was given
+async fn save_state(state: Option<&FileWatermarkStore>) -> Result<()> {
+    if let Some(state) = state {
+        state.persist().await.context("Failed to write state file")?;
+        info!("Saved collection state");
+    }
+    Ok(())
+}
+
+/**
+ * Wait for whichever shutdown signal arrives first
+ *
+ * Returns the signal name for logging. On Unix this listens for both SIGINT
+ * and SIGTERM; elsewhere only Ctrl+C is available.
+ */
+#[cfg(unix)]
+async fn wait_for_shutdown_signal() -> Result<&'static str> {
+    use signal::unix::{signal as unix_signal, SignalKind};
+
+    let mut sigterm = unix_signal(SignalKind::terminate())?;
+    let mut sigint = unix_signal(SignalKind::interrupt())?;
+
+    tokio::select! {
+        _ = sigterm.recv() => Ok("SIGTERM"),
+        _ = sigint.recv() => Ok("SIGINT"),
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_shutdown_signal() -> Result<&'static str> {
+    signal::ctrl_c().await?;
+    Ok("Ctrl+C")
+}
+
+/**
+ * Reload the sensitivity lexicon file on every SIGHUP
+ *
+ * A file that fails to load is logged and the lexicon in use is kept.
+ * Without Unix signals the lexicon is only read at startup.
+ */
+#[cfg(unix)]
+async fn reload_lexicon_on_hangup(path: String) {
+    use signal::unix::{signal as unix_signal, SignalKind};
+
+    let mut sighup = match unix_signal(SignalKind::hangup()) {
+        Ok(sighup) => sighup,
+        Err(e) => {
+            warn!("Cannot listen for SIGHUP, lexicon reload disabled: {}", e);
+            return;
+        },
+    };
+
+    while sighup.recv().await.is_some() {
+        match SensitivityClassifier::from_file(&path) {
+            Ok(classifier) => {
+                set_default_classifier(classifier);
+                info!("Sensitivity lexicon reloaded from {}", path);
+            },
+            Err(e) => warn!("Keeping the current sensitivity lexicon: {}", e),
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn reload_lexicon_on_hangup(_path: String) {}
+
+/**
+ * Validate connector credentials without starting ingestion
+ *
+ * Prints a table (or JSON with `--json`) of platform -> valid/invalid/error
+ * and exits with status 1 if any required connector failed. A configured
+ * Kafka sink is listed with its missing settings. With `--platform` only
+ * the named platforms are checked.
+ */
+async fn run_validate(config: &Config, json: bool, platforms: &[Platform]) -> Result<()> {
+    let registry = ConnectorRegistry::from_config(config)?;
+    let selected = |entries: &[ConfiguredConnector]| -> Vec<ConfiguredConnector> {
+        entries
+            .iter()
+            .filter(|entry| platforms.is_empty() || platforms.iter().any(|p| p.to_string() == entry.connector.platform_name()))
+            .cloned()
+            .collect()
+    };
+
+    // Skipped connectors are reported as missing credentials
+    let mut results = validation::validate_connectors(&selected(registry.entries())).await;
+    results.extend(validation::validate_connectors(&selected(registry.skipped())).await);
+
+    // Sinks aren't platforms, so a --platform filter leaves them out
+    if let Some(kafka) = config.sinks.kafka.as_ref().filter(|_| platforms.is_empty()) {
+        results.push(validation::validate_kafka(kafka));
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        print!("{}", validation::render_table(&results));
+    }
+
+    if validation::has_blocking_failures(&results) {
+        warn!("One or more required connectors failed validation");
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/**
+ * Report credential validity and remaining quota without starting ingestion
+ *
+ * Covers every enabled connector, including those skipped for missing
+ * credentials. Needs no database: the report only talks to the platforms.
+ */
+async fn run_status(config: &Config, json: bool) -> Result<()> {
+    let registry = ConnectorRegistry::from_config(config)?;
+    let connectors = registry.entries()
+        .iter()
+        .chain(registry.skipped())
+        .map(|entry| Arc::clone(&entry.connector))
+        .collect();
+
+    let service = IngestionService::new(connectors, Arc::new(InMemoryPostStore::new()), DedupScope::PerRun);
+    let statuses = service.status_report().await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&statuses)?);
+    } else {
+        print!("{}", validation::render_status_table(&statuses));
+    }
+
+    Ok(())
+}
+
+/**
+ * Feed the Twitter filtered stream into storage and sinks until shutdown
+ *
+ * Tweets go through the same dedup, enrichment and sinks as searched posts,
+ * in batches of `processing.batch_size`. A SIGINT/SIGTERM flushes the
+ * partial batch; losing stream access (401/403) flushes and fails.
+ */
+async fn run_stream(config: &Config, rules: Vec<String>) -> Result<()> {
+    let twitter = ConnectorRegistry::twitter_from_config(config)?;
+    let service = IngestionService::from_config(config).await?;
+
+    let stream = twitter.stream_posts(rules).await?;
+    tokio::pin!(stream);
+    let shutdown = wait_for_shutdown_signal();
+    tokio::pin!(shutdown);
+
+    let mut failure = None;
+    loop {
+        tokio::select! {
+            received = &mut shutdown => {
+                info!("Shutdown signal received: {}", received?);
+                break;
+            },
+            item = stream.next() => match item {
+                Some(Ok(post)) => {
+                    service.enqueue(vec![post]).await?;
+                },
+                Some(Err(e @ ConnectorError::ConfigError { .. })) => {
+                    failure = Some(e);
+                    break;
+                },
+                Some(Err(e)) => warn!("Skipping unreadable stream event: {}", e),
+                None => break,
+            },
+        }
+    }
+
+    service.shutdown().await?;
+    match failure {
+        Some(e) => Err(e.into()),
+        None => Ok(()),
+    }
+}
+
+/**
+ * Run posts fetched by a one-off subcommand through dedup, enrichment and
+ * every sink, then flush and exit
+ */
+async fn store_posts(config: &Config, posts: Vec<SocialPost>) -> Result<()> {
+    let service = IngestionService::from_config(config).await?;
+    info!("Collected {} posts", posts.len());
+    service.enqueue(posts).await?;
+    service.shutdown().await
+}
+
@@ -0,0 +1,461 @@
+/*
+ * SentinelBERT Media Archival
+ *
+ * This module downloads the images and videos attached to posts and keeps
+ * a local copy for chain-of-custody, since platform media URLs expire or
+ * get taken down.
+ *
+ * Archive Layout:
+ * - Every file is named by the SHA-256 of its bytes (lowercase hex) inside
+ *   the configured directory, so identical media is stored once and the
+ *   name itself verifies the content
+ * - Files are written to a temporary name and renamed into place, so a
+ *   crashed download never leaves a partial file under a hash name
+ *
+ * Limits and Failures:
+ * - Media over `max_bytes` is skipped, checked against Content-Length and
+ *   again while streaming for servers that don't send it
+ * - Each attachment gets its own outcome; one failed download never aborts
+ *   the rest of the post
+ *
+ * Short URLs:
+ * ShortUrlResolver (opt-in with `processing.resolve_short_urls`) replaces
+ * links on known shortener hosts (bit.ly, tinyurl.com, ...) in
+ * SocialPost.urls with where they lead, so link analysis can cluster them.
+ * - Redirects are followed with HEAD requests, at most `max_hops` of them
+ * - Only the configured hosts are resolved; Twitter's t.co links already
+ *   arrive expanded (`expanded_url`), so they rarely need a request
+ * - Each replaced link is kept under metadata["unshortened_urls"]
+ * - A link that fails or exceeds the hop limit is left as it was
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use anyhow::{bail, Context, Result};    // Simplified error handling with context
+use async_trait::async_trait;           // Enricher implementation
+use reqwest::header::{CONTENT_TYPE, LOCATION}; // Archived content type, redirect targets
+use reqwest::{redirect, Client, Url};   // Media downloads and short URL resolution
+use serde::{Deserialize, Serialize};    // Config and outcome (de)serialization
+use sha2::{Digest, Sha256};             // Content hashes for file names
+use std::path::PathBuf;                 // Archive locations
+use std::time::Duration;                // Download timeout
+use tracing::{debug, warn};             // Structured logging
+
+use crate::api_connectors::SocialPost;
+use crate::enrich::Enricher;
+
+/// Media archive settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MediaArchiveConfig {
+    /// Directory receiving the archived files
+    pub directory: PathBuf,
+
+    /// Largest file downloaded, in bytes
+    pub max_bytes: u64,
+
+    /// Seconds one download may take
+    pub request_timeout_secs: u64,
+}
+
+impl Default for MediaArchiveConfig {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("media-archive"),
+            max_bytes: 50 * 1024 * 1024,
+            request_timeout_secs: 60,
+        }
+    }
+}
+
+/// What happened to one attachment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ArchiveOutcome {
+    /// Downloaded and written to `path`
+    Stored {
+        path: PathBuf,
+        sha256: String,
+        content_type: Option<String>,
+        size: u64,
+    },
+
+    /// Larger than `max_bytes`; not downloaded
+    TooLarge { size: u64 },
+
+    /// The download or write failed
+    Failed { error: String },
+}
+
+/// Archive result for one MediaAttachment, by URL
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedMedia {
+    pub url: String,
+
+    #[serde(flatten)]
+    pub outcome: ArchiveOutcome,
+}
+
+/**
+ * MediaArchiver - Downloads post media into a content-addressed directory
+ */
+pub struct MediaArchiver {
+    client: Client,
+    config: MediaArchiveConfig,
+}
+
+impl MediaArchiver {
+    pub fn new(config: MediaArchiveConfig) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .build()
+            .context("Failed to create media download client")?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Download every attachment of a post, one outcome per attachment
+    ///
+    /// Fails only if the archive directory can't be created.
+    pub async fn archive(&self, post: &SocialPost) -> Result<Vec<ArchivedMedia>> {
+        tokio::fs::create_dir_all(&self.config.directory)
+            .await
+            .with_context(|| format!("Failed to create media archive {}", self.config.directory.display()))?;
+
+        let mut archived = Vec::with_capacity(post.media.len());
+        for attachment in &post.media {
+            let outcome = match self.download(&attachment.url).await {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    warn!("Failed to archive media of {} post {}: {:#}", post.platform, post.id, e);
+                    ArchiveOutcome::Failed { error: format!("{:#}", e) }
+                }
+            };
+            archived.push(ArchivedMedia { url: attachment.url.clone(), outcome });
+        }
+
+        Ok(archived)
+    }
+
+    /// Archive a post's media and record the results on the post
+    ///
+    /// Stored attachments get their `file_size` from the downloaded length,
+    /// and the outcomes are kept under metadata["archived_media"].
+    pub async fn archive_post(&self, post: &mut SocialPost) -> Result<Vec<ArchivedMedia>> {
+        let archived = self.archive(post).await?;
+
+        for (attachment, media) in post.media.iter_mut().zip(&archived) {
+            if let ArchiveOutcome::Stored { size, .. } = media.outcome {
+                attachment.file_size = Some(size);
+            }
+        }
+        post.metadata.insert("archived_media".to_string(), serde_json::to_value(&archived)?);
+
+        Ok(archived)
+    }
+
+    /// Download one URL into the archive
+    async fn download(&self, url: &str) -> Result<ArchiveOutcome> {
+        let mut response = self.client.get(url).send().await?.error_for_status()?;
+
+        let max_bytes = self.config.max_bytes;
+        if let Some(size) = response.content_length().filter(|size| *size > max_bytes) {
+            return Ok(ArchiveOutcome::TooLarge { size });
+        }
+
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+            if bytes.len() as u64 > max_bytes {
+                return Ok(ArchiveOutcome::TooLarge { size: bytes.len() as u64 });
+            }
+        }
+
+        let sha256 = hex::encode(Sha256::digest(&bytes));
+        let path = self.config.directory.join(&sha256);
+
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            debug!("Media {} already archived as {}", url, sha256);
+        } else {
+            let partial = self.config.directory.join(format!("{}.{}.partial", sha256, uuid::Uuid::new_v4()));
+            tokio::fs::write(&partial, &bytes).await?;
+            tokio::fs::rename(&partial, &path).await?;
+        }
+
+        Ok(ArchiveOutcome::Stored { path, sha256, content_type, size: bytes.len() as u64 })
+    }
+}
+
+/// Short URL resolution settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShortUrlConfig {
+    /// Shortener hosts whose links are resolved (subdomains included)
+    pub hosts: Vec<String>,
+
+    /// Most redirects followed for one link
+    pub max_hops: usize,
+
+    /// Seconds one HEAD request may take
+    pub request_timeout_secs: u64,
+}
+
+impl Default for ShortUrlConfig {
+    fn default() -> Self {
+        Self {
+            hosts: [
+                "bit.ly", "buff.ly", "cutt.ly", "dlvr.it", "goo.gl", "is.gd", "lnkd.in",
+                "ow.ly", "rb.gy", "shorturl.at", "t.co", "t.ly", "tinyurl.com", "trib.al",
+            ].iter().map(|host| host.to_string()).collect(),
+            max_hops: 5,
+            request_timeout_secs: 10,
+        }
+    }
+}
+
+/// A short link and where it led, as recorded in metadata["unshortened_urls"]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnshortenedUrl {
+    pub short: String,
+    pub resolved: String,
+}
+
+/**
+ * ShortUrlResolver - Replaces shortener links in posts with their destinations
+ */
+pub struct ShortUrlResolver {
+    client: Client,
+    config: ShortUrlConfig,
+}
+
+impl ShortUrlResolver {
+    pub fn new(config: ShortUrlConfig) -> Result<Self> {
+        // Redirects are followed by hand to count hops and stay on HEAD
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .redirect(redirect::Policy::none())
+            .build()
+            .context("Failed to create short URL client")?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Whether a link points at one of the configured shortener hosts
+    pub fn is_short_url(&self, url: &str) -> bool {
+        let Some(host) = Url::parse(url).ok().and_then(|url| url.host_str().map(str::to_lowercase)) else {
+            return false;
+        };
+
+        self.config.hosts.iter().any(|short| {
+            let short = short.to_lowercase();
+            host == short || host.ends_with(&format!(".{}", short))
+        })
+    }
+
+    /// Follow a link's redirects to the URL that finally answers
+    ///
+    /// Fails on request errors and when it takes more than `max_hops` redirects.
+    pub async fn resolve(&self, url: &str) -> Result<String> {
+        let mut current = Url::parse(url).with_context(|| format!("Invalid URL {}", url))?;
+
+        for _ in 0..=self.config.max_hops {
+            let response = self.client.head(current.clone()).send().await?;
+            let location = response.headers().get(LOCATION).and_then(|value| value.to_str().ok());
+
+            match location {
+                Some(location) if response.status().is_redirection() => {
+                    current = current.join(location)
+                        .with_context(|| format!("Invalid redirect from {} to {}", current, location))?;
+                },
+                _ => return Ok(current.to_string()),
+            }
+        }
+
+        bail!("{} redirected more than {} times", url, self.config.max_hops)
+    }
+
+    /// Replace the post's short links with their destinations
+    ///
+    /// Each replaced link is recorded under metadata["unshortened_urls"];
+    /// links that can't be resolved are logged and kept.
+    pub async fn resolve_short_urls(&self, post: &mut SocialPost) -> Vec<UnshortenedUrl> {
+        let mut unshortened = Vec::new();
+
+        for url in post.urls.iter_mut() {
+            if !self.is_short_url(url) {
+                continue;
+            }
+
+            match self.resolve(url).await {
+                Ok(resolved) if resolved != *url => {
+                    unshortened.push(UnshortenedUrl { short: url.clone(), resolved: resolved.clone() });
+                    *url = resolved;
+                },
+                Ok(_) => {},
+                Err(e) => warn!("Failed to resolve short URL in {} post {}: {:#}", post.platform, post.id, e),
+            }
+        }
+
+        if !unshortened.is_empty() {
+            post.metadata.insert("unshortened_urls".to_string(), serde_json::json!(unshortened));
+        }
+        unshortened
+    }
+}
+
+#[async_trait]
+impl Enricher for ShortUrlResolver {
+    fn name(&self) -> &str {
+        "resolve_short_urls"
+    }
+
+    async fn enrich(&self, post: &mut SocialPost) -> Result<()> {
+        self.resolve_short_urls(post).await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils::post;
+    use crate::api_connectors::MediaAttachment;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// 1x1 transparent PNG
+    const PIXEL: &[u8] = &[
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f, 0x15, 0xc4,
+        0x89, 0x00, 0x00, 0x00, 0x0a, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0x00, 0x01, 0x00, 0x00,
+        0x05, 0x00, 0x01, 0x0d, 0x0a, 0x2d, 0xb4, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae,
+        0x42, 0x60, 0x82,
+    ];
+
+    fn attachment(url: String) -> MediaAttachment {
+        MediaAttachment { media_type: "image".to_string(), url, alt_text: None, dimensions: None, file_size: None }
+    }
+
+    #[tokio::test]
+    async fn test_archive_stores_image_by_hash() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/pixel.png"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(PIXEL, "image/png"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/huge.mp4"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(vec![0u8; 4096], "video/mp4"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/gone.jpg"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let directory = std::env::temp_dir().join(format!("sentinel-media-{}", uuid::Uuid::new_v4()));
+        let archiver = MediaArchiver::new(MediaArchiveConfig {
+            directory: directory.clone(),
+            max_bytes: 1024,
+            ..MediaArchiveConfig::default()
+        }).unwrap();
+
+        let mut post = post("1", "evidence");
+        post.media = ["pixel.png", "huge.mp4", "gone.jpg"]
+            .iter()
+            .map(|name| attachment(format!("{}/{}", server.uri(), name)))
+            .collect();
+
+        let archived = archiver.archive_post(&mut post).await.unwrap();
+
+        let sha256 = hex::encode(Sha256::digest(PIXEL));
+        assert_eq!(archived[0].outcome, ArchiveOutcome::Stored {
+            path: directory.join(&sha256),
+            sha256: sha256.clone(),
+            content_type: Some("image/png".to_string()),
+            size: PIXEL.len() as u64,
+        });
+        assert_eq!(std::fs::read(directory.join(&sha256)).unwrap(), PIXEL);
+        assert_eq!(archived[1].outcome, ArchiveOutcome::TooLarge { size: 4096 });
+        assert!(matches!(archived[2].outcome, ArchiveOutcome::Failed { .. }));
+
+        assert_eq!(post.media[0].file_size, Some(PIXEL.len() as u64));
+        assert_eq!(post.media[1].file_size, None);
+        assert_eq!(post.metadata["archived_media"][1]["status"], "too_large");
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    fn resolver(max_hops: usize) -> ShortUrlResolver {
+        ShortUrlResolver::new(ShortUrlConfig {
+            hosts: vec!["127.0.0.1".to_string(), "bit.ly".to_string()],
+            max_hops,
+            ..ShortUrlConfig::default()
+        }).unwrap()
+    }
+
+    async fn redirect_chain() -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/abc"))
+            .respond_with(ResponseTemplate::new(301).insert_header("location", format!("{}/hop", server.uri()).as_str()))
+            .mount(&server)
+            .await;
+        // Relative redirects resolve against the current URL
+        Mock::given(method("HEAD"))
+            .and(path("/hop"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/news/flood-relief"))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/news/flood-relief"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_short_urls_resolve_through_redirect_chain() {
+        let server = redirect_chain().await;
+        let resolver = resolver(5);
+
+        let mut post = post("1", "relief camps");
+        let short = format!("{}/abc", server.uri());
+        post.urls = vec![short.clone(), "https://news.example/flood".to_string()];
+
+        let unshortened = resolver.resolve_short_urls(&mut post).await;
+
+        let resolved = format!("{}/news/flood-relief", server.uri());
+        assert_eq!(post.urls, vec![resolved.clone(), "https://news.example/flood".to_string()]);
+        assert_eq!(unshortened, vec![UnshortenedUrl { short: short.clone(), resolved: resolved.clone() }]);
+        assert_eq!(post.metadata["unshortened_urls"], serde_json::json!([{ "short": short, "resolved": resolved }]));
+
+        assert!(resolver.is_short_url("https://BIT.ly/3xyz"));
+        assert!(!resolver.is_short_url("https://notbit.ly/3xyz"));
+    }
+
+    #[tokio::test]
+    async fn test_short_url_over_hop_limit_is_kept() {
+        let server = redirect_chain().await;
+        let resolver = resolver(1);
+
+        let short = format!("{}/abc", server.uri());
+        assert!(resolver.resolve(&short).await.is_err());
+
+        let mut post = post("1", "relief camps");
+        post.urls = vec![short.clone()];
+        assert!(resolver.resolve_short_urls(&mut post).await.is_empty());
+        assert_eq!(post.urls, vec![short]);
+        assert!(!post.metadata.contains_key("unshortened_urls"));
+    }
+}
@@ -0,0 +1,336 @@
+/*
+ * SentinelBERT Ingestion Metrics
+ *
+ * This module tracks per-platform ingestion counters and serves them over
+ * HTTP for Prometheus to scrape.
+ *
+ * Endpoints:
+ * - /metrics (configurable path): Prometheus text exposition format
+ * - /healthz: 200 while the ingestion service is running, 503 otherwise
+ *
+ * Exported Series (all labeled by platform):
+ * - sentinel_ingestion_posts_collected_total (counter)
+ * - sentinel_ingestion_api_calls_made_total (counter)
+ * - sentinel_ingestion_errors_encountered_total (counter)
+ * - sentinel_ingestion_rate_limit_hits_total (counter)
+ * - sentinel_ingestion_rate_limit_remaining (gauge)
+ *
+ * Unlabeled:
+ * - sentinel_ingestion_queue_depth (gauge): posts waiting for the writer
+ *
+ * Request Latency:
+ * - connector_request_duration_seconds (histogram), labeled by platform,
+ *   operation and status (the HTTP status code, or "error" when no
+ *   response arrived). Connectors record every HTTP call, successful or
+ *   not, in one process-wide registry (`request_durations`) that every
+ *   /metrics render includes. p50/p95/p99 come from histogram_quantile.
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use anyhow::Result;                             // Simplified error handling with context
+use hyper::service::{make_service_fn, service_fn}; // Per-connection request handlers
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use std::collections::BTreeMap;                 // Stable platform ordering in output
+use std::convert::Infallible;                   // Handlers never fail
+use std::fmt::Write as _;                       // Render into a String
+use std::net::TcpListener;                      // Pre-bound listener (port 0 in tests)
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering}; // Liveness flag, queue depth
+use std::sync::{Arc, Mutex, OnceLock};          // Counters (never held across await)
+use std::time::Duration;                        // Request latencies
+
+/// Counters for one platform, mirroring the fields of IngestionMetrics
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlatformMetrics {
+    pub posts_collected: u64,
+    pub api_calls_made: u64,
+    pub errors_encountered: u64,
+    pub rate_limit_hits: u64,
+
+    /// Posts dropped for lacking required fields
+    pub filtered_out: u64,
+
+    /// Requests left in the current window, as last reported by the connector
+    pub rate_limit_remaining: Option<u32>,
+}
+
+/// Counter series: (name suffix, help text, value)
+type Counter = (&'static str, &'static str, fn(&PlatformMetrics) -> u64);
+
+const COUNTERS: [Counter; 5] = [
+    ("posts_collected_total", "Posts collected from each platform", |m| m.posts_collected),
+    ("api_calls_made_total", "API calls made to each platform", |m| m.api_calls_made),
+    ("errors_encountered_total", "Failed API calls per platform", |m| m.errors_encountered),
+    ("rate_limit_hits_total", "Rate limit rejections per platform", |m| m.rate_limit_hits),
+    ("filtered_out_total", "Posts dropped for lacking required fields", |m| m.filtered_out),
+];
+
+/// Upper bounds of the latency buckets, in seconds
+const LATENCY_BUCKETS: [f64; 12] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/// Latency distribution of one labeled series
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LatencyHistogram {
+    /// Non-cumulative count per bucket of LATENCY_BUCKETS; slower requests
+    /// only count towards `count` (the +Inf bucket)
+    pub buckets: [u64; LATENCY_BUCKETS.len()],
+    pub sum_seconds: f64,
+    pub count: u64,
+}
+
+impl LatencyHistogram {
+    fn observe(&mut self, seconds: f64) {
+        if let Some(bucket) = LATENCY_BUCKETS.iter().position(|bound| seconds <= *bound) {
+            self.buckets[bucket] += 1;
+        }
+        self.sum_seconds += seconds;
+        self.count += 1;
+    }
+}
+
+/// Series key: (platform, operation, status)
+type LatencyKey = (String, String, String);
+
+/// Connector HTTP request latencies
+#[derive(Debug, Default)]
+pub struct RequestDurations {
+    series: Mutex<BTreeMap<LatencyKey, LatencyHistogram>>,
+}
+
+impl RequestDurations {
+    pub fn record(&self, platform: &str, operation: &str, status: &str, elapsed: Duration) {
+        let key = (platform.to_string(), operation.to_string(), status.to_string());
+        self.series.lock().unwrap().entry(key).or_default().observe(elapsed.as_secs_f64());
+    }
+
+    /// Current histogram for one series, None if nothing was recorded
+    pub fn snapshot(&self, platform: &str, operation: &str, status: &str) -> Option<LatencyHistogram> {
+        let key = (platform.to_string(), operation.to_string(), status.to_string());
+        self.series.lock().unwrap().get(&key).cloned()
+    }
+
+    fn render(&self, out: &mut String) {
+        let _ = writeln!(out, "# HELP connector_request_duration_seconds Connector HTTP request latency");
+        let _ = writeln!(out, "# TYPE connector_request_duration_seconds histogram");
+        for ((platform, operation, status), histogram) in self.series.lock().unwrap().iter() {
+            let labels = format!(
+                "platform=\"{}\",operation=\"{}\",status=\"{}\"",
+                escape_label(platform), escape_label(operation), escape_label(status),
+            );
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+                cumulative += count;
+                let _ = writeln!(out, "connector_request_duration_seconds_bucket{{{},le=\"{}\"}} {}", labels, bound, cumulative);
+            }
+            let _ = writeln!(out, "connector_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}", labels, histogram.count);
+            let _ = writeln!(out, "connector_request_duration_seconds_sum{{{}}} {}", labels, histogram.sum_seconds);
+            let _ = writeln!(out, "connector_request_duration_seconds_count{{{}}} {}", labels, histogram.count);
+        }
+    }
+}
+
+/// Request latencies recorded by every connector in this process
+pub fn request_durations() -> &'static RequestDurations {
+    static DURATIONS: OnceLock<RequestDurations> = OnceLock::new();
+    DURATIONS.get_or_init(RequestDurations::default)
+}
+
+/// Shared metrics registry updated by the ingestion loop
+#[derive(Debug, Default)]
+pub struct Metrics {
+    platforms: Mutex<BTreeMap<String, PlatformMetrics>>,
+    running: AtomicBool,
+    queue_depth: AtomicUsize,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&self, platform: &str, apply: impl FnOnce(&mut PlatformMetrics)) {
+        let mut platforms = self.platforms.lock().unwrap();
+        apply(platforms.entry(platform.to_string()).or_default());
+    }
+
+    pub fn record_posts(&self, platform: &str, count: usize) {
+        self.update(platform, |m| m.posts_collected += count as u64);
+    }
+
+    pub fn record_api_call(&self, platform: &str) {
+        self.update(platform, |m| m.api_calls_made += 1);
+    }
+
+    pub fn record_error(&self, platform: &str) {
+        self.update(platform, |m| m.errors_encountered += 1);
+    }
+
+    pub fn record_rate_limit_hit(&self, platform: &str) {
+        self.update(platform, |m| m.rate_limit_hits += 1);
+    }
+
+    pub fn record_filtered_out(&self, platform: &str, count: usize) {
+        self.update(platform, |m| m.filtered_out += count as u64);
+    }
+
+    pub fn set_rate_limit_remaining(&self, platform: &str, remaining: u32) {
+        self.update(platform, |m| m.rate_limit_remaining = Some(remaining));
+    }
+
+    /// Mark the ingestion service as running (or stopped) for /healthz
+    pub fn set_running(&self, running: bool) {
+        self.running.store(running, Ordering::SeqCst);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Record how many posts are waiting in the writer queue
+    pub fn set_queue_depth(&self, depth: usize) {
+        self.queue_depth.store(depth, Ordering::SeqCst);
+    }
+
+    pub fn queue_depth(&self) -> usize {
+        self.queue_depth.load(Ordering::SeqCst)
+    }
+
+    /// Current counters for a platform
+    pub fn platform(&self, platform: &str) -> PlatformMetrics {
+        self.platforms.lock().unwrap().get(platform).cloned().unwrap_or_default()
+    }
+
+    /// Render every series in Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let platforms = self.platforms.lock().unwrap();
+
+        let mut out = String::new();
+        for (name, help, value) in COUNTERS {
+            let _ = writeln!(out, "# HELP sentinel_ingestion_{} {}", name, help);
+            let _ = writeln!(out, "# TYPE sentinel_ingestion_{} counter", name);
+            for (platform, metrics) in platforms.iter() {
+                let _ = writeln!(out, "sentinel_ingestion_{}{{platform=\"{}\"}} {}", name, escape_label(platform), value(metrics));
+            }
+        }
+
+        let _ = writeln!(out, "# HELP sentinel_ingestion_rate_limit_remaining Requests left in the current rate limit window");
+        let _ = writeln!(out, "# TYPE sentinel_ingestion_rate_limit_remaining gauge");
+        for (platform, metrics) in platforms.iter() {
+            if let Some(remaining) = metrics.rate_limit_remaining {
+                let _ = writeln!(out, "sentinel_ingestion_rate_limit_remaining{{platform=\"{}\"}} {}", escape_label(platform), remaining);
+            }
+        }
+
+        let _ = writeln!(out, "# HELP sentinel_ingestion_queue_depth Posts waiting for the writer");
+        let _ = writeln!(out, "# TYPE sentinel_ingestion_queue_depth gauge");
+        let _ = writeln!(out, "sentinel_ingestion_queue_depth {}", self.queue_depth());
+
+        request_durations().render(&mut out);
+        out
+    }
+}
+
+/// Escape a label value per the Prometheus text format
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Serve /metrics (at `metrics_path`) and /healthz until the task is dropped
+pub async fn serve(listener: TcpListener, metrics: Arc<Metrics>, metrics_path: String) -> Result<()> {
+    let make_service = make_service_fn(move |_| {
+        let metrics = Arc::clone(&metrics);
+        let metrics_path = metrics_path.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let response = route(&request, &metrics, &metrics_path);
+                async move { Ok::<_, Infallible>(response) }
+            }))
+        }
+    });
+
+    Server::from_tcp(listener)?.serve(make_service).await?;
+    Ok(())
+}
+
+fn route(request: &Request<Body>, metrics: &Metrics, metrics_path: &str) -> Response<Body> {
+    let (status, content_type, body) = match (request.method(), request.uri().path()) {
+        (&Method::GET, path) if path == metrics_path => {
+            (StatusCode::OK, "text/plain; version=0.0.4", metrics.render())
+        },
+        (&Method::GET, "/healthz") if metrics.is_running() => (StatusCode::OK, "text/plain", "ok".to_string()),
+        (&Method::GET, "/healthz") => (StatusCode::SERVICE_UNAVAILABLE, "text/plain", "stopped".to_string()),
+        _ => (StatusCode::NOT_FOUND, "text/plain", "not found".to_string()),
+    };
+
+    Response::builder()
+        .status(status)
+        .header("content-type", content_type)
+        .body(Body::from(body))
+        .expect("static response parts are valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn spawn_server(metrics: Arc<Metrics>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, metrics, "/metrics".to_string()));
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_exposes_counters() {
+        let metrics = Arc::new(Metrics::new());
+        metrics.record_api_call("twitter");
+        metrics.record_posts("twitter", 7);
+        metrics.set_rate_limit_remaining("twitter", 295);
+
+        let base = spawn_server(Arc::clone(&metrics)).await;
+        let response = reqwest::get(format!("{}/metrics", base)).await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body = response.text().await.unwrap();
+        assert!(body.contains("# TYPE sentinel_ingestion_posts_collected_total counter"));
+        assert!(body.contains("sentinel_ingestion_posts_collected_total{platform=\"twitter\"} 7"));
+        assert!(body.contains("sentinel_ingestion_rate_limit_remaining{platform=\"twitter\"} 295"));
+    }
+
+    #[tokio::test]
+    async fn test_request_latency_appears_in_histogram() {
+        request_durations().record("latency_test", "search", "200", Duration::from_millis(120));
+        request_durations().record("latency_test", "search", "200", Duration::from_millis(3));
+        request_durations().record("latency_test", "search", "error", Duration::from_secs(45));
+
+        let ok = request_durations().snapshot("latency_test", "search", "200").unwrap();
+        assert_eq!(ok.count, 2);
+        assert_eq!(ok.buckets[0], 1);
+        assert_eq!(ok.buckets[5], 1);
+        assert!((ok.sum_seconds - 0.123).abs() < 1e-9);
+        assert!(request_durations().snapshot("latency_test", "get_by_id", "200").is_none());
+
+        let base = spawn_server(Arc::new(Metrics::new())).await;
+        let body = reqwest::get(format!("{}/metrics", base)).await.unwrap().text().await.unwrap();
+        let labels = "platform=\"latency_test\",operation=\"search\"";
+        assert!(body.contains("# TYPE connector_request_duration_seconds histogram"));
+        assert!(body.contains(&format!("connector_request_duration_seconds_bucket{{{},status=\"200\",le=\"0.1\"}} 1", labels)));
+        assert!(body.contains(&format!("connector_request_duration_seconds_bucket{{{},status=\"200\",le=\"0.25\"}} 2", labels)));
+        assert!(body.contains(&format!("connector_request_duration_seconds_bucket{{{},status=\"error\",le=\"30\"}} 0", labels)));
+        assert!(body.contains(&format!("connector_request_duration_seconds_count{{{},status=\"error\"}} 1", labels)));
+    }
+
+    #[tokio::test]
+    async fn test_healthz_reflects_running_state() {
+        let metrics = Arc::new(Metrics::new());
+        let base = spawn_server(Arc::clone(&metrics)).await;
+
+        let stopped = reqwest::get(format!("{}/healthz", base)).await.unwrap();
+        assert_eq!(stopped.status(), 503);
+
+        metrics.set_running(true);
+        let running = reqwest::get(format!("{}/healthz", base)).await.unwrap();
+        assert_eq!(running.status(), 200);
+    }
+}
@@ -20,6 +20,8 @@ use serde::{Deserialize, Serialize};            // JSON serialization/deserializ
 use std::collections::HashMap;                  // Key-value mappings for flexible data
 use uuid::Uuid;                                 // Unique identifier generation
 
+use crate::api_connectors::utils::{SensitivityClassifier, SensitivityScore};
+
 /**
  * SocialPost - Core data structure representing a social media post
  * 
@@ -31,7 +33,8 @@ use uuid::Uuid;                                 // Unique identifier generation
  */
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SocialPost {
-    /// Unique identifier for this post (UUID format)
+    /// Canonical identifier "{platform}:{native id}", stable across ingestion
+    /// paths; the native id is kept in `metadata.native_id`
     pub id: String,
     
     /// Source platform (Twitter, Instagram, Reddit, etc.)
@@ -84,12 +87,15 @@ pub struct SocialPost {
  * Each platform has its own API connector and data normalization logic.
  * 
  * Adding new platforms requires:
- * 1. Adding the variant here
- * 2. Implementing the Display trait case
+ * 1. Adding the variant here and to `Platform::all`
+ * 2. Implementing the Display and FromStr cases
  * 3. Creating a platform-specific connector
  * 4. Adding API configuration
+ *
+ * Serializes as the lowercase Display name, matching what the database
+ * stores; see the serde impls below FromStr.
  */
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Platform {
     /// Twitter/X.com - Microblogging platform
     Twitter,
@@ -133,6 +139,101 @@ impl std::fmt::Display for Platform {
     }
 }
 
+impl Platform {
+    /// Every supported platform, in declaration order
+    pub fn all() -> &'static [Platform] {
+        &[
+            Platform::Twitter,
+            Platform::Instagram,
+            Platform::Reddit,
+            Platform::Facebook,
+            Platform::Telegram,
+            Platform::TikTok,
+            Platform::YouTube,
+        ]
+    }
+
+    /// Most results one API request can return (the page size limit)
+    pub fn max_results_per_request(&self) -> u32 {
+        match self {
+            Platform::Twitter => 100,   // search/recent max_results
+            Platform::Instagram => 100,
+            Platform::Reddit => 100,    // listing limit
+            Platform::Facebook => 100,
+            Platform::Telegram => 100,  // getUpdates limit
+            Platform::TikTok => 100,    // Research API max_count
+            Platform::YouTube => 50,    // search.list maxResults
+        }
+    }
+
+    /// Results requested when a search doesn't ask for a number
+    pub fn default_results(&self) -> u32 {
+        match self {
+            Platform::Twitter => 10,
+            Platform::Instagram => 25,
+            Platform::Reddit => 25,
+            Platform::Facebook => 25,
+            Platform::Telegram => 100,
+            Platform::TikTok => 20,
+            Platform::YouTube => 5,
+        }
+    }
+
+    /// Results to ask one request for: the requested number (or the
+    /// default) capped at the page size limit
+    pub fn results_per_request(&self, requested: Option<u32>) -> u32 {
+        requested
+            .unwrap_or_else(|| self.default_results())
+            .min(self.max_results_per_request())
+    }
+}
+
+/// A string that names no supported platform
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown platform '{input}' (expected one of: twitter, instagram, reddit, facebook, telegram, tiktok, youtube; or x, ig, yt)")]
+pub struct ParsePlatformError {
+    pub input: String,
+}
+
+/**
+ * FromStr implementation for Platform enum
+ *
+ * Accepts the Display names case-insensitively, plus common aliases
+ * ("x" for Twitter, "ig" for Instagram, "yt" for YouTube).
+ */
+impl std::str::FromStr for Platform {
+    type Err = ParsePlatformError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "twitter" | "x" => Ok(Platform::Twitter),
+            "instagram" | "ig" => Ok(Platform::Instagram),
+            "reddit" => Ok(Platform::Reddit),
+            "facebook" => Ok(Platform::Facebook),
+            "telegram" => Ok(Platform::Telegram),
+            "tiktok" => Ok(Platform::TikTok),
+            "youtube" | "yt" => Ok(Platform::YouTube),
+            _ => Err(ParsePlatformError { input: s.to_string() }),
+        }
+    }
+}
+
+/// Serializes as the Display name, e.g. "twitter"
+impl Serialize for Platform {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes anything FromStr accepts, including the capitalized variant
+/// names older records were written with (e.g. "Twitter")
+impl<'de> Deserialize<'de> for Platform {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EngagementMetrics {
     pub likes: u64,
@@ -144,6 +245,8 @@ pub struct EngagementMetrics {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PostMetadata {
+    /// The platform's own id for the post
+    pub native_id: String,
     pub post_type: PostType,
     pub is_verified_author: bool,
     pub is_promoted: bool,
@@ -216,7 +319,7 @@ pub enum JobType {
     FollowNetwork,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct JobParameters {
     pub keywords: Vec<String>,
     pub hashtags: Vec<String>,
@@ -235,7 +338,7 @@ pub struct DateRange {
     pub end: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum JobStatus {
     Pending,
     Running,
@@ -269,12 +372,13 @@ pub struct IngestionMetrics {
 impl SocialPost {
     pub fn new(
         platform: Platform,
+        native_id: String,
         content: String,
         author_id: String,
         author_username: String,
     ) -> Self {
         Self {
-            id: Uuid::new_v4().to_string(),
+            id: crate::api_connectors::utils::canonical_id(&platform.to_string(), &native_id),
             platform,
             content,
             author_id,
@@ -289,6 +393,7 @@ impl SocialPost {
                 reactions: HashMap::new(),
             },
             metadata: PostMetadata {
+                native_id,
                 post_type: PostType::Original,
                 is_verified_author: false,
                 is_promoted: false,
@@ -322,6 +427,13 @@ impl SocialPost {
             .collect();
     }
 
+    /// Score the content and record matched categories in content_warnings
+    pub fn classify_sensitivity(&mut self, classifier: &SensitivityClassifier) -> SensitivityScore {
+        let score = classifier.classify(&self.content);
+        self.metadata.content_warnings = score.categories.clone();
+        score
+    }
+
     pub fn calculate_engagement_score(&self) -> f64 {
         let likes_weight = 1.0;
         let shares_weight = 3.0;
@@ -337,6 +449,25 @@ impl SocialPost {
     }
 }
 
+impl IngestionJob {
+    /// A pending job with no retries allowed or used yet
+    pub fn new(platform: Platform, job_type: JobType, parameters: JobParameters) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            platform,
+            job_type,
+            parameters,
+            status: JobStatus::Pending,
+            created_at: Utc::now(),
+            started_at: None,
+            completed_at: None,
+            error_message: None,
+            retry_count: 0,
+            max_retries: 0,
+        }
+    }
+}
+
 impl UserProfile {
     pub fn calculate_influence_score(&self) -> f64 {
         let follower_ratio = if self.following_count > 0 {
@@ -371,6 +502,7 @@ mod tests {
     fn test_social_post_creation() {
         let post = SocialPost::new(
             Platform::Twitter,
+            "1816851032487211264".to_string(),
             "Test post #hashtag @mention".to_string(),
             "user123".to_string(),
             "testuser".to_string(),
@@ -380,12 +512,15 @@ mod tests {
         assert_eq!(post.content, "Test post #hashtag @mention");
         assert_eq!(post.author_id, "user123");
         assert_eq!(post.author_username, "testuser");
+        assert_eq!(post.id, "twitter:1816851032487211264");
+        assert_eq!(post.metadata.native_id, "1816851032487211264");
     }
 
     #[test]
     fn test_hashtag_extraction() {
         let mut post = SocialPost::new(
             Platform::Twitter,
+            "1".to_string(),
             "This is a #test post with #multiple #hashtags".to_string(),
             "user123".to_string(),
             "testuser".to_string(),
@@ -398,10 +533,26 @@ mod tests {
         assert!(post.hashtags.contains(&"#hashtags".to_string()));
     }
 
+    #[test]
+    fn test_classify_sensitivity_records_content_warnings() {
+        let mut post = SocialPost::new(
+            Platform::Reddit,
+            "abc123".to_string(),
+            "Content warning: g.o.r.e".to_string(),
+            "user123".to_string(),
+            "testuser".to_string(),
+        );
+
+        let score = post.classify_sensitivity(&SensitivityClassifier::default());
+        assert!(score.is_sensitive());
+        assert_eq!(post.metadata.content_warnings, vec!["violence"]);
+    }
+
     #[test]
     fn test_engagement_score_calculation() {
         let mut post = SocialPost::new(
             Platform::Twitter,
+            "2".to_string(),
             "Test post".to_string(),
             "user123".to_string(),
             "testuser".to_string(),
@@ -438,4 +589,48 @@ mod tests {
         let influence_score = profile.calculate_influence_score();
         assert!(influence_score > 0.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_platform_parses_canonical_names() {
+        for platform in Platform::all() {
+            assert_eq!(platform.to_string().parse::<Platform>(), Ok(*platform));
+        }
+        assert_eq!(" Reddit ".parse::<Platform>(), Ok(Platform::Reddit));
+        assert_eq!("TikTok".parse::<Platform>(), Ok(Platform::TikTok));
+    }
+
+    #[test]
+    fn test_platform_parses_aliases() {
+        assert_eq!("X".parse::<Platform>(), Ok(Platform::Twitter));
+        assert_eq!("yt".parse::<Platform>(), Ok(Platform::YouTube));
+        assert_eq!("IG".parse::<Platform>(), Ok(Platform::Instagram));
+    }
+
+    #[test]
+    fn test_invalid_platform_lists_valid_values() {
+        let error = "myspace".parse::<Platform>().unwrap_err();
+        assert_eq!(error.input, "myspace");
+        assert!(error.to_string().contains("twitter, instagram, reddit, facebook, telegram, tiktok, youtube"));
+    }
+
+    #[test]
+    fn test_results_per_request_defaults_and_caps() {
+        assert_eq!(Platform::Twitter.max_results_per_request(), 100);
+        assert_eq!(Platform::Twitter.results_per_request(Some(500)), 100);
+        assert_eq!(Platform::Twitter.results_per_request(Some(50)), 50);
+        assert_eq!(Platform::Twitter.results_per_request(None), 10);
+        assert_eq!(Platform::Reddit.results_per_request(None), 25);
+        assert_eq!(Platform::Reddit.results_per_request(Some(1000)), 100);
+    }
+
+    #[test]
+    fn test_platform_serde_round_trips_lowercase() {
+        assert_eq!(serde_json::to_string(&Platform::Reddit).unwrap(), r#""reddit""#);
+        assert_eq!(serde_json::from_str::<Platform>(r#""reddit""#).unwrap(), Platform::Reddit);
+
+        // Records written before the lowercase form, and aliases
+        assert_eq!(serde_json::from_str::<Platform>(r#""Reddit""#).unwrap(), Platform::Reddit);
+        assert_eq!(serde_json::from_str::<Platform>(r#""x""#).unwrap(), Platform::Twitter);
+        assert!(serde_json::from_str::<Platform>(r#""myspace""#).is_err());
+    }
+}
@@ -0,0 +1,312 @@
+/*
+ * SentinelBERT Collection Quotas
+ *
+ * This module puts a hard ceiling on how much each platform is asked for in
+ * a day or a month, independent of the rate limits, so a misconfigured
+ * query can't burn through a paid API cap (e.g. Twitter's 500k posts a
+ * month) before anyone notices.
+ *
+ * Quotas:
+ * - One quota per platform, counting either collected posts or requests
+ * - Requests are counted in the connector's quota units
+ *   (`ApiConnector::request_cost`), e.g. 100 for a YouTube search
+ * - Windows follow the UTC calendar: a daily quota resets at midnight UTC,
+ *   a monthly one at midnight UTC on the first of the month
+ * - Once the cap is reached, searches on that platform fail with
+ *   ConnectorError::QuotaExhausted until the window resets
+ *
+ * The IngestionService checks the quota before every search and records
+ * usage after it. Usage lives in memory, and is kept across runs in the
+ * `--state-file` alongside the watermarks.
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use chrono::{DateTime, Datelike, Months, TimeZone, Utc};  // Window boundaries
+use serde::{Deserialize, Serialize};    // Config and state (de)serialization
+use std::collections::HashMap;          // Per-platform limits and usage
+use std::sync::Mutex;                   // Shared counters (never held across await)
+
+use crate::api_connectors::ConnectorError;
+
+/// What a quota counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaUnit {
+    /// Posts collected
+    #[default]
+    Posts,
+
+    /// Search requests made, in the connector's quota units
+    Requests,
+}
+
+/// How often a quota resets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaWindow {
+    /// At midnight UTC
+    #[default]
+    Daily,
+
+    /// At midnight UTC on the first of each month
+    Monthly,
+}
+
+impl QuotaWindow {
+    /// Start of the window containing `now`
+    pub fn start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let day = match self {
+            Self::Daily => now.date_naive(),
+            Self::Monthly => now.date_naive().with_day(1).expect("every month has a first day"),
+        };
+        Utc.from_utc_datetime(&day.and_hms_opt(0, 0, 0).expect("midnight exists"))
+    }
+
+    /// Start of the window after the one containing `now`
+    pub fn next_start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let start = self.start(now);
+        match self {
+            Self::Daily => start + chrono::Duration::days(1),
+            Self::Monthly => start + Months::new(1),
+        }
+    }
+}
+
+/// Cap on one platform's collection
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct QuotaConfig {
+    /// Posts or requests allowed per window
+    pub limit: u64,
+
+    /// Whether `limit` counts posts or requests
+    #[serde(default)]
+    pub unit: QuotaUnit,
+
+    /// When the count resets
+    #[serde(default)]
+    pub window: QuotaWindow,
+}
+
+/// Amount of a quota used in one window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    pub window_start: DateTime<Utc>,
+    pub used: u64,
+}
+
+/// One platform's usage as written to a state file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaEntry {
+    pub platform: String,
+    #[serde(flatten)]
+    pub usage: QuotaUsage,
+}
+
+#[derive(Debug, Default)]
+struct Quotas {
+    limits: HashMap<String, QuotaConfig>,
+    usage: HashMap<String, QuotaUsage>,
+}
+
+impl Quotas {
+    /// Usage in the window containing `now`, starting over in a new window
+    fn current(&mut self, platform: &str, window: QuotaWindow, now: DateTime<Utc>) -> &mut QuotaUsage {
+        let window_start = window.start(now);
+        let usage = self.usage
+            .entry(platform.to_string())
+            .or_insert_with(|| QuotaUsage { window_start, used: 0 });
+        if usage.window_start != window_start {
+            *usage = QuotaUsage { window_start, used: 0 };
+        }
+        usage
+    }
+}
+
+/// Per-platform daily or monthly collection caps
+///
+/// Platforms without a configured quota are never limited.
+#[derive(Debug, Default)]
+pub struct DailyQuota {
+    quotas: Mutex<Quotas>,
+}
+
+impl DailyQuota {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set (or replace) the quota for a platform
+    pub fn set_limit(&self, platform: &str, config: QuotaConfig) {
+        self.quotas.lock().unwrap().limits.insert(platform.to_string(), config);
+    }
+
+    /// Fail with QuotaExhausted if the platform has used up its quota
+    pub fn check(&self, platform: &str, now: DateTime<Utc>) -> Result<(), ConnectorError> {
+        match self.remaining(platform, now) {
+            Some(0) => {
+                let quotas = self.quotas.lock().unwrap();
+                let config = &quotas.limits[platform];
+                Err(ConnectorError::QuotaExhausted {
+                    platform: platform.to_string(),
+                    limit: config.limit,
+                    resets_at: config.window.next_start(now),
+                })
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail with QuotaExhausted if a request costing `cost` units would go
+    /// over a request quota (or the quota is used up)
+    pub fn check_request(&self, platform: &str, cost: u32, now: DateTime<Utc>) -> Result<(), ConnectorError> {
+        self.check(platform, now)?;
+
+        let quotas = self.quotas.lock().unwrap();
+        let Some(config) = quotas.limits.get(platform).filter(|config| config.unit == QuotaUnit::Requests) else {
+            return Ok(());
+        };
+        let used = quotas.usage.get(platform)
+            .filter(|usage| usage.window_start == config.window.start(now))
+            .map_or(0, |usage| usage.used);
+        if used + u64::from(cost) > config.limit {
+            return Err(ConnectorError::QuotaExhausted {
+                platform: platform.to_string(),
+                limit: config.limit,
+                resets_at: config.window.next_start(now),
+            });
+        }
+        Ok(())
+    }
+
+    /// Posts or requests left in the current window, None when unlimited
+    pub fn remaining(&self, platform: &str, now: DateTime<Utc>) -> Option<u64> {
+        let mut quotas = self.quotas.lock().unwrap();
+        let config = quotas.limits.get(platform)?.clone();
+        let usage = quotas.current(platform, config.window, now);
+        Some(config.limit.saturating_sub(usage.used))
+    }
+
+    /// Units the quota counts for a platform, None when unlimited
+    pub fn unit(&self, platform: &str) -> Option<QuotaUnit> {
+        self.quotas.lock().unwrap().limits.get(platform).map(|config| config.unit)
+    }
+
+    /// Count a request costing `cost` units against a request-based quota
+    pub fn record_request(&self, platform: &str, cost: u32, now: DateTime<Utc>) {
+        self.record(platform, QuotaUnit::Requests, u64::from(cost), now);
+    }
+
+    /// Count collected posts against a post-based quota
+    pub fn record_posts(&self, platform: &str, posts: u64, now: DateTime<Utc>) {
+        self.record(platform, QuotaUnit::Posts, posts, now);
+    }
+
+    fn record(&self, platform: &str, unit: QuotaUnit, amount: u64, now: DateTime<Utc>) {
+        let mut quotas = self.quotas.lock().unwrap();
+        let Some(config) = quotas.limits.get(platform).cloned() else {
+            return;
+        };
+        if config.unit == unit {
+            quotas.current(platform, config.window, now).used += amount;
+        }
+    }
+
+    /// Usage of every platform, sorted by platform, for saving
+    pub fn snapshot(&self) -> Vec<QuotaEntry> {
+        let quotas = self.quotas.lock().unwrap();
+        let mut entries: Vec<QuotaEntry> = quotas.usage
+            .iter()
+            .map(|(platform, usage)| QuotaEntry { platform: platform.clone(), usage: usage.clone() })
+            .collect();
+        entries.sort_by(|a, b| a.platform.cmp(&b.platform));
+        entries
+    }
+
+    /// Load usage saved by `snapshot`, replacing what is recorded
+    ///
+    /// Usage from a past window is kept as is and reset on first use.
+    pub fn restore(&self, entries: Vec<QuotaEntry>) {
+        let mut quotas = self.quotas.lock().unwrap();
+        for entry in entries {
+            quotas.usage.insert(entry.platform, entry.usage);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_follow_the_utc_calendar() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 31, 18, 30, 0).unwrap();
+
+        assert_eq!(QuotaWindow::Daily.start(now), Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap());
+        assert_eq!(QuotaWindow::Daily.next_start(now), Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap());
+        assert_eq!(QuotaWindow::Monthly.start(now), Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(QuotaWindow::Monthly.next_start(now), Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_quota_exhausts_and_resets_with_the_window() {
+        let quota = DailyQuota::new();
+        quota.set_limit("twitter", QuotaConfig { limit: 2, unit: QuotaUnit::Requests, window: QuotaWindow::Daily });
+        let morning = Utc.with_ymd_and_hms(2024, 3, 10, 9, 0, 0).unwrap();
+
+        quota.record_request("twitter", 1, morning);
+        // Posts don't count against a request quota, and unlimited platforms are ignored
+        quota.record_posts("twitter", 500, morning);
+        quota.record_request("reddit", 1, morning);
+        assert_eq!(quota.remaining("twitter", morning), Some(1));
+        assert_eq!(quota.remaining("reddit", morning), None);
+        assert!(quota.check("twitter", morning).is_ok());
+
+        quota.record_request("twitter", 1, morning);
+        match quota.check("twitter", morning) {
+            Err(ConnectorError::QuotaExhausted { platform, limit, resets_at }) => {
+                assert_eq!(platform, "twitter");
+                assert_eq!(limit, 2);
+                assert_eq!(resets_at, Utc.with_ymd_and_hms(2024, 3, 11, 0, 0, 0).unwrap());
+            },
+            other => panic!("expected QuotaExhausted, got {:?}", other),
+        }
+
+        // Saved usage survives a restart, and resets once the day is over
+        let restarted = DailyQuota::new();
+        restarted.set_limit("twitter", QuotaConfig { limit: 2, unit: QuotaUnit::Requests, window: QuotaWindow::Daily });
+        restarted.restore(quota.snapshot());
+        assert!(restarted.check("twitter", morning).is_err());
+        assert!(restarted.check("twitter", morning + chrono::Duration::days(1)).is_ok());
+        assert_eq!(restarted.remaining("twitter", morning + chrono::Duration::days(1)), Some(2));
+    }
+
+    #[test]
+    fn test_requests_are_debited_by_their_cost() {
+        use crate::api_connectors::{ApiConnector, Operation, PrivacyConfig, RedditConnector, YouTubeConnector};
+
+        let youtube = YouTubeConnector::new("key".to_string(), PrivacyConfig::default());
+        let reddit = RedditConnector::new("id".to_string(), "secret".to_string(), PrivacyConfig::default());
+        assert_eq!(youtube.request_cost(Operation::Search), 100);
+        assert_eq!(youtube.request_cost(Operation::GetById), 1);
+        assert_eq!(reddit.request_cost(Operation::Search), 1);
+
+        let quota = DailyQuota::new();
+        quota.set_limit("youtube", QuotaConfig { limit: 250, unit: QuotaUnit::Requests, window: QuotaWindow::Daily });
+        let now = Utc.with_ymd_and_hms(2024, 3, 10, 9, 0, 0).unwrap();
+
+        for _ in 0..2 {
+            quota.check_request("youtube", youtube.request_cost(Operation::Search), now).unwrap();
+            quota.record_request("youtube", youtube.request_cost(Operation::Search), now);
+        }
+        assert_eq!(quota.remaining("youtube", now), Some(50));
+
+        // A third search would overshoot the cap; a video lookup still fits
+        assert!(matches!(
+            quota.check_request("youtube", youtube.request_cost(Operation::Search), now),
+            Err(ConnectorError::QuotaExhausted { limit: 250, .. })
+        ));
+        assert!(quota.check_request("youtube", youtube.request_cost(Operation::GetById), now).is_ok());
+    }
+}
@@ -0,0 +1,284 @@
+/*
+ * SentinelBERT Rate Limit Scheduler
+ *
+ * This module coordinates API requests across concurrent tasks. Each
+ * connector used to track its own limit, which doesn't stop several tasks
+ * searching the same platform from tripping it together. The scheduler owns
+ * one token bucket per platform, and connectors call `acquire` before every
+ * request.
+ *
+ * Token Buckets:
+ * - A bucket holds up to `requests` tokens and refills at requests/window,
+ *   so a full window's budget can be spent in a burst but not exceeded
+ * - Waiters are served in arrival order; each takes one token
+ * - Rate limit headers from responses can lower the token count, and a
+ *   reported remaining of 0 blocks the bucket until the reported reset
+ * - Platforms without a bucket are not throttled
+ * - `estimate` predicts how long a number of requests will take from the
+ *   bucket's current state, e.g. to plan a backfill
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use chrono::{DateTime, Utc};        // Reset times reported by APIs
+use serde::{Deserialize, Serialize}; // Bucket settings in config
+use std::collections::HashMap;      // Buckets keyed by platform
+use std::sync::Mutex;               // Bucket state (never held across await)
+use std::time::Duration;
+use tokio::time::{sleep, Instant};  // Waiting for refills
+use tracing::debug;                 // Throttling diagnostics
+
+/// Budget for one platform: `requests` per `window_secs`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    pub requests: u32,
+    pub window_secs: u64,
+}
+
+impl RateLimitConfig {
+    /// Twitter API v2 Essential Access search: 300 requests per 15 minutes
+    pub const TWITTER: Self = Self { requests: 300, window_secs: 900 };
+
+    /// Reddit OAuth API: 100 requests per minute
+    pub const REDDIT: Self = Self { requests: 100, window_secs: 60 };
+
+    /// Replace the request count and/or window where set
+    pub fn with_overrides(self, requests: Option<u32>, window_secs: Option<u64>) -> Self {
+        Self {
+            requests: requests.unwrap_or(self.requests),
+            window_secs: window_secs.unwrap_or(self.window_secs),
+        }
+    }
+
+    /// Time for one token to refill
+    fn refill_interval(&self) -> Duration {
+        Duration::from_secs(self.window_secs) / self.requests.max(1)
+    }
+}
+
+/// Token bucket state for one platform
+#[derive(Debug)]
+struct TokenBucket {
+    config: RateLimitConfig,
+    tokens: f64,
+    last_refill: Instant,
+
+    /// Set when the API reported the limit exhausted
+    blocked_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            tokens: f64::from(config.requests),
+            last_refill: Instant::now(),
+            blocked_until: None,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let refilled = elapsed.as_secs_f64() / self.config.refill_interval().as_secs_f64();
+        self.tokens = (self.tokens + refilled).min(f64::from(self.config.requests));
+        self.last_refill = now;
+    }
+
+    /// Time until a token can be taken (zero if one is available now)
+    fn wait_time(&self, now: Instant) -> Duration {
+        if let Some(blocked_until) = self.blocked_until {
+            if blocked_until > now {
+                return blocked_until - now;
+            }
+        }
+
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            self.config.refill_interval().mul_f64(1.0 - self.tokens)
+        }
+    }
+}
+
+/// A platform's bucket plus the queue of tasks waiting on it
+#[derive(Debug)]
+struct Bucket {
+    /// Held by the task at the front of the queue while it waits for a token
+    turnstile: tokio::sync::Mutex<()>,
+    state: Mutex<TokenBucket>,
+}
+
+/// Per-platform token buckets shared by all connectors
+#[derive(Debug, Default)]
+pub struct RateLimitScheduler {
+    buckets: HashMap<String, Bucket>,
+}
+
+impl RateLimitScheduler {
+    /// Scheduler with no buckets (nothing is throttled)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scheduler with the default Twitter and Reddit budgets
+    pub fn with_platform_defaults() -> Self {
+        Self::new()
+            .with_limit("twitter", RateLimitConfig::TWITTER)
+            .with_limit("reddit", RateLimitConfig::REDDIT)
+    }
+
+    /// Add (or replace) a platform's bucket, starting full
+    pub fn with_limit(mut self, platform: &str, config: RateLimitConfig) -> Self {
+        self.buckets.insert(platform.to_string(), Bucket {
+            turnstile: tokio::sync::Mutex::new(()),
+            state: Mutex::new(TokenBucket::new(config)),
+        });
+        self
+    }
+
+    /// Budget configured for a platform
+    pub fn limit(&self, platform: &str) -> Option<RateLimitConfig> {
+        self.buckets.get(platform).map(|bucket| bucket.state.lock().unwrap().config)
+    }
+
+    /// Wait for and take one request token for `platform`
+    pub async fn acquire(&self, platform: &str) {
+        let Some(bucket) = self.buckets.get(platform) else {
+            return;
+        };
+
+        // tokio's Mutex is fair, so waiters take tokens in arrival order
+        let _turn = bucket.turnstile.lock().await;
+        loop {
+            let wait = {
+                let mut state = bucket.state.lock().unwrap();
+                let now = Instant::now();
+                state.refill(now);
+
+                let wait = state.wait_time(now);
+                if wait.is_zero() {
+                    state.tokens -= 1.0;
+                    return;
+                }
+                wait
+            };
+
+            debug!("{} rate limit budget exhausted, waiting {:?}", platform, wait);
+            sleep(wait).await;
+        }
+    }
+
+    /// Expected time to make `total_requests` requests from now
+    ///
+    /// Uses the platform's current tokens (including any reported
+    /// remaining count and block until reset) and refill rate, with
+    /// requests at least `min_spacing` apart.
+    pub fn estimate(&self, platform: &str, total_requests: u32, min_spacing: Duration) -> Duration {
+        if total_requests == 0 {
+            return Duration::ZERO;
+        }
+        let spacing = min_spacing * (total_requests - 1);
+
+        let Some(bucket) = self.buckets.get(platform) else {
+            return spacing;
+        };
+
+        let state = bucket.state.lock().unwrap();
+        let now = Instant::now();
+        let interval = state.config.refill_interval().as_secs_f64();
+        let capacity = f64::from(state.config.requests);
+
+        // Tokens keep refilling while blocked, as in `acquire`
+        let start = state.blocked_until.map_or(Duration::ZERO, |until| until.saturating_duration_since(now));
+        let elapsed = (now.saturating_duration_since(state.last_refill) + start).as_secs_f64();
+        let tokens = (state.tokens + elapsed / interval).min(capacity);
+
+        let shortfall = (f64::from(total_requests) - tokens).max(0.0);
+        start + Duration::from_secs_f64(shortfall * interval).max(spacing)
+    }
+
+    /// Apply rate limit headers from an API response
+    ///
+    /// Lowers the bucket to the reported `remaining`; when that is zero, no
+    /// tokens are handed out until `reset_time`.
+    pub fn update(&self, platform: &str, remaining: u32, reset_time: DateTime<Utc>) {
+        let Some(bucket) = self.buckets.get(platform) else {
+            return;
+        };
+
+        let mut state = bucket.state.lock().unwrap();
+        let now = Instant::now();
+        state.refill(now);
+        state.tokens = state.tokens.min(f64::from(remaining));
+
+        if remaining == 0 {
+            if let Ok(until_reset) = (reset_time - Utc::now()).to_std() {
+                state.blocked_until = Some(now + until_reset);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_concurrent_acquires_serialize_under_tight_budget() {
+        // 2 requests per 10s: a token every 5s after the initial burst of 2
+        let scheduler = Arc::new(
+            RateLimitScheduler::new().with_limit("twitter", RateLimitConfig { requests: 2, window_secs: 10 }),
+        );
+        let started = Instant::now();
+
+        let tasks: Vec<_> = (0..5)
+            .map(|_| {
+                let scheduler = Arc::clone(&scheduler);
+                tokio::spawn(async move {
+                    scheduler.acquire("twitter").await;
+                    started.elapsed()
+                })
+            })
+            .collect();
+
+        let mut granted = Vec::new();
+        for task in tasks {
+            granted.push(task.await.unwrap().as_secs());
+        }
+        granted.sort_unstable();
+
+        assert_eq!(granted, vec![0, 0, 5, 10, 15]);
+
+        // Unknown platforms are never throttled
+        scheduler.acquire("telegram").await;
+        assert_eq!(started.elapsed().as_secs(), 15);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_exhausted_headers_block_until_reset() {
+        let scheduler = RateLimitScheduler::with_platform_defaults();
+        let started = Instant::now();
+
+        scheduler.update("reddit", 0, Utc::now() + chrono::Duration::seconds(30));
+        scheduler.acquire("reddit").await;
+
+        assert!(started.elapsed() >= Duration::from_secs(29));
+        assert_eq!(scheduler.limit("reddit"), Some(RateLimitConfig::REDDIT));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_estimate_accounts_for_spacing_and_block() {
+        let scheduler = RateLimitScheduler::with_platform_defaults();
+        let spacing = Duration::from_millis(600);
+
+        assert_eq!(scheduler.estimate("reddit", 0, spacing), Duration::ZERO);
+        assert_eq!(scheduler.estimate("telegram", 11, spacing), Duration::from_secs(6));
+
+        // Blocked for 30s: the same refill-limited run starts 30s later
+        scheduler.update("reddit", 0, Utc::now() + chrono::Duration::seconds(30));
+        let estimate = scheduler.estimate("reddit", 200, spacing);
+        assert!(estimate >= Duration::from_secs(149) && estimate <= Duration::from_secs(150), "{:?}", estimate);
+    }
+}
@@ -0,0 +1,134 @@
+/*
+ * SentinelBERT Retry Budget
+ *
+ * This module caps how much retrying one job may do in total, so an
+ * endpoint that keeps returning retryable errors can't hold a worker
+ * forever. Each job gets a RetryBudget limiting both the number of retries
+ * and the total time spent waiting before them, across all its requests.
+ *
+ * Scope:
+ * The IngestionService runs each job inside `RetryBudget::scope`, and the
+ * connectors' retry helpers look the budget up with `current()`, the same
+ * way job ids reach connector logs through the tracing span. Work outside
+ * a scope retries without a budget.
+ *
+ * Once spent, the next retry fails with ConnectorError::RetryBudgetExhausted,
+ * which is not retryable.
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use serde::Deserialize;                 // Config deserialization
+use std::future::Future;                // Scoped job futures
+use std::sync::{Arc, Mutex};            // Shared per-job counters (never held across await)
+use std::time::Duration;                // Retry wait accounting
+
+use crate::api_connectors::ConnectorError;
+
+tokio::task_local! {
+    /// Budget of the job currently running on this task
+    static CURRENT: Arc<RetryBudget>;
+}
+
+/// Retry limits applied to each job
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct RetryBudgetConfig {
+    /// Retries allowed per job, across all its requests
+    pub max_retries: u32,
+
+    /// Seconds a job may spend waiting before retries
+    pub max_retry_time_secs: u64,
+}
+
+impl Default for RetryBudgetConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 10,
+            max_retry_time_secs: 300,
+        }
+    }
+}
+
+/// Retries spent so far by one job
+#[derive(Debug, Default)]
+struct Spent {
+    retries: u32,
+    retry_time: Duration,
+}
+
+/// Retry allowance shared by every request of one job
+#[derive(Debug)]
+pub struct RetryBudget {
+    config: RetryBudgetConfig,
+    spent: Mutex<Spent>,
+}
+
+impl RetryBudget {
+    pub fn new(config: RetryBudgetConfig) -> Self {
+        Self { config, spent: Mutex::default() }
+    }
+
+    /// Budget of the job running on this task, if any
+    pub fn current() -> Option<Arc<RetryBudget>> {
+        CURRENT.try_with(Arc::clone).ok()
+    }
+
+    /// Run `job` with this budget as the current one
+    pub async fn scope<F: Future>(self: Arc<Self>, job: F) -> F::Output {
+        CURRENT.scope(self, job).await
+    }
+
+    /// Reserve one retry after waiting `wait`
+    ///
+    /// Fails without reserving anything when the retry would exceed either
+    /// limit; the caller should give up instead of retrying.
+    pub fn spend(&self, wait: Duration) -> Result<(), ConnectorError> {
+        let mut spent = self.spent.lock().unwrap();
+        let retry_time = spent.retry_time + wait;
+
+        if spent.retries >= self.config.max_retries
+            || retry_time > Duration::from_secs(self.config.max_retry_time_secs)
+        {
+            return Err(ConnectorError::RetryBudgetExhausted {
+                retries: spent.retries,
+                retry_time: spent.retry_time,
+            });
+        }
+
+        spent.retries += 1;
+        spent.retry_time = retry_time;
+        Ok(())
+    }
+
+    /// Retries reserved so far
+    pub fn retries(&self) -> u32 {
+        self.spent.lock().unwrap().retries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_budget_caps_count_and_time_within_scope() {
+        let budget = Arc::new(RetryBudget::new(RetryBudgetConfig { max_retries: 2, max_retry_time_secs: 10 }));
+        assert!(RetryBudget::current().is_none());
+
+        Arc::clone(&budget).scope(async {
+            let current = RetryBudget::current().unwrap();
+            current.spend(Duration::from_secs(4)).unwrap();
+
+            // Would push waiting past 10s
+            assert!(matches!(current.spend(Duration::from_secs(7)), Err(ConnectorError::RetryBudgetExhausted { retries: 1, .. })));
+
+            current.spend(Duration::from_secs(6)).unwrap();
+            assert!(current.spend(Duration::ZERO).is_err());
+        })
+        .await;
+
+        assert_eq!(budget.retries(), 2);
+    }
+}
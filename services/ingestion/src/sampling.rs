@@ -0,0 +1,258 @@
+/*
+ * SentinelBERT Ingestion Sampling
+ *
+ * This module thins the stream of fetched posts before it reaches
+ * deduplication and the sinks, for jobs that only need a representative
+ * slice of a high-volume query.
+ *
+ * Strategies:
+ * - None: keep everything (default)
+ * - FirstN: keep the first `limit` posts; the service stops fetching
+ *   further pages and platforms once the cap is met, saving API quota
+ * - Reservoir: keep a uniform random sample of `size` posts from a stream
+ *   of unknown length (Vitter's algorithm R)
+ * - RatePerMinute: keep at most `limit` posts per wall-clock minute,
+ *   shared across every search of the service
+ *
+ * FirstN and Reservoir apply per job: one collection pass, one backfill,
+ * or one scheduled search.
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use rand::rngs::StdRng;                 // Seedable RNG for reproducible samples
+use rand::{Rng, SeedableRng};           // Random replacement index
+use serde::{Deserialize, Serialize};    // Strategy configuration
+use std::sync::{Arc, Mutex};            // Rate window shared across searches
+use std::time::Duration;                // Rate window length
+use tokio::time::Instant;               // Rate window start (follows paused test time)
+
+/// How fetched posts are sampled before storage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum SamplingStrategy {
+    /// Keep every post
+    #[default]
+    None,
+
+    /// Keep the first `limit` posts and stop fetching once reached
+    FirstN { limit: usize },
+
+    /// Keep a uniform random sample of `size` posts
+    Reservoir { size: usize },
+
+    /// Keep at most `limit` posts per minute
+    RatePerMinute { limit: usize },
+}
+
+/// Posts admitted during the current minute, for RatePerMinute
+#[derive(Debug)]
+pub struct RateWindow {
+    started: Instant,
+    admitted: usize,
+}
+
+impl Default for RateWindow {
+    fn default() -> Self {
+        Self { started: Instant::now(), admitted: 0 }
+    }
+}
+
+impl RateWindow {
+    const LENGTH: Duration = Duration::from_secs(60);
+
+    /// Admit one post if the current minute has room, starting a new minute as needed
+    fn admit(&mut self, limit: usize) -> bool {
+        let now = Instant::now();
+        if now.duration_since(self.started) >= Self::LENGTH {
+            self.started = now;
+            self.admitted = 0;
+        }
+
+        if self.admitted < limit {
+            self.admitted += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/**
+ * Sampler - Applies a SamplingStrategy to one job's stream of items
+ */
+pub struct Sampler<T> {
+    strategy: SamplingStrategy,
+
+    /// Items offered so far
+    seen: usize,
+
+    /// Items currently in the sample
+    kept: Vec<T>,
+
+    /// Items already handed out by `take_settled`
+    taken: usize,
+
+    rng: StdRng,
+
+    /// Per-minute admissions, shared with other samplers of the same service
+    rate: Arc<Mutex<RateWindow>>,
+}
+
+impl<T> Sampler<T> {
+    /// Sampler with its own rate window and an entropy-seeded RNG
+    pub fn new(strategy: SamplingStrategy) -> Self {
+        Self {
+            strategy,
+            seen: 0,
+            kept: Vec::new(),
+            taken: 0,
+            rng: StdRng::from_entropy(),
+            rate: Arc::default(),
+        }
+    }
+
+    /// Use a fixed seed, for reproducible reservoir samples
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Count RatePerMinute admissions against a shared window
+    pub fn with_rate_window(mut self, rate: Arc<Mutex<RateWindow>>) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    /// Whether a FirstN cap has been met, so fetching can stop
+    pub fn is_full(&self) -> bool {
+        self.remaining() == Some(0)
+    }
+
+    /// Items a FirstN cap still has room for; None for other strategies
+    pub fn remaining(&self) -> Option<usize> {
+        match self.strategy {
+            SamplingStrategy::FirstN { limit } => Some(limit.saturating_sub(self.taken + self.kept.len())),
+            _ => None,
+        }
+    }
+
+    /// Offer one item to the sample
+    pub fn offer(&mut self, item: T) {
+        self.seen += 1;
+
+        match self.strategy {
+            SamplingStrategy::None => self.kept.push(item),
+            SamplingStrategy::FirstN { .. } => {
+                if !self.is_full() {
+                    self.kept.push(item);
+                }
+            },
+            SamplingStrategy::Reservoir { size } => {
+                // Algorithm R: the i-th item replaces a random slot with probability size/i
+                if self.kept.len() < size {
+                    self.kept.push(item);
+                } else {
+                    let slot = self.rng.gen_range(0..self.seen);
+                    if slot < size {
+                        self.kept[slot] = item;
+                    }
+                }
+            },
+            SamplingStrategy::RatePerMinute { limit } => {
+                if self.rate.lock().unwrap().admit(limit) {
+                    self.kept.push(item);
+                }
+            },
+        }
+    }
+
+    /// Offer every item in turn
+    pub fn extend(&mut self, items: impl IntoIterator<Item = T>) {
+        for item in items {
+            self.offer(item);
+        }
+    }
+
+    /// Number of items offered so far
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /// Take the sampled items whose place in the sample is final
+    ///
+    /// That is every kept item, except under Reservoir where later items can
+    /// still replace them; those stay until `finish`.
+    pub fn take_settled(&mut self) -> Vec<T> {
+        if matches!(self.strategy, SamplingStrategy::Reservoir { .. }) {
+            return Vec::new();
+        }
+        self.taken += self.kept.len();
+        std::mem::take(&mut self.kept)
+    }
+
+    /// The sampled items not yet taken
+    pub fn finish(self) -> Vec<T> {
+        self.kept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reservoir_keeps_exactly_k_uniformly() {
+        let (k, n, trials) = (5, 50, 20_000);
+        let mut hits = vec![0usize; n];
+
+        for trial in 0..trials {
+            let mut sampler = Sampler::new(SamplingStrategy::Reservoir { size: k }).with_seed(trial as u64);
+            sampler.extend(0..n);
+            assert_eq!(sampler.seen(), n);
+
+            let sample = sampler.finish();
+            assert_eq!(sample.len(), k);
+            for item in sample {
+                hits[item] += 1;
+            }
+        }
+
+        // Each item is expected trials * k / n = 2000 times
+        let expected = (trials * k / n) as f64;
+        for (item, count) in hits.iter().enumerate() {
+            let deviation = (*count as f64 - expected).abs() / expected;
+            assert!(deviation < 0.1, "item {} sampled {} times, expected ~{}", item, count, expected);
+        }
+
+        // A short stream is kept whole
+        let mut short = Sampler::new(SamplingStrategy::Reservoir { size: k });
+        short.extend(0..3);
+        assert_eq!(short.finish(), vec![0, 1, 2]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_first_n_and_rate_per_minute() {
+        let mut first = Sampler::new(SamplingStrategy::FirstN { limit: 2 });
+        first.offer("a");
+        assert!(!first.is_full());
+        first.extend(["b", "c"]);
+        assert!(first.is_full());
+        assert_eq!(first.finish(), vec!["a", "b"]);
+
+        // Two samplers sharing a window share the per-minute budget
+        let window = Arc::new(Mutex::new(RateWindow::default()));
+        let strategy = SamplingStrategy::RatePerMinute { limit: 3 };
+        let mut one = Sampler::new(strategy).with_rate_window(window.clone());
+        one.extend(0..2);
+        let mut two = Sampler::new(strategy).with_rate_window(window.clone());
+        two.extend(2..5);
+        assert_eq!((one.finish(), two.finish()), (vec![0, 1], vec![2]));
+
+        tokio::time::advance(Duration::from_secs(60)).await;
+        let mut next_minute = Sampler::new(strategy).with_rate_window(window);
+        next_minute.extend(5..10);
+        assert_eq!(next_minute.finish(), vec![5, 6, 7]);
+    }
+}
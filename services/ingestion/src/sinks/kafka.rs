@@ -0,0 +1,335 @@
+/*
+ * =============================================================================
+ * Kafka Sink for SentinelBERT
+ * =============================================================================
+ *
+ * This module publishes ingested posts to a Kafka topic for streaming
+ * consumers (e.g. the BERT scoring service).
+ *
+ * Message Format:
+ * - One message per post; the value is the post serialized as JSON
+ * - Key is `{platform}:{id}`, so every version of a post lands on the same
+ *   partition and stays ordered
+ * - Message timestamp is the post's created_at (milliseconds since epoch)
+ *
+ * Delivery Semantics:
+ * - `consume` waits for every message of the batch to be acknowledged (or
+ *   to time out) and fails if any could not be delivered
+ * - `flush` on shutdown waits up to `flush_timeout_secs` for messages still
+ *   queued in the producer
+ *
+ * Configuration:
+ * - brokers / topic fall back to KAFKA_BROKERS / KAFKA_TOPIC when empty
+ * - compression: none, lz4 or zstd
+ *
+ * =============================================================================
+ */
+
+use super::Sink;
+use crate::api_connectors::{ConfigRequirement, SocialPost};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use futures::future::join_all;
+use rdkafka::config::ClientConfig;
+use rdkafka::producer::{FutureProducer, FutureRecord, Producer};
+use rdkafka::util::Timeout;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Message compression codec
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KafkaCompression {
+    #[default]
+    None,
+    Lz4,
+    Zstd,
+}
+
+impl KafkaCompression {
+    /// Value for librdkafka's `compression.type`
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Lz4 => "lz4",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+/// Kafka sink configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KafkaConfig {
+    /// Comma-separated bootstrap servers (KAFKA_BROKERS when empty)
+    pub brokers: String,
+
+    /// Topic receiving the posts (KAFKA_TOPIC when empty)
+    pub topic: String,
+
+    pub compression: KafkaCompression,
+
+    /// How long one message may wait for delivery before failing
+    pub message_timeout_secs: u64,
+
+    /// How long shutdown waits for queued messages
+    pub flush_timeout_secs: u64,
+}
+
+impl Default for KafkaConfig {
+    fn default() -> Self {
+        Self {
+            brokers: String::new(),
+            topic: String::new(),
+            compression: KafkaCompression::default(),
+            message_timeout_secs: 30,
+            flush_timeout_secs: 10,
+        }
+    }
+}
+
+impl KafkaConfig {
+    /// Settings the sink can't run without
+    pub fn config_requirements() -> Vec<ConfigRequirement> {
+        vec![
+            ConfigRequirement {
+                key: "KAFKA_BROKERS".to_string(),
+                description: "Comma-separated Kafka bootstrap servers".to_string(),
+                required: true,
+                example: Some("kafka-1.internal:9092,kafka-2.internal:9092".to_string()),
+                source_url: None,
+            },
+            ConfigRequirement {
+                key: "KAFKA_TOPIC".to_string(),
+                description: "Topic that ingested posts are published to".to_string(),
+                required: true,
+                example: Some("sentinel.posts.raw".to_string()),
+                source_url: None,
+            },
+        ]
+    }
+
+    /// Requirements met by neither this config nor the environment
+    pub fn missing_requirements(&self) -> Vec<ConfigRequirement> {
+        self.missing_requirements_with(env)
+    }
+
+    fn missing_requirements_with(&self, env: impl Fn(&str) -> Option<String>) -> Vec<ConfigRequirement> {
+        Self::config_requirements()
+            .into_iter()
+            .filter(|requirement| {
+                let configured = match requirement.key.as_str() {
+                    "KAFKA_BROKERS" => &self.brokers,
+                    _ => &self.topic,
+                };
+                configured.trim().is_empty() && env(&requirement.key).is_none()
+            })
+            .collect()
+    }
+
+    /// Fill empty brokers/topic from the environment, failing if still missing
+    fn resolved(mut self) -> Result<Self> {
+        if self.brokers.trim().is_empty() {
+            self.brokers = env("KAFKA_BROKERS").context("Kafka sink needs brokers (set KAFKA_BROKERS)")?;
+        }
+        if self.topic.trim().is_empty() {
+            self.topic = env("KAFKA_TOPIC").context("Kafka sink needs a topic (set KAFKA_TOPIC)")?;
+        }
+
+        Ok(self)
+    }
+}
+
+/// Non-blank value of an environment variable
+fn env(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+/// A serialized post ready to publish
+#[derive(Debug, Clone, PartialEq)]
+pub struct KafkaMessage {
+    pub key: String,
+    pub payload: Vec<u8>,
+
+    /// Milliseconds since the Unix epoch
+    pub timestamp_ms: i64,
+}
+
+impl KafkaMessage {
+    /// Message for one post, keyed by its canonical id
+    pub fn from_post(post: &SocialPost) -> Result<Self> {
+        Ok(Self {
+            key: post.canonical_id(),
+            payload: serde_json::to_vec(post)?,
+            timestamp_ms: post.created_at.timestamp_millis(),
+        })
+    }
+}
+
+/// Publishes messages to a topic; the seam between KafkaSink and rdkafka
+#[async_trait]
+pub trait MessageProducer: Send + Sync {
+    /// Publish one message, returning once the broker acknowledged it
+    async fn send(&self, topic: &str, message: &KafkaMessage) -> Result<()>;
+
+    /// Wait up to `timeout` for queued messages to be delivered
+    async fn flush(&self, timeout: Duration) -> Result<()>;
+}
+
+/// MessageProducer backed by an rdkafka FutureProducer
+pub struct RdKafkaProducer {
+    producer: FutureProducer,
+    message_timeout: Duration,
+}
+
+impl RdKafkaProducer {
+    pub fn new(config: &KafkaConfig) -> Result<Self> {
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", &config.brokers)
+            .set("compression.type", config.compression.as_str())
+            .set("message.timeout.ms", (config.message_timeout_secs * 1000).to_string())
+            .create()
+            .context("Failed to create Kafka producer")?;
+
+        Ok(Self { producer, message_timeout: Duration::from_secs(config.message_timeout_secs) })
+    }
+}
+
+#[async_trait]
+impl MessageProducer for RdKafkaProducer {
+    async fn send(&self, topic: &str, message: &KafkaMessage) -> Result<()> {
+        let record = FutureRecord::to(topic)
+            .key(&message.key)
+            .payload(&message.payload)
+            .timestamp(message.timestamp_ms);
+
+        self.producer
+            .send(record, Timeout::After(self.message_timeout))
+            .await
+            .map(|_| ())
+            .map_err(|(e, _)| anyhow!("Failed to publish {} to Kafka: {}", message.key, e))
+    }
+
+    async fn flush(&self, timeout: Duration) -> Result<()> {
+        // librdkafka's flush blocks the calling thread
+        let producer = self.producer.clone();
+        tokio::task::spawn_blocking(move || producer.flush(Timeout::After(timeout)))
+            .await?
+            .context("Timed out flushing Kafka producer")
+    }
+}
+
+/// Publishes each ingested post to a Kafka topic
+pub struct KafkaSink {
+    producer: Arc<dyn MessageProducer>,
+    topic: String,
+    flush_timeout: Duration,
+}
+
+impl KafkaSink {
+    /// Create a sink with an rdkafka producer
+    pub fn new(config: KafkaConfig) -> Result<Self> {
+        let config = config.resolved()?;
+        let producer = Arc::new(RdKafkaProducer::new(&config)?);
+        info!("Publishing posts to Kafka topic {} on {}", config.topic, config.brokers);
+        Ok(Self::with_producer(producer, &config))
+    }
+
+    /// Create a sink publishing through the given producer
+    pub fn with_producer(producer: Arc<dyn MessageProducer>, config: &KafkaConfig) -> Self {
+        Self {
+            producer,
+            topic: config.topic.clone(),
+            flush_timeout: Duration::from_secs(config.flush_timeout_secs),
+        }
+    }
+}
+
+#[async_trait]
+impl Sink for KafkaSink {
+    fn name(&self) -> &str {
+        "kafka"
+    }
+
+    async fn consume(&self, posts: &[SocialPost]) -> Result<()> {
+        let messages = posts.iter().map(KafkaMessage::from_post).collect::<Result<Vec<_>>>()?;
+
+        let results = join_all(messages.iter().map(|message| self.producer.send(&self.topic, message))).await;
+        let failed = results.iter().filter(|result| result.is_err()).count();
+        if let Some(Err(e)) = results.into_iter().find(Result::is_err) {
+            return Err(e.context(format!("{} of {} posts not published", failed, posts.len())));
+        }
+
+        debug!("Published {} posts to Kafka topic {}", posts.len(), self.topic);
+        Ok(())
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.producer.flush(self.flush_timeout).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils::post;
+    use std::sync::Mutex;
+
+    /// Producer recording what it was asked to publish
+    #[derive(Default)]
+    struct MockProducer {
+        sent: Mutex<Vec<(String, KafkaMessage)>>,
+        flushed: Mutex<Vec<Duration>>,
+    }
+
+    #[async_trait]
+    impl MessageProducer for MockProducer {
+        async fn send(&self, topic: &str, message: &KafkaMessage) -> Result<()> {
+            self.sent.lock().unwrap().push((topic.to_string(), message.clone()));
+            Ok(())
+        }
+
+        async fn flush(&self, timeout: Duration) -> Result<()> {
+            self.flushed.lock().unwrap().push(timeout);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_messages_keyed_by_platform_and_id() {
+        let producer = Arc::new(MockProducer::default());
+        let config = KafkaConfig { topic: "sentinel.posts".to_string(), ..KafkaConfig::default() };
+        let sink = KafkaSink::with_producer(producer.clone(), &config);
+
+        let mut tiktok = post("7341", "second");
+        tiktok.platform = "tiktok".to_string();
+        sink.consume(&[post("abc123", "first"), tiktok.clone()]).await.unwrap();
+        sink.flush().await.unwrap();
+
+        let sent = producer.sent.lock().unwrap().clone();
+        let keys: Vec<&str> = sent.iter().map(|(_, message)| message.key.as_str()).collect();
+        assert_eq!(keys, vec!["reddit:abc123", "tiktok:7341"]);
+        assert!(sent.iter().all(|(topic, _)| topic == "sentinel.posts"));
+
+        let (_, message) = &sent[1];
+        assert_eq!(message.timestamp_ms, tiktok.created_at.timestamp_millis());
+        let payload: SocialPost = serde_json::from_slice(&message.payload).unwrap();
+        assert_eq!(payload.content, "second");
+
+        assert_eq!(*producer.flushed.lock().unwrap(), vec![Duration::from_secs(10)]);
+    }
+
+    #[test]
+    fn test_missing_requirements_fall_back_to_environment() {
+        let config = KafkaConfig { topic: "sentinel.posts".to_string(), ..KafkaConfig::default() };
+
+        let missing = config.missing_requirements_with(|_| None);
+        let keys: Vec<&str> = missing.iter().map(|requirement| requirement.key.as_str()).collect();
+        assert_eq!(keys, vec!["KAFKA_BROKERS"]);
+
+        let from_env = |name: &str| (name == "KAFKA_BROKERS").then(|| "kafka-1:9092".to_string());
+        assert!(config.missing_requirements_with(from_env).is_empty());
+    }
+}
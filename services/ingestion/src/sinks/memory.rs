@@ -0,0 +1,45 @@
+/*
+ * =============================================================================
+ * In-Memory Sink for SentinelBERT
+ * =============================================================================
+ *
+ * Collects every consumed post in memory. Used by tests and dry runs to
+ * observe exactly what the pipeline emitted.
+ *
+ * =============================================================================
+ */
+
+use super::Sink;
+use crate::api_connectors::SocialPost;
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+/// Sink that keeps consumed posts in memory
+#[derive(Default)]
+pub struct MemorySink {
+    posts: Mutex<Vec<SocialPost>>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of all consumed posts, in arrival order
+    pub async fn posts(&self) -> Vec<SocialPost> {
+        self.posts.lock().await.clone()
+    }
+}
+
+#[async_trait]
+impl Sink for MemorySink {
+    fn name(&self) -> &str {
+        "memory"
+    }
+
+    async fn consume(&self, posts: &[SocialPost]) -> Result<()> {
+        self.posts.lock().await.extend_from_slice(posts);
+        Ok(())
+    }
+}
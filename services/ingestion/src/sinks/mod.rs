@@ -0,0 +1,69 @@
+/*
+ * =============================================================================
+ * SentinelBERT Output Sinks Module
+ * =============================================================================
+ * 
+ * This module provides destinations that receive normalized posts as they are
+ * ingested. Every destination implements the common Sink trait so the
+ * ingestion pipeline can fan the same batch out to a configured list of sinks.
+ * 
+ * Supported Sinks:
+ * - PostStore (via StoreSink)
+ * - Webhook (HTTP POST with optional HMAC-SHA256 signature)
+ * - Kafka (one JSON message per post, keyed by platform and id)
+ * - In-memory (tests and dry runs)
+ * 
+ * =============================================================================
+ */
+
+use crate::api_connectors::SocialPost;
+use crate::storage::PostStore;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub mod kafka;
+pub mod memory;
+pub mod webhook;
+
+// Re-export sink structs for easy access
+pub use kafka::{KafkaConfig, KafkaSink};
+pub use memory::MemorySink;
+pub use webhook::{WebhookConfig, WebhookSink};
+
+/// Common trait for all post destinations
+#[async_trait]
+pub trait Sink: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &str;
+
+    /// Consume a batch of posts
+    async fn consume(&self, posts: &[SocialPost]) -> Result<()>;
+
+    /// Deliver anything still buffered; called once during shutdown
+    async fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Adapts a PostStore so it can sit in the sink list
+pub struct StoreSink {
+    store: Arc<dyn PostStore>,
+}
+
+impl StoreSink {
+    pub fn new(store: Arc<dyn PostStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl Sink for StoreSink {
+    fn name(&self) -> &str {
+        "store"
+    }
+
+    async fn consume(&self, posts: &[SocialPost]) -> Result<()> {
+        self.store.save_posts(posts).await
+    }
+}
@@ -0,0 +1,247 @@
+/*
+ * =============================================================================
+ * Webhook Sink for SentinelBERT
+ * =============================================================================
+ *
+ * This module pushes batches of ingested posts to an external HTTP endpoint
+ * (e.g. a case-management system) as a JSON array.
+ *
+ * Delivery Semantics:
+ * - One POST per batch with `Content-Type: application/json`
+ * - `X-Event-Type` header identifies the event to the receiver
+ * - Optional `X-Signature-256: sha256=<hex>` HMAC over the raw body
+ * - 5xx responses and network errors are retried with exponential backoff
+ * - Batches still failing after max attempts are dropped and logged
+ *
+ * Verifying Signatures (receiver side):
+ * 1. Compute HMAC-SHA256 over the exact request body using the shared secret
+ * 2. Hex-encode it and compare against the header value after `sha256=`
+ *
+ * =============================================================================
+ */
+
+use super::Sink;
+use crate::api_connectors::SocialPost;
+use anyhow::Result;
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{debug, error, warn};
+
+/// Header carrying the event type
+pub const EVENT_TYPE_HEADER: &str = "X-Event-Type";
+
+/// Header carrying the HMAC-SHA256 body signature
+pub const SIGNATURE_HEADER: &str = "X-Signature-256";
+
+/// Webhook sink configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    /// Endpoint receiving the POSTed batches
+    pub url: String,
+
+    /// Shared secret for the HMAC-SHA256 signature (unsigned if absent)
+    pub secret: Option<String>,
+
+    /// Value sent in the X-Event-Type header
+    pub event_type: String,
+
+    /// Total delivery attempts per batch, including the first
+    pub max_attempts: u32,
+
+    /// Delay before the first retry; doubled on each subsequent retry
+    pub initial_backoff_ms: u64,
+
+    /// Per-request timeout in seconds
+    pub timeout_secs: u64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            secret: None,
+            event_type: "posts.ingested".to_string(),
+            max_attempts: 3,
+            initial_backoff_ms: 500,
+            timeout_secs: 10,
+        }
+    }
+}
+
+/// Pushes post batches to an external HTTP endpoint
+pub struct WebhookSink {
+    /// HTTP client for webhook requests
+    client: Client,
+
+    /// Sink configuration
+    config: WebhookConfig,
+}
+
+impl WebhookSink {
+    /// Create a new webhook sink
+    pub fn new(config: WebhookConfig) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Compute the signature header value for a request body
+    pub fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    /// Deliver a batch of posts
+    ///
+    /// Returns `Ok(true)` when the endpoint accepted the batch and `Ok(false)`
+    /// when it was dropped after exhausting retries or being rejected with a
+    /// non-retryable status. Errors are only returned for serialization failures.
+    pub async fn send(&self, posts: &[SocialPost]) -> Result<bool> {
+        if posts.is_empty() {
+            return Ok(true);
+        }
+
+        let body = serde_json::to_vec(posts)?;
+        let signature = self.config.secret.as_deref().map(|secret| Self::sign(secret, &body));
+
+        let mut backoff = Duration::from_millis(self.config.initial_backoff_ms);
+        let max_attempts = self.config.max_attempts.max(1);
+
+        for attempt in 1..=max_attempts {
+            let mut request = self.client
+                .post(&self.config.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(EVENT_TYPE_HEADER, &self.config.event_type)
+                .body(body.clone());
+
+            if let Some(signature) = &signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+
+            match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    debug!("Delivered {} posts to webhook {}", posts.len(), self.config.url);
+                    return Ok(true);
+                },
+                Ok(response) if response.status().is_server_error() => {
+                    warn!(
+                        "Webhook {} returned {} (attempt {}/{})",
+                        self.config.url, response.status(), attempt, max_attempts
+                    );
+                },
+                Ok(response) => {
+                    // 4xx won't succeed on retry
+                    error!(
+                        "Webhook {} rejected batch with {}, dropping {} posts",
+                        self.config.url, response.status(), posts.len()
+                    );
+                    return Ok(false);
+                },
+                Err(e) => {
+                    warn!(
+                        "Webhook {} request failed: {} (attempt {}/{})",
+                        self.config.url, e, attempt, max_attempts
+                    );
+                }
+            }
+
+            if attempt < max_attempts {
+                sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+
+        error!(
+            "Webhook {} failed after {} attempts, dropping {} posts",
+            self.config.url, max_attempts, posts.len()
+        );
+        Ok(false)
+    }
+}
+
+#[async_trait]
+impl Sink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    /// Dropped batches are already logged by `send`, so they don't fail the pipeline
+    async fn consume(&self, posts: &[SocialPost]) -> Result<()> {
+        self.send(posts).await.map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn post(id: &str) -> SocialPost {
+        test_utils::post(id, &format!("post {}", id))
+    }
+
+    fn config(url: String) -> WebhookConfig {
+        WebhookConfig {
+            url,
+            secret: Some("shared-secret".to_string()),
+            initial_backoff_ms: 1,
+            ..WebhookConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_signature_header() {
+        let server = MockServer::start().await;
+        let posts = vec![post("1"), post("2")];
+        let body = serde_json::to_vec(&posts).unwrap();
+        let expected_signature = WebhookSink::sign("shared-secret", &body);
+
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(header(SIGNATURE_HEADER, expected_signature.as_str()))
+            .and(header(EVENT_TYPE_HEADER, "posts.ingested"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let sink = WebhookSink::new(config(format!("{}/hook", server.uri()))).unwrap();
+        assert!(sink.send(&posts).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_retries_server_errors_then_drops() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let sink = WebhookSink::new(config(format!("{}/hook", server.uri()))).unwrap();
+        assert!(!sink.send(&[post("1")]).await.unwrap());
+    }
+
+    #[test]
+    fn test_sign_known_vector() {
+        // RFC 4231 test case 2
+        let signature = WebhookSink::sign("Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            signature,
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
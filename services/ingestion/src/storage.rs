@@ -0,0 +1,199 @@
+/*
+ * SentinelBERT Post Storage
+ *
+ * This module persists normalized posts produced by the API connectors.
+ * Storage backends implement the PostStore trait so the ingestion service
+ * can work against PostgreSQL in production and an in-memory store in tests.
+ *
+ * Key Design Principles:
+ * - Posts are keyed by canonical id ("{platform}:{post id}") so
+ *   re-ingestion upserts
+ * - Every stored post is indexed by its content hash for deduplication
+ * - Posts carry the id of the job that last collected them (an
+ *   IngestionJob or any other job's `job_id`; metadata["job_id"] and the
+ *   indexed job_id column), so a job's data can be retracted
+ * - Backends are interchangeable behind a single async trait
+ *
+ * Schema: sql/ingestion_posts_schema.sql
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use anyhow::Result;                             // Simplified error handling with context
+use async_trait::async_trait;                   // Async methods in traits
+use sqlx::postgres::{PgPool, PgPoolOptions};    // PostgreSQL connection pooling
+use std::collections::{HashMap, HashSet};       // In-memory indexes
+use tokio::sync::RwLock;                        // Async-aware interior mutability
+use uuid::Uuid;                                 // Ingestion job ids
+
+use crate::api_connectors::SocialPost;
+use crate::dedup::content_hash;
+
+/**
+ * PostStore - Persistence backend for normalized posts
+ *
+ * Implementations must keep a content hash index so that
+ * `contains_content_hash` is a cheap lookup rather than a scan.
+ */
+#[async_trait]
+pub trait PostStore: Send + Sync {
+    /// Insert or update the given posts
+    async fn save_posts(&self, posts: &[SocialPost]) -> Result<()>;
+
+    /// Check whether any stored post has the given content hash
+    async fn contains_content_hash(&self, hash: &str) -> Result<bool>;
+
+    /// Delete every post collected by an ingestion job, returning how many
+    async fn delete_by_job(&self, job_id: Uuid) -> Result<u64>;
+}
+
+/**
+ * InMemoryPostStore - Process-local store used for tests and dry runs
+ */
+#[derive(Default)]
+pub struct InMemoryPostStore {
+    /// Posts keyed by canonical id
+    posts: RwLock<HashMap<String, SocialPost>>,
+
+    /// Content hash index over stored posts
+    content_hashes: RwLock<HashSet<String>>,
+}
+
+impl InMemoryPostStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of all stored posts
+    pub async fn posts(&self) -> Vec<SocialPost> {
+        self.posts.read().await.values().cloned().collect()
+    }
+
+    /// Number of stored posts
+    pub async fn len(&self) -> usize {
+        self.posts.read().await.len()
+    }
+
+    /// Whether no posts have been stored
+    pub async fn is_empty(&self) -> bool {
+        self.posts.read().await.is_empty()
+    }
+}
+
+#[async_trait]
+impl PostStore for InMemoryPostStore {
+    async fn save_posts(&self, posts: &[SocialPost]) -> Result<()> {
+        let mut stored = self.posts.write().await;
+        let mut hashes = self.content_hashes.write().await;
+
+        for post in posts {
+            hashes.insert(content_hash(post));
+            stored.insert(post.canonical_id(), post.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn contains_content_hash(&self, hash: &str) -> Result<bool> {
+        Ok(self.content_hashes.read().await.contains(hash))
+    }
+
+    async fn delete_by_job(&self, job_id: Uuid) -> Result<u64> {
+        let mut stored = self.posts.write().await;
+        let before = stored.len();
+        stored.retain(|_, post| post.job_id() != Some(job_id));
+
+        // Other posts may share a deleted post's content hash
+        *self.content_hashes.write().await = stored.values().map(content_hash).collect();
+        Ok((before - stored.len()) as u64)
+    }
+}
+
+/**
+ * PostgresPostStore - Production store backed by the ingested_posts table
+ *
+ * The content_hash column carries a hash index so persistent
+ * deduplication checks stay O(1) as the table grows.
+ */
+pub struct PostgresPostStore {
+    pool: PgPool,
+}
+
+impl PostgresPostStore {
+    /// Wrap an existing connection pool
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Connect to PostgreSQL using a connection URL
+    pub async fn connect(database_url: &str, max_connections: u32) -> Result<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(max_connections)
+            .connect(database_url)
+            .await?;
+
+        Ok(Self::new(pool))
+    }
+
+    /// Underlying connection pool, for stores sharing the database
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+}
+
+#[async_trait]
+impl PostStore for PostgresPostStore {
+    async fn save_posts(&self, posts: &[SocialPost]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for post in posts {
+            sqlx::query(
+                "INSERT INTO ingested_posts \
+                 (canonical_id, platform, post_id, content, content_hash, author_id_hash, created_at, job_id, payload) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9::jsonb) \
+                 ON CONFLICT (canonical_id) DO UPDATE SET \
+                 content = EXCLUDED.content, \
+                 content_hash = EXCLUDED.content_hash, \
+                 job_id = EXCLUDED.job_id, \
+                 payload = EXCLUDED.payload"
+            )
+            .bind(post.canonical_id())
+            .bind(&post.platform)
+            .bind(post.native_id())
+            .bind(&post.content)
+            .bind(content_hash(post))
+            .bind(&post.author.id_hash)
+            .bind(post.created_at)
+            .bind(post.job_id())
+            .bind(serde_json::to_string(post)?)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn contains_content_hash(&self, hash: &str) -> Result<bool> {
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM ingested_posts WHERE content_hash = $1)"
+        )
+        .bind(hash)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    async fn delete_by_job(&self, job_id: Uuid) -> Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM ingested_posts WHERE job_id = $1"
+        )
+        .bind(job_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
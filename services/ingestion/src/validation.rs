@@ -0,0 +1,287 @@
+/*
+ * SentinelBERT Credential Validation
+ *
+ * This module backs the `validate` CLI subcommand: it checks every configured
+ * connector's credentials without starting ingestion, so a deployment can be
+ * verified (e.g. in CI) before real collection begins.
+ *
+ * Each connector makes exactly one validate_credentials call; connectors
+ * without credentials are reported as invalid without touching the network.
+ *
+ * A configured Kafka sink is checked for its required settings too, so a
+ * missing broker list or topic fails validation rather than startup.
+ *
+ * The `status` subcommand adds each connector's remaining quota to the
+ * credential check (see IngestionService::status_report). Connectors that
+ * can't report their rate limit show no quota rather than failing.
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use chrono::{DateTime, Utc};        // Rate limit reset times
+use futures::future::join_all;      // Validate all connectors concurrently
+use serde::Serialize;               // Machine-readable (--json) output
+
+use crate::api_connectors::{ApiConnector, ConfiguredConnector};
+use crate::sinks::KafkaConfig;
+
+/// Outcome of validating a single connector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationStatus {
+    /// Credentials were accepted
+    Valid,
+
+    /// Credentials are missing or were rejected
+    Invalid,
+
+    /// Validation could not complete (network, API error, ...)
+    Error,
+}
+
+/// Validation report row for one connector
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationResult {
+    pub platform: String,
+    pub required: bool,
+    pub status: ValidationStatus,
+
+    /// Reason for an invalid or error status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl ValidationResult {
+    /// Whether this result should fail the validation run
+    pub fn is_blocking(&self) -> bool {
+        self.required && self.status != ValidationStatus::Valid
+    }
+}
+
+/// Validate every connector's credentials concurrently
+pub async fn validate_connectors(connectors: &[ConfiguredConnector]) -> Vec<ValidationResult> {
+    join_all(connectors.iter().map(|entry| async move {
+        let (status, message) = validate_one(entry.connector.as_ref()).await;
+        ValidationResult {
+            platform: entry.name.clone(),
+            required: entry.required,
+            status,
+            message,
+        }
+    }))
+    .await
+}
+
+async fn validate_one(connector: &dyn ApiConnector) -> (ValidationStatus, Option<String>) {
+    if !connector.is_configured() {
+        return (ValidationStatus::Invalid, Some("missing credentials".to_string()));
+    }
+
+    match connector.validate_credentials().await {
+        Ok(true) => (ValidationStatus::Valid, None),
+        Ok(false) => (ValidationStatus::Invalid, Some("credentials rejected".to_string())),
+        Err(e) => (ValidationStatus::Error, Some(e.to_string())),
+    }
+}
+
+/// Validation row for the Kafka sink, invalid while a required setting is
+/// missing from both the config and the environment
+pub fn validate_kafka(config: &KafkaConfig) -> ValidationResult {
+    let missing = config.missing_requirements();
+    let message = (!missing.is_empty()).then(|| {
+        missing
+            .iter()
+            .map(|requirement| format!("missing {} ({})", requirement.key, requirement.description))
+            .collect::<Vec<_>>()
+            .join("; ")
+    });
+
+    ValidationResult {
+        platform: "kafka".to_string(),
+        required: true,
+        status: if missing.is_empty() { ValidationStatus::Valid } else { ValidationStatus::Invalid },
+        message,
+    }
+}
+
+/// Credential and quota snapshot for one connector
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectorStatus {
+    pub platform: String,
+    pub configured: bool,
+
+    /// Whether the credentials were accepted (None if the check couldn't run)
+    pub valid: Option<bool>,
+
+    /// Requests left in the current window (None if unknown)
+    pub remaining: Option<u32>,
+    pub limit: Option<u32>,
+    pub reset_time: Option<DateTime<Utc>>,
+
+    /// Why validity or quota are unknown
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Check one connector's credentials and rate limit concurrently
+///
+/// Unconfigured connectors skip the credential check. Rate limits come
+/// from the connector's tracked (or default) state where it has no live
+/// query, and are left unknown if it can't report one at all.
+pub async fn connector_status(connector: &dyn ApiConnector) -> ConnectorStatus {
+    let configured = connector.is_configured();
+    let validity = async {
+        match configured {
+            true => Some(connector.validate_credentials().await),
+            false => None,
+        }
+    };
+    let (validity, rate_limit) = futures::join!(validity, connector.get_rate_limit_status());
+
+    let mut messages = Vec::new();
+    let valid = match validity {
+        None => {
+            messages.push("missing credentials".to_string());
+            None
+        },
+        Some(Ok(valid)) => Some(valid),
+        Some(Err(e)) => {
+            messages.push(e.to_string());
+            None
+        },
+    };
+    let rate_limit = rate_limit
+        .map_err(|e| messages.push(format!("rate limit unavailable: {}", e)))
+        .ok();
+
+    ConnectorStatus {
+        platform: connector.platform_name().to_string(),
+        configured,
+        valid,
+        remaining: rate_limit.as_ref().map(|info| info.remaining),
+        limit: rate_limit.as_ref().map(|info| info.limit),
+        reset_time: rate_limit.map(|info| info.reset_time),
+        message: (!messages.is_empty()).then(|| messages.join("; ")),
+    }
+}
+
+/// Render a status report as a plain-text table for terminal output
+pub fn render_status_table(statuses: &[ConnectorStatus]) -> String {
+    let width = statuses
+        .iter()
+        .map(|s| s.platform.len())
+        .chain(std::iter::once("PLATFORM".len()))
+        .max()
+        .unwrap_or_default();
+    let unknown = || "-".to_string();
+
+    let mut table = format!(
+        "{:<width$}  {:<10}  {:<5}  {:>9}  {:>9}  {:<20}  DETAIL\n",
+        "PLATFORM", "CONFIGURED", "VALID", "REMAINING", "LIMIT", "RESET"
+    );
+    for status in statuses {
+        table.push_str(&format!(
+            "{:<width$}  {:<10}  {:<5}  {:>9}  {:>9}  {:<20}  {}\n",
+            status.platform,
+            if status.configured { "yes" } else { "no" },
+            status.valid.map_or_else(unknown, |valid| if valid { "yes" } else { "no" }.to_string()),
+            status.remaining.map_or_else(unknown, |remaining| remaining.to_string()),
+            status.limit.map_or_else(unknown, |limit| limit.to_string()),
+            status.reset_time.map_or_else(unknown, |reset| reset.format("%Y-%m-%d %H:%M:%S").to_string()),
+            status.message.as_deref().unwrap_or("")
+        ));
+    }
+
+    table
+}
+
+/// Whether any required connector failed validation
+pub fn has_blocking_failures(results: &[ValidationResult]) -> bool {
+    results.iter().any(ValidationResult::is_blocking)
+}
+
+/// Render results as a plain-text table for terminal output
+pub fn render_table(results: &[ValidationResult]) -> String {
+    let width = results
+        .iter()
+        .map(|r| r.platform.len())
+        .chain(std::iter::once("PLATFORM".len()))
+        .max()
+        .unwrap_or_default();
+
+    let mut table = format!("{:<width$}  {:<8}  {:<7}  DETAIL\n", "PLATFORM", "REQUIRED", "STATUS");
+    for result in results {
+        let status = match result.status {
+            ValidationStatus::Valid => "valid",
+            ValidationStatus::Invalid => "invalid",
+            ValidationStatus::Error => "error",
+        };
+
+        table.push_str(&format!(
+            "{:<width$}  {:<8}  {:<7}  {}\n",
+            result.platform,
+            if result.required { "yes" } else { "no" },
+            status,
+            result.message.as_deref().unwrap_or("")
+        ));
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils::MockConnector;
+    use crate::api_connectors::*;
+    use std::sync::Arc;
+
+    fn entry(name: &str, required: bool, configured: bool, outcome: fn() -> Result<bool, ConnectorError>) -> ConfiguredConnector {
+        let connector = MockConnector::new("fake").with_validation(outcome);
+        ConfiguredConnector {
+            name: name.to_string(),
+            required,
+            connector: Arc::new(if configured { connector } else { connector.unconfigured() }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validation_statuses() {
+        let connectors = vec![
+            entry("twitter", true, true, || Ok(true)),
+            entry("reddit", true, true, || Ok(false)),
+            entry("youtube", false, true, || Err(ConnectorError::Generic { message: "timeout".to_string() })),
+            entry("telegram", false, false, || panic!("unconfigured connectors must not be called")),
+        ];
+
+        let results = validate_connectors(&connectors).await;
+        let statuses: Vec<ValidationStatus> = results.iter().map(|r| r.status).collect();
+
+        assert_eq!(statuses, vec![
+            ValidationStatus::Valid,
+            ValidationStatus::Invalid,
+            ValidationStatus::Error,
+            ValidationStatus::Invalid,
+        ]);
+        assert_eq!(results[3].message.as_deref(), Some("missing credentials"));
+
+        let json = serde_json::to_value(&results[0]).unwrap();
+        assert_eq!(json, serde_json::json!({ "platform": "twitter", "required": true, "status": "valid" }));
+    }
+
+    #[tokio::test]
+    async fn test_only_required_failures_block() {
+        let optional_failures = vec![
+            entry("twitter", true, true, || Ok(true)),
+            entry("youtube", false, true, || Ok(false)),
+        ];
+        let results = validate_connectors(&optional_failures).await;
+        assert!(!has_blocking_failures(&results));
+
+        let required_failure = vec![entry("reddit", true, false, || Ok(true))];
+        let results = validate_connectors(&required_failure).await;
+        assert!(has_blocking_failures(&results));
+        assert!(render_table(&results).contains("reddit    yes       invalid  missing credentials"));
+    }
+}
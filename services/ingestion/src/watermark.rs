@@ -0,0 +1,395 @@
+/*
+ * SentinelBERT Ingestion Watermarks
+ *
+ * This module tracks how far each scheduled query has been collected, so a
+ * polling run only asks the platforms for posts newer than the last run
+ * instead of re-fetching (and re-deduplicating) the same window every cycle.
+ *
+ * Watermarks:
+ * - One watermark per (platform, query hash), holding the newest post id
+ *   and timestamp seen
+ * - The post id is passed to connectors as `SearchParams::since_id`
+ *   (Twitter `since_id`, Reddit `before`)
+ * - Results are also filtered against the timestamp, for platforms that
+ *   ignore the cursor
+ *
+ * Schema: ingestion_watermarks (platform, query_hash) primary key
+ *
+ * Runs without the database (e.g. cron exports with `--state-file`) keep
+ * watermarks in a JSON file instead, see FileWatermarkStore. The same file
+ * carries the platforms' quota usage (quota.rs) between runs.
+ *
+ * Author: SentinelBERT Team
+ * License: MIT
+ */
+
+use anyhow::Result;                     // Simplified error handling with context
+use async_trait::async_trait;           // Async methods in traits
+use chrono::{DateTime, Utc};            // Post timestamps
+use serde::{Deserialize, Serialize};    // Watermark (de)serialization
+use sha2::{Digest, Sha256};             // Stable query hashing
+use sqlx::postgres::PgPool;             // PostgreSQL persistence
+use std::collections::HashMap;          // In-memory watermarks
+use std::path::{Path, PathBuf};         // State file location
+use std::sync::Arc;                     // Shared quota tracker
+use tokio::io::AsyncWriteExt;           // State file writes
+use tokio::sync::RwLock;                // Async-aware interior mutability
+use tracing::warn;                      // Unreadable state file reports
+
+use crate::api_connectors::SocialPost;
+use crate::quota::{DailyQuota, QuotaEntry};
+
+/// Newest post collected for one platform and query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Watermark {
+    /// Native id, as connectors expect it in `since_id`
+    pub post_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Watermark {
+    /// Watermark for the newest of `posts`, if any
+    pub fn newest(posts: &[SocialPost]) -> Option<Self> {
+        posts
+            .iter()
+            .max_by_key(|post| post.created_at)
+            .map(|post| Self { post_id: post.native_id().to_string(), created_at: post.created_at })
+    }
+
+    /// Whether a post was collected after this watermark
+    ///
+    /// Posts sharing the watermark's timestamp are kept unless they are the
+    /// watermark post itself, since several posts can share a second.
+    pub fn precedes(&self, post: &SocialPost) -> bool {
+        post.created_at > self.created_at
+            || (post.created_at == self.created_at && post.native_id() != self.post_id)
+    }
+
+    /// The later of this watermark and the newest of `posts`
+    pub fn advance(current: Option<Self>, posts: &[SocialPost]) -> Option<Self> {
+        match (current, Self::newest(posts)) {
+            (Some(current), Some(newest)) if newest.created_at <= current.created_at => Some(current),
+            (current, newest) => newest.or(current),
+        }
+    }
+}
+
+/// Stable key for a query string
+pub fn query_hash(query: &str) -> String {
+    hex::encode(Sha256::digest(query.trim().as_bytes()))
+}
+
+/**
+ * WatermarkStore - Persistence backend for collection watermarks
+ */
+#[async_trait]
+pub trait WatermarkStore: Send + Sync {
+    /// Watermark for a platform and query hash, if one has been saved
+    async fn load(&self, platform: &str, query_hash: &str) -> Result<Option<Watermark>>;
+
+    /// Insert or replace the watermark for a platform and query hash
+    async fn save(&self, platform: &str, query_hash: &str, watermark: &Watermark) -> Result<()>;
+}
+
+/**
+ * InMemoryWatermarkStore - Process-local store used for tests and dry runs
+ */
+#[derive(Default)]
+pub struct InMemoryWatermarkStore {
+    watermarks: RwLock<HashMap<(String, String), Watermark>>,
+}
+
+impl InMemoryWatermarkStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl WatermarkStore for InMemoryWatermarkStore {
+    async fn load(&self, platform: &str, query_hash: &str) -> Result<Option<Watermark>> {
+        let key = (platform.to_string(), query_hash.to_string());
+        Ok(self.watermarks.read().await.get(&key).cloned())
+    }
+
+    async fn save(&self, platform: &str, query_hash: &str, watermark: &Watermark) -> Result<()> {
+        let key = (platform.to_string(), query_hash.to_string());
+        self.watermarks.write().await.insert(key, watermark.clone());
+        Ok(())
+    }
+}
+
+/// One watermark as written to a state file
+#[derive(Debug, Serialize, Deserialize)]
+struct StateEntry {
+    platform: String,
+    query_hash: String,
+    #[serde(flatten)]
+    watermark: Watermark,
+}
+
+/// Everything a state file holds
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StateFile {
+    watermarks: Vec<StateEntry>,
+    #[serde(default)]
+    quotas: Vec<QuotaEntry>,
+}
+
+/// State file as read, including files written before quotas were saved
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StoredState {
+    Current(StateFile),
+    WatermarksOnly(Vec<StateEntry>),
+}
+
+/**
+ * FileWatermarkStore - Watermarks kept in a JSON state file between runs
+ *
+ * The file is read once by `open` and written back by `persist`, normally
+ * at shutdown. It is written to a temporary name, synced to disk and
+ * renamed into place, so a crash mid-write (or a power loss right after
+ * the rename) leaves the previous state intact.
+ */
+pub struct FileWatermarkStore {
+    path: PathBuf,
+    memory: InMemoryWatermarkStore,
+
+    /// Quota usage as read from the file
+    quotas: Vec<QuotaEntry>,
+
+    /// Tracker whose usage is saved, once attached with `track_quota`
+    quota: Option<Arc<DailyQuota>>,
+}
+
+impl FileWatermarkStore {
+    /// Load the state file at `path`
+    ///
+    /// A missing or unreadable file is logged and treated as empty, so the
+    /// run starts fresh rather than failing.
+    pub async fn open(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let mut state = StateFile::default();
+
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => match serde_json::from_slice::<StoredState>(&bytes) {
+                Ok(StoredState::Current(stored)) => state = stored,
+                Ok(StoredState::WatermarksOnly(watermarks)) => state.watermarks = watermarks,
+                Err(e) => warn!("Ignoring corrupt state file {}, starting fresh: {}", path.display(), e),
+            },
+            Err(e) => warn!("Could not read state file {}, starting fresh: {}", path.display(), e),
+        }
+
+        let watermarks = state.watermarks
+            .into_iter()
+            .map(|entry| ((entry.platform, entry.query_hash), entry.watermark))
+            .collect();
+
+        Self {
+            path,
+            memory: InMemoryWatermarkStore { watermarks: RwLock::new(watermarks) },
+            quotas: state.quotas,
+            quota: None,
+        }
+    }
+
+    /// Restore the saved quota usage into `quota` and save its usage from now on
+    pub fn track_quota(&mut self, quota: Arc<DailyQuota>) {
+        quota.restore(self.quotas.clone());
+        self.quota = Some(quota);
+    }
+
+    /// Write the current watermarks and quota usage to the state file
+    pub async fn persist(&self) -> Result<()> {
+        let mut watermarks: Vec<StateEntry> = self.memory.watermarks.read().await
+            .iter()
+            .map(|((platform, query_hash), watermark)| StateEntry {
+                platform: platform.clone(),
+                query_hash: query_hash.clone(),
+                watermark: watermark.clone(),
+            })
+            .collect();
+        watermarks.sort_by(|a, b| (&a.platform, &a.query_hash).cmp(&(&b.platform, &b.query_hash)));
+
+        let state = StateFile {
+            watermarks,
+            quotas: match &self.quota {
+                Some(quota) => quota.snapshot(),
+                None => self.quotas.clone(),
+            },
+        };
+
+        let mut partial = self.path.clone().into_os_string();
+        partial.push(format!(".{}.partial", uuid::Uuid::new_v4()));
+        let mut file = tokio::fs::File::create(&partial).await?;
+        file.write_all(&serde_json::to_vec_pretty(&state)?).await?;
+        // Without the sync, the rename can reach disk before the contents
+        file.sync_all().await?;
+        drop(file);
+        tokio::fs::rename(&partial, &self.path).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WatermarkStore for FileWatermarkStore {
+    async fn load(&self, platform: &str, query_hash: &str) -> Result<Option<Watermark>> {
+        self.memory.load(platform, query_hash).await
+    }
+
+    async fn save(&self, platform: &str, query_hash: &str, watermark: &Watermark) -> Result<()> {
+        self.memory.save(platform, query_hash, watermark).await
+    }
+}
+
+/**
+ * PostgresWatermarkStore - Production store backed by the ingestion_watermarks table
+ */
+pub struct PostgresWatermarkStore {
+    pool: PgPool,
+}
+
+impl PostgresWatermarkStore {
+    /// Wrap an existing connection pool
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl WatermarkStore for PostgresWatermarkStore {
+    async fn load(&self, platform: &str, query_hash: &str) -> Result<Option<Watermark>> {
+        let row: Option<(String, DateTime<Utc>)> = sqlx::query_as(
+            "SELECT post_id, created_at FROM ingestion_watermarks \
+             WHERE platform = $1 AND query_hash = $2"
+        )
+        .bind(platform)
+        .bind(query_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|(post_id, created_at)| Watermark { post_id, created_at }))
+    }
+
+    async fn save(&self, platform: &str, query_hash: &str, watermark: &Watermark) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO ingestion_watermarks (platform, query_hash, post_id, created_at, updated_at) \
+             VALUES ($1, $2, $3, $4, NOW()) \
+             ON CONFLICT (platform, query_hash) DO UPDATE SET \
+             post_id = EXCLUDED.post_id, \
+             created_at = EXCLUDED.created_at, \
+             updated_at = EXCLUDED.updated_at"
+        )
+        .bind(platform)
+        .bind(query_hash)
+        .bind(&watermark.post_id)
+        .bind(watermark.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_connectors::test_utils::post;
+    use chrono::Duration;
+
+    #[test]
+    fn test_advance_keeps_newest_and_filters_older() {
+        let now = Utc::now();
+        let mut old = post("1", "old");
+        old.created_at = now - Duration::minutes(5);
+        let mut new = post("2", "new");
+        new.created_at = now;
+
+        let mark = Watermark::advance(None, &[old.clone(), new.clone()]).unwrap();
+        assert_eq!(mark.post_id, "2");
+
+        // An older batch never moves the watermark backwards
+        assert_eq!(Watermark::advance(Some(mark.clone()), &[old.clone()]), Some(mark.clone()));
+
+        let mut same_second = post("3", "same second");
+        same_second.created_at = now;
+        assert!(!mark.precedes(&old));
+        assert!(!mark.precedes(&new));
+        assert!(mark.precedes(&same_second));
+
+        assert_eq!(query_hash(" flood "), query_hash("flood"));
+    }
+
+    #[tokio::test]
+    async fn test_state_file_round_trips_watermarks() {
+        let path = std::env::temp_dir().join(format!("sentinel-state-{}.json", uuid::Uuid::new_v4()));
+        let mark = Watermark { post_id: "42".to_string(), created_at: Utc::now() };
+
+        // First run: no file yet
+        let state = FileWatermarkStore::open(&path).await;
+        assert_eq!(state.load("twitter", &query_hash("flood")).await.unwrap(), None);
+        state.save("twitter", &query_hash("flood"), &mark).await.unwrap();
+        state.persist().await.unwrap();
+
+        let reopened = FileWatermarkStore::open(&path).await;
+        assert_eq!(reopened.load("twitter", &query_hash("flood")).await.unwrap(), Some(mark));
+        assert_eq!(reopened.load("reddit", &query_hash("flood")).await.unwrap(), None);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_state_file_starts_fresh() {
+        let path = std::env::temp_dir().join(format!("sentinel-state-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&path, b"[{\"platform\": \"twitter\", \"query_ha").unwrap();
+
+        let state = FileWatermarkStore::open(&path).await;
+        assert_eq!(state.load("twitter", &query_hash("flood")).await.unwrap(), None);
+
+        // The next persist replaces the corrupt file
+        let mark = Watermark { post_id: "7".to_string(), created_at: Utc::now() };
+        state.save("twitter", &query_hash("flood"), &mark).await.unwrap();
+        state.persist().await.unwrap();
+        assert_eq!(FileWatermarkStore::open(&path).await.load("twitter", &query_hash("flood")).await.unwrap(), Some(mark));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_state_file_keeps_quota_usage_and_reads_old_files() {
+        use crate::quota::{QuotaConfig, QuotaUnit, QuotaWindow};
+
+        let path = std::env::temp_dir().join(format!("sentinel-state-{}.json", uuid::Uuid::new_v4()));
+        let now = Utc::now();
+        let mark = Watermark { post_id: "42".to_string(), created_at: now };
+        // Written before quota usage was saved
+        std::fs::write(&path, serde_json::to_vec(&[StateEntry {
+            platform: "twitter".to_string(),
+            query_hash: query_hash("flood"),
+            watermark: mark.clone(),
+        }]).unwrap()).unwrap();
+
+        let quota = || {
+            let quota = Arc::new(DailyQuota::new());
+            quota.set_limit("twitter", QuotaConfig { limit: 10, unit: QuotaUnit::Requests, window: QuotaWindow::Monthly });
+            quota
+        };
+
+        let mut state = FileWatermarkStore::open(&path).await;
+        assert_eq!(state.load("twitter", &query_hash("flood")).await.unwrap(), Some(mark.clone()));
+        let first_run = quota();
+        state.track_quota(Arc::clone(&first_run));
+        first_run.record_request("twitter", 1, now);
+        first_run.record_request("twitter", 1, now);
+        state.persist().await.unwrap();
+
+        let mut reopened = FileWatermarkStore::open(&path).await;
+        let second_run = quota();
+        reopened.track_quota(Arc::clone(&second_run));
+        assert_eq!(second_run.remaining("twitter", now), Some(8));
+        assert_eq!(reopened.load("twitter", &query_hash("flood")).await.unwrap(), Some(mark));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,39 @@
+-- SentinelBERT Ingestion Service Post Storage Schema
+-- Normalized posts written by the Rust ingestion service (services/ingestion)
+
+-- Ingested Posts Table
+-- canonical_id is "{platform}:{post_id}" (SocialPost::canonical_id) and is
+-- the upsert key; post_id keeps the platform's native id. job_id is the
+-- job that last collected the post (also payload metadata.job_id), for
+-- retracting a job's data
+CREATE TABLE IF NOT EXISTS ingested_posts (
+    canonical_id VARCHAR(306) PRIMARY KEY,
+    platform VARCHAR(50) NOT NULL,
+    post_id VARCHAR(255) NOT NULL,
+    content TEXT NOT NULL,
+    content_hash CHAR(64) NOT NULL,
+    author_id_hash VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    job_id UUID,
+    payload JSONB NOT NULL DEFAULT '{}'::jsonb,
+    ingested_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Tables created before canonical ids were keyed on (platform, post_id)
+ALTER TABLE ingested_posts ADD COLUMN IF NOT EXISTS canonical_id VARCHAR(306);
+UPDATE ingested_posts SET canonical_id = platform || ':' || post_id WHERE canonical_id IS NULL;
+DROP INDEX IF EXISTS idx_ingested_posts_canonical_id;
+ALTER TABLE ingested_posts DROP CONSTRAINT IF EXISTS ingested_posts_pkey;
+ALTER TABLE ingested_posts ADD PRIMARY KEY (canonical_id);
+
+-- Tables created before job ids had their own column
+ALTER TABLE ingested_posts ADD COLUMN IF NOT EXISTS job_id UUID;
+UPDATE ingested_posts SET job_id = (payload->'metadata'->>'job_id')::uuid
+WHERE job_id IS NULL AND payload->'metadata' ? 'job_id';
+
+-- Indexes for performance
+-- content_hash backs persistent deduplication lookups
+CREATE INDEX IF NOT EXISTS idx_ingested_posts_content_hash ON ingested_posts USING HASH (content_hash);
+CREATE INDEX IF NOT EXISTS idx_ingested_posts_created_at ON ingested_posts(created_at);
+-- job_id backs PostStore::delete_by_job
+CREATE INDEX IF NOT EXISTS idx_ingested_posts_job_id ON ingested_posts(job_id);